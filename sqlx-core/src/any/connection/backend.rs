@@ -1,5 +1,6 @@
 use crate::any::{Any, AnyArguments, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
 use crate::describe::Describe;
+use crate::transaction::TxOptions;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
@@ -28,6 +29,19 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// Begin a new transaction or establish a savepoint within the active transaction.
     fn begin(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
+    /// Begin a new transaction with the given options.
+    fn begin_with(&mut self, options: TxOptions) -> BoxFuture<'_, crate::Result<()>> {
+        if options == TxOptions::default() {
+            return self.begin();
+        }
+
+        Box::pin(async {
+            Err(crate::Error::Configuration(
+                "transaction options are not supported by this database".into(),
+            ))
+        })
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
     fn rollback(&mut self) -> BoxFuture<'_, crate::Result<()>>;
@@ -2,7 +2,7 @@ use futures_util::future::BoxFuture;
 
 use crate::any::{Any, AnyConnection};
 use crate::error::Error;
-use crate::transaction::TransactionManager;
+use crate::transaction::{TransactionManager, TxOptions};
 
 pub struct AnyTransactionManager;
 
@@ -13,6 +13,13 @@ impl TransactionManager for AnyTransactionManager {
         conn.backend.begin()
     }
 
+    fn begin_with(
+        conn: &mut AnyConnection,
+        options: TxOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        conn.backend.begin_with(options)
+    }
+
     fn commit(conn: &mut AnyConnection) -> BoxFuture<'_, Result<(), Error>> {
         conn.backend.commit()
     }
@@ -5,6 +5,21 @@ use hashlink::lru_cache::LruCache;
 #[derive(Debug)]
 pub struct StatementCache<T> {
     inner: LruCache<String, T>,
+    stats: StatementCacheStats,
+}
+
+/// Counters of the lookups in, and evictions from, a [`StatementCache`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StatementCacheStats {
+    /// The number of lookups that found a cached statement.
+    pub hits: u64,
+
+    /// The number of lookups that found no cached statement.
+    pub misses: u64,
+
+    /// The number of statements removed to make room for another.
+    pub evictions: u64,
 }
 
 impl<T> StatementCache<T> {
@@ -12,13 +27,22 @@ impl<T> StatementCache<T> {
     pub fn new(capacity: usize) -> Self {
         Self {
             inner: LruCache::new(capacity),
+            stats: StatementCacheStats::default(),
         }
     }
 
     /// Returns a mutable reference to the value corresponding to the given key
     /// in the cache, if any.
     pub fn get_mut(&mut self, k: &str) -> Option<&mut T> {
-        self.inner.get_mut(k)
+        let value = self.inner.get_mut(k);
+
+        if value.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+
+        value
     }
 
     /// Inserts a new statement to the cache, returning the least recently used
@@ -29,6 +53,10 @@ impl<T> StatementCache<T> {
 
         if self.capacity() == self.len() && !self.contains_key(k) {
             lru_item = self.remove_lru();
+
+            if lru_item.is_some() {
+                self.stats.evictions += 1;
+            }
         } else if self.contains_key(k) {
             lru_item = self.inner.remove(k);
         }
@@ -67,9 +95,38 @@ impl<T> StatementCache<T> {
         self.inner.capacity()
     }
 
+    /// Returns the counters of lookups and evictions since the cache was created.
+    pub fn stats(&self) -> StatementCacheStats {
+        self.stats
+    }
+
     /// Returns true if the cache capacity is more than 0.
     #[allow(dead_code)] // Only used for some `cfg`s
     pub fn is_enabled(&self) -> bool {
         self.capacity() > 0
     }
 }
+
+#[test]
+fn test_statement_cache_lru() {
+    let mut cache = StatementCache::new(2);
+
+    assert_eq!(cache.insert("SELECT 1", 1), None);
+    assert_eq!(cache.insert("SELECT 2", 2), None);
+
+    // looking up a statement makes it the most recently used
+    assert_eq!(cache.get_mut("SELECT 1"), Some(&mut 1));
+    assert_eq!(cache.get_mut("SELECT 3"), None);
+
+    assert_eq!(cache.insert("SELECT 3", 3), Some(2));
+    assert_eq!(cache.len(), 2);
+
+    assert_eq!(
+        cache.stats(),
+        StatementCacheStats {
+            hits: 1,
+            misses: 1,
+            evictions: 1,
+        }
+    );
+}
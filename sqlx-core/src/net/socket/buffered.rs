@@ -110,6 +110,10 @@ impl<S: Socket> BufferedSocket<S> {
         self.read_buf.shrink();
     }
 
+    pub fn peer_certificate(&self) -> Option<Vec<u8>> {
+        self.socket.peer_certificate()
+    }
+
     pub fn into_inner(self) -> S {
         self.socket
     }
@@ -82,6 +82,11 @@ impl<S: Socket> Socket for RustlsSocket<S> {
 
         Poll::Ready(Ok(()))
     }
+
+    fn peer_certificate(&self) -> Option<Vec<u8>> {
+        // the certificate of the server comes first in its chain
+        Some(self.state.peer_certificates()?.first()?.to_vec())
+    }
 }
 
 pub async fn handshake<S>(socket: S, tls_config: TlsConfig<'_>) -> Result<RustlsSocket<S>, Error>
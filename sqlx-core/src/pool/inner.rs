@@ -468,6 +468,18 @@ fn is_beyond_idle_timeout<DB: Database>(idle: &Idle<DB>, options: &PoolOptions<D
         .map_or(false, |timeout| idle.idle_since.elapsed() > timeout)
 }
 
+/// Returns `true` if the connection would be idle for longer than `options.keepalive_interval`
+/// before the next run of the reaper, `period` from now.
+fn needs_keepalive<DB: Database>(
+    idle: &Idle<DB>,
+    options: &PoolOptions<DB>,
+    period: Duration,
+) -> bool {
+    options.keepalive_interval.map_or(false, |interval| {
+        idle.idle_since.elapsed() + period >= interval
+    })
+}
+
 async fn check_idle_conn<DB: Database>(
     mut conn: Floating<DB, Idle<DB>>,
     options: &PoolOptions<DB>,
@@ -511,22 +523,25 @@ fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
     // so they don't keep `PoolInner` from being dropped.
     let pool_weak = Arc::downgrade(pool);
 
-    let period = match (pool.options.max_lifetime, pool.options.idle_timeout) {
-        (Some(it), None) | (None, Some(it)) => it,
-
-        (Some(a), Some(b)) => cmp::min(a, b),
-
-        (None, None) => {
-            if pool.options.min_connections > 0 {
-                crate::rt::spawn(async move {
-                    if let Some(pool) = pool_weak.upgrade() {
-                        pool.min_connections_maintenance(None).await;
-                    }
-                });
-            }
-
-            return;
+    let period = [
+        pool.options.max_lifetime,
+        pool.options.idle_timeout,
+        pool.options.keepalive_interval,
+    ]
+    .into_iter()
+    .flatten()
+    .min();
+
+    let Some(period) = period else {
+        if pool.options.min_connections > 0 {
+            crate::rt::spawn(async move {
+                if let Some(pool) = pool_weak.upgrade() {
+                    pool.min_connections_maintenance(None).await;
+                }
+            });
         }
+
+        return;
     };
 
     // Immediately cancel this task if the pool is closed.
@@ -548,12 +563,22 @@ fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
                     // open a new one immediately. Note that other connections may be popped from
                     // the queue in the meantime - that's fine, there is no harm in checking more
                     for _ in 0..pool.num_idle() {
-                        if let Some(conn) = pool.try_acquire() {
+                        if let Some(mut conn) = pool.try_acquire() {
                             if is_beyond_idle_timeout(&conn, &pool.options)
                                 || is_beyond_max_lifetime(&conn, &pool.options)
                             {
                                 let _ = conn.close().await;
                                 pool.min_connections_maintenance(Some(next_run)).await;
+                            } else if needs_keepalive(&conn, &pool.options, period) {
+                                if let Err(error) = conn.ping().await {
+                                    tracing::debug!(%error, "idle connection failed keepalive ping");
+
+                                    // the server already closed the connection
+                                    let _ = conn.close_hard().await;
+                                    pool.min_connections_maintenance(Some(next_run)).await;
+                                } else {
+                                    pool.release(conn.into_live());
+                                }
                             } else {
                                 pool.release(conn.into_live());
                             }
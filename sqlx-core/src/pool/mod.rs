@@ -68,7 +68,7 @@ use futures_util::FutureExt;
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TxOptions};
 
 pub use self::connection::PoolConnection;
 use self::inner::PoolInner;
@@ -370,6 +370,15 @@ impl<DB: Database> Pool<DB> {
         Transaction::begin(MaybePoolConnection::PoolConnection(self.acquire().await?)).await
     }
 
+    /// Retrieves a connection and immediately begins a new transaction with the given options.
+    pub async fn begin_with(&self, options: TxOptions) -> Result<Transaction<'static, DB>, Error> {
+        Transaction::begin_with(
+            MaybePoolConnection::PoolConnection(self.acquire().await?),
+            options,
+        )
+        .await
+    }
+
     /// Attempts to retrieve a connection and immediately begins a new transaction if successful.
     pub async fn try_begin(&self) -> Result<Option<Transaction<'static, DB>>, Error> {
         match self.try_acquire() {
@@ -82,6 +82,7 @@ pub struct PoolOptions<DB: Database> {
     pub(crate) min_connections: u32,
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) keepalive_interval: Option<Duration>,
     pub(crate) fair: bool,
 
     pub(crate) parent_pool: Option<Pool<DB>>,
@@ -105,6 +106,7 @@ impl<DB: Database> Clone for PoolOptions<DB> {
             min_connections: self.min_connections,
             max_lifetime: self.max_lifetime,
             idle_timeout: self.idle_timeout,
+            keepalive_interval: self.keepalive_interval,
             fair: self.fair,
             parent_pool: self.parent_pool.clone(),
         }
@@ -160,6 +162,7 @@ impl<DB: Database> PoolOptions<DB> {
             acquire_timeout: Duration::from_secs(30),
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
+            keepalive_interval: None,
             fair: true,
             parent_pool: None,
         }
@@ -307,6 +310,27 @@ impl<DB: Database> PoolOptions<DB> {
         self.idle_timeout
     }
 
+    /// Set an interval at which idle connections are pinged with [`Connection::ping`]
+    /// to keep them open.
+    ///
+    /// Servers close connections which have been idle for too long, e.g. after `wait_timeout`
+    /// in MySQL, and the pool would only find out when the connection is next used.
+    /// Setting this shorter than that timeout keeps idle connections alive; connections
+    /// which fail the ping are closed and replaced as needed to keep [`min_connections`].
+    ///
+    /// Defaults to `None`, i.e. idle connections are not pinged.
+    ///
+    /// [`min_connections`]: Self::min_connections
+    pub fn keepalive_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+        self.keepalive_interval = interval.into();
+        self
+    }
+
+    /// Get the interval at which idle connections are pinged.
+    pub fn get_keepalive_interval(&self) -> Option<Duration> {
+        self.keepalive_interval
+    }
+
     /// If true, the health of a connection will be verified by a call to [`Connection::ping`]
     /// before returning the connection.
     ///
@@ -590,6 +614,7 @@ impl<DB: Database> Debug for PoolOptions<DB> {
             .field("connect_timeout", &self.acquire_timeout)
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
+            .field("keepalive_interval", &self.keepalive_interval)
             .field("test_before_acquire", &self.test_before_acquire)
             .finish()
     }
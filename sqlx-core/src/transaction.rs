@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::fmt::{self, Debug, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
 use std::ops::{Deref, DerefMut};
 
 use futures_core::future::BoxFuture;
@@ -20,6 +20,24 @@ pub trait TransactionManager {
         conn: &mut <Self::Database as Database>::Connection,
     ) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Begin a new transaction with the given options.
+    ///
+    /// Returns an error if options are set and the database does not support them.
+    fn begin_with(
+        conn: &mut <Self::Database as Database>::Connection,
+        options: TxOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        if options == TxOptions::default() {
+            return Self::begin(conn);
+        }
+
+        Box::pin(async {
+            Err(Error::Configuration(
+                "transaction options are not supported by this database".into(),
+            ))
+        })
+    }
+
     /// Commit the active transaction or release the most recent savepoint.
     fn commit(
         conn: &mut <Self::Database as Database>::Connection,
@@ -96,6 +114,23 @@ where
         })
     }
 
+    #[doc(hidden)]
+    pub fn begin_with(
+        conn: impl Into<MaybePoolConnection<'c, DB>>,
+        options: TxOptions,
+    ) -> BoxFuture<'c, Result<Self, Error>> {
+        let mut conn = conn.into();
+
+        Box::pin(async move {
+            DB::TransactionManager::begin_with(&mut conn, options).await?;
+
+            Ok(Self {
+                connection: conn,
+                open: true,
+            })
+        })
+    }
+
     /// Commits this transaction or savepoint.
     pub async fn commit(mut self) -> Result<(), Error> {
         DB::TransactionManager::commit(&mut self.connection).await?;
@@ -258,6 +293,98 @@ where
     }
 }
 
+/// The isolation level of a transaction, set with [`TxOptions::isolation()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl Display for IsolationLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        })
+    }
+}
+
+/// Options of a transaction started with [`Connection::begin_with`] or [`Pool::begin_with`].
+///
+/// Unset options take the defaults of the session. Options can only be set on a new
+/// transaction, not on a savepoint, and are only supported by Postgres and MySQL.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::{IsolationLevel, TxOptions};
+/// use sqlx::Connection;
+///
+/// let mut tx = conn
+///     .begin_with(
+///         TxOptions::new()
+///             .isolation(IsolationLevel::Serializable)
+///             .read_only(),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Connection::begin_with`]: crate::connection::Connection::begin_with()
+/// [`Pool::begin_with`]: crate::pool::Pool::begin_with()
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TxOptions {
+    isolation: Option<IsolationLevel>,
+    read_only: bool,
+    deferrable: bool,
+}
+
+impl TxOptions {
+    /// Creates options with none set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the isolation level of the transaction.
+    pub fn isolation(mut self, level: IsolationLevel) -> Self {
+        self.isolation = Some(level);
+        self
+    }
+
+    /// Makes the transaction read-only, so statements which write to tables fail.
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Makes the transaction deferrable (Postgres only): a `SERIALIZABLE`, read-only
+    /// transaction then waits for a snapshot it can run on without the risk of a
+    /// serialization failure.
+    pub fn deferrable(mut self) -> Self {
+        self.deferrable = true;
+        self
+    }
+
+    /// Returns the isolation level, if set.
+    pub fn isolation_level(&self) -> Option<IsolationLevel> {
+        self.isolation
+    }
+
+    /// Returns `true` if the transaction is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns `true` if the transaction is deferrable.
+    pub fn is_deferrable(&self) -> bool {
+        self.deferrable
+    }
+}
+
 pub fn begin_ansi_transaction_sql(depth: usize) -> Cow<'static, str> {
     if depth == 0 {
         Cow::Borrowed("BEGIN")
@@ -1,9 +1,13 @@
 use crate::encode::{Encode, IsNull};
+use crate::protocol::text::ColumnType;
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo};
+use futures_io::AsyncRead;
 pub(crate) use sqlx_core::arguments::*;
 use sqlx_core::error::BoxDynError;
+use std::fmt::{self, Debug, Formatter};
 use std::ops::Deref;
+use std::sync::{Arc, Mutex, PoisonError};
 
 /// Implementation of [`Arguments`] for MySQL.
 #[derive(Debug, Default, Clone)]
@@ -11,6 +15,33 @@ pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: NullBitMap,
+    pub(crate) long_data: Vec<LongData>,
+}
+
+/// The value of a parameter that is sent with `COM_STMT_SEND_LONG_DATA`
+/// instead of with the other values.
+#[derive(Clone)]
+pub(crate) struct LongData {
+    pub(crate) index: u16,
+    reader: Arc<Mutex<Option<Box<dyn AsyncRead + Send + Unpin>>>>,
+}
+
+impl LongData {
+    /// Take the reader of the value, which can only be sent once.
+    pub(crate) fn take_reader(&self) -> Option<Box<dyn AsyncRead + Send + Unpin>> {
+        self.reader
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+impl Debug for LongData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LongData")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
 }
 
 impl MySqlArguments {
@@ -35,6 +66,51 @@ impl MySqlArguments {
 
         Ok(())
     }
+
+    /// Adds a `BLOB` or `TEXT` parameter whose value is read from `reader` and sent to the
+    /// server in chunks before the statement is executed.
+    ///
+    /// This allows values larger than the `max_allowed_packet` of the server, and values
+    /// that should not be held in memory all at once. The value can only be sent once;
+    /// executing these arguments again fails.
+    ///
+    /// Only supported by prepared statements, which are used whenever a query has arguments.
+    ///
+    /// As [`Encode`] writes a value into the buffer of the arguments, such a value cannot be
+    /// bound with `.bind()`; add the other arguments here as well and pass them all with
+    /// `query_with()`:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(
+    /// #     conn: &mut sqlx::MySqlConnection,
+    /// #     file: impl futures::AsyncRead + Send + Unpin + 'static,
+    /// # ) -> sqlx::Result<()> {
+    /// use sqlx::mysql::MySqlArguments;
+    /// use sqlx::Arguments;
+    ///
+    /// let mut arguments = MySqlArguments::default();
+    /// Arguments::add(&mut arguments, 1_i64).map_err(sqlx::Error::Encode)?;
+    /// arguments.add_long_data(file);
+    ///
+    /// sqlx::query_with("INSERT INTO uploads (id, data) VALUES (?, ?)", arguments)
+    ///     .execute(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_long_data<R>(&mut self, reader: R)
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        self.long_data.push(LongData {
+            index: self.types.len() as u16,
+            reader: Arc::new(Mutex::new(Some(Box::new(reader)))),
+        });
+
+        // the value itself is left out of `COM_STMT_EXECUTE`
+        self.types.push(MySqlTypeInfo::binary(ColumnType::LongBlob));
+        self.null_bitmap.push(IsNull::No);
+    }
 }
 
 impl<'q> Arguments<'q> for MySqlArguments {
@@ -105,4 +181,22 @@ mod test {
 
         assert_eq!([0b01010101, 0b1].as_slice(), bit_map.deref());
     }
+
+    #[test]
+    fn add_long_data_should_leave_out_value() {
+        let mut arguments = MySqlArguments::default();
+
+        arguments.add(1_i32).unwrap();
+        arguments.add_long_data(&b"long"[..]);
+
+        assert_eq!(arguments.types.len(), 2);
+        assert_eq!(arguments.values, 1_i32.to_le_bytes());
+        assert_eq!(&*arguments.null_bitmap, [0]);
+
+        let cloned = arguments.clone();
+
+        assert_eq!(arguments.long_data[0].index, 1);
+        assert!(arguments.long_data[0].take_reader().is_some());
+        assert!(cloned.long_data[0].take_reader().is_none());
+    }
 }
@@ -14,6 +14,45 @@ pub struct MySqlColumn {
     pub(crate) flags: Option<ColumnFlags>,
 }
 
+impl MySqlColumn {
+    /// Returns whether the column can be `NULL`, or `None` if that is not known, as for
+    /// columns from cached query metadata.
+    pub fn is_nullable(&self) -> Option<bool> {
+        self.flags
+            .map(|flags| !flags.contains(ColumnFlags::NOT_NULL))
+    }
+
+    /// Returns `true` if the column is an `UNSIGNED` number.
+    pub fn is_unsigned(&self) -> bool {
+        self.type_info.flags.contains(ColumnFlags::UNSIGNED)
+    }
+
+    /// Returns `true` if the column is binary, e.g. `BINARY`, `VARBINARY` or `BLOB`, or
+    /// has a binary collation.
+    pub fn is_binary(&self) -> bool {
+        self.type_info.flags.contains(ColumnFlags::BINARY)
+    }
+
+    /// Returns `true` if the column is part of the primary key of its table.
+    pub fn is_primary_key(&self) -> bool {
+        self.has_flag(ColumnFlags::PRIMARY_KEY)
+    }
+
+    /// Returns `true` if the column is part of a unique key of its table.
+    pub fn is_unique_key(&self) -> bool {
+        self.has_flag(ColumnFlags::UNIQUE_KEY)
+    }
+
+    /// Returns `true` if the column is `AUTO_INCREMENT`.
+    pub fn is_auto_increment(&self) -> bool {
+        self.has_flag(ColumnFlags::AUTO_INCREMENT)
+    }
+
+    fn has_flag(&self, flag: ColumnFlags) -> bool {
+        self.flags.is_some_and(|flags| flags.contains(flag))
+    }
+}
+
 impl Column for MySqlColumn {
     type Database = MySql;
 
@@ -29,3 +68,21 @@ impl Column for MySqlColumn {
         &self.type_info
     }
 }
+
+/// Returns the definitions of columns of the given types, named `c0`, `c1` and so on,
+/// for testing the decoding of rows.
+#[cfg(test)]
+pub(crate) fn test_columns(
+    types: impl IntoIterator<Item = crate::protocol::text::ColumnType>,
+) -> Vec<MySqlColumn> {
+    types
+        .into_iter()
+        .enumerate()
+        .map(|(ordinal, ty)| MySqlColumn {
+            ordinal,
+            name: UStr::new(&format!("c{ordinal}")),
+            type_info: MySqlTypeInfo::binary(ty),
+            flags: None,
+        })
+        .collect()
+}
@@ -1,25 +1,40 @@
 use bytes::buf::Chain;
 use bytes::Bytes;
 use digest::{Digest, OutputSizeUser};
+#[cfg(feature = "ed25519")]
+use ed25519_dalek::hazmat::{raw_sign, ExpandedSecretKey};
+#[cfg(feature = "ed25519")]
+use ed25519_dalek::VerifyingKey;
 use generic_array::GenericArray;
 use rand::thread_rng;
 use rsa::{pkcs8::DecodePublicKey, Oaep, RsaPublicKey};
 use sha1::Sha1;
 use sha2::Sha256;
+#[cfg(feature = "ed25519")]
+use sha2::Sha512;
 
 use crate::connection::stream::MySqlStream;
 use crate::error::Error;
+use crate::net::Socket;
 use crate::protocol::auth::AuthPlugin;
 use crate::protocol::Packet;
 
 impl AuthPlugin {
-    pub(super) async fn scramble(
+    pub(super) async fn scramble<S: Socket>(
         self,
-        stream: &mut MySqlStream,
+        stream: &mut MySqlStream<S>,
         password: &str,
         nonce: &Chain<Bytes, Bytes>,
     ) -> Result<Vec<u8>, Error> {
         match self {
+            // like libmysqlclient, send an empty response rather than a scramble
+            // of an empty password, which the server does not accept
+            AuthPlugin::CachingSha2Password | AuthPlugin::MySqlNativePassword
+                if password.is_empty() =>
+            {
+                Ok(Vec::new())
+            }
+
             // https://mariadb.com/kb/en/caching_sha2_password-authentication-plugin/
             AuthPlugin::CachingSha2Password => Ok(scramble_sha256(password, nonce).to_vec()),
 
@@ -33,12 +48,16 @@ impl AuthPlugin {
                 pw_bytes.push(0); // null terminate
                 Ok(pw_bytes)
             }
+
+            // https://mariadb.com/kb/en/authentication-plugin-ed25519/
+            #[cfg(feature = "ed25519")]
+            AuthPlugin::ClientEd25519 => Ok(sign_ed25519(password, nonce).to_vec()),
         }
     }
 
-    pub(super) async fn handle(
+    pub(super) async fn handle<S: Socket>(
         self,
-        stream: &mut MySqlStream,
+        stream: &mut MySqlStream<S>,
         packet: Packet<Bytes>,
         password: &str,
         nonce: &Chain<Bytes, Bytes>,
@@ -129,8 +148,26 @@ fn scramble_sha256(
     pw_hash
 }
 
-async fn encrypt_rsa<'s>(
-    stream: &'s mut MySqlStream,
+#[cfg(feature = "ed25519")]
+fn sign_ed25519(password: &str, nonce: &Chain<Bytes, Bytes>) -> [u8; 64] {
+    // Sign the nonce with Ed25519, using SHA512(password) in place of
+    // the hash of a random 32-byte seed to derive the key.
+    let mut hash = [0; 64];
+    hash.copy_from_slice(&Sha512::digest(password));
+
+    let secret_key = ExpandedSecretKey::from_bytes(&hash);
+    let public_key = VerifyingKey::from(&secret_key);
+
+    let (a, b) = (nonce.first_ref(), nonce.last_ref());
+    let mut message = Vec::with_capacity(a.len() + b.len());
+    message.extend_from_slice(a);
+    message.extend_from_slice(b);
+
+    raw_sign::<Sha512>(&secret_key, &message, &public_key).to_bytes()
+}
+
+async fn encrypt_rsa<'s, S: Socket>(
+    stream: &'s mut MySqlStream<S>,
     public_key_request_id: u8,
     password: &'s str,
     nonce: &'s Chain<Bytes, Bytes>,
@@ -195,3 +232,58 @@ fn parse_rsa_pub_key(key: &[u8]) -> Result<RsaPublicKey, Error> {
 
     RsaPublicKey::from_public_key_pem(pem).map_err(Error::protocol)
 }
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn test_sign_ed25519() {
+    use bytes::Buf;
+    use ed25519_dalek::{Signature, Verifier};
+
+    let nonce = Bytes::from_static(&[0x2a; 32]).chain(Bytes::new());
+    let signature = Signature::from_bytes(&sign_ed25519("password", &nonce));
+
+    // the server stores the public key derived from the password
+    let mut hash = [0; 64];
+    hash.copy_from_slice(&Sha512::digest("password"));
+    let public_key = VerifyingKey::from(&ExpandedSecretKey::from_bytes(&hash));
+
+    assert!(public_key.verify(&[0x2a; 32], &signature).is_ok());
+    assert!(public_key.verify(&[0x2b; 32], &signature).is_err());
+}
+
+#[test]
+fn test_scramble_empty_password() {
+    use crate::collation::CharSet;
+    use crate::connection::stream::MemorySocket;
+    use crate::MySqlConnectOptions;
+    use bytes::Buf;
+    use futures_util::FutureExt;
+
+    let mut stream = MySqlStream::with_socket(
+        CharSet::utf8mb4,
+        CharSet::utf8mb4.default_collation(),
+        &MySqlConnectOptions::new(),
+        MemorySocket {
+            input: Bytes::new(),
+            output: Vec::new(),
+        },
+    );
+
+    let nonce = Bytes::from_static(&[0x2a; 20]).chain(Bytes::new());
+
+    for plugin in [
+        AuthPlugin::MySqlNativePassword,
+        AuthPlugin::CachingSha2Password,
+    ] {
+        let empty = plugin.scramble(&mut stream, "", &nonce).now_or_never();
+        assert!(empty.unwrap().unwrap().is_empty(), "{}", plugin.name());
+
+        let scrambled = plugin
+            .scramble(&mut stream, "password", &nonce)
+            .now_or_never();
+        assert!(!scrambled.unwrap().unwrap().is_empty(), "{}", plugin.name());
+    }
+
+    // nothing is exchanged with the server
+    assert!(stream.socket.into_inner().output.is_empty());
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use crate::connection::Connection;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::{MySqlConnectOptions, MySqlConnection};
+
+/// A handle to kill the statement running on a [`MySqlConnection`], from another task
+/// or after a timeout.
+///
+/// Obtained with [`MySqlConnection::cancel_handle`].
+#[derive(Debug, Clone)]
+pub struct MySqlCancelHandle {
+    options: Arc<MySqlConnectOptions>,
+    connection_id: u32,
+}
+
+impl MySqlConnection {
+    /// Returns a handle to kill the statement running on this connection, if any.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut conn: sqlx_mysql::MySqlConnection) -> sqlx_core::Result<()> {
+    /// let handle = conn.cancel_handle();
+    ///
+    /// // e.g. in another task, or when a timeout fires
+    /// handle.cancel().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cancel_handle(&self) -> MySqlCancelHandle {
+        MySqlCancelHandle {
+            options: Arc::clone(&self.inner.options),
+            connection_id: self.inner.connection_id,
+        }
+    }
+}
+
+impl MySqlCancelHandle {
+    /// Returns the id of the connection on the server, as in `CONNECTION_ID()`.
+    pub fn connection_id(&self) -> u32 {
+        self.connection_id
+    }
+
+    /// Kills the statement running on the connection with `KILL QUERY`, sent from a
+    /// short-lived connection opened with the same options.
+    ///
+    /// The statement fails with error 1317 (`ER_QUERY_INTERRUPTED`) and the connection
+    /// remains usable. Nothing happens if no statement is running; note that a statement
+    /// started just before the kill arrives is killed instead.
+    pub async fn cancel(&self) -> Result<(), Error> {
+        let mut conn = MySqlConnection::establish(&self.options).await?;
+
+        conn.execute(&*format!("KILL QUERY {}", self.connection_id))
+            .await?;
+
+        conn.close().await
+    }
+}
@@ -0,0 +1,346 @@
+use std::cmp;
+use std::io;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, BytesMut};
+use futures_core::ready;
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use miniz_oxide::inflate::decompress_to_vec_zlib_with_limit;
+
+use crate::io::ReadBuf;
+use crate::net::Socket;
+
+// https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_basic_compression.html
+
+// compressing smaller payloads is not worth it; this is the threshold used by `libmysqlclient`
+const MIN_COMPRESS_LEN: usize = 50;
+
+const MAX_PAYLOAD_LEN: usize = 0xFF_FF_FF;
+
+const HEADER_LEN: usize = 7;
+
+/// The algorithm used to compress packets.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Compression {
+    Zlib,
+
+    /// With the compression level for packets written by the client.
+    #[cfg(feature = "zstd")]
+    Zstd(u8),
+}
+
+impl Compression {
+    fn compress(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Compression::Zlib => Ok(compress_to_vec_zlib(data, 6)),
+
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(level) => zstd::bulk::compress(data, level.into()),
+        }
+    }
+
+    fn decompress(self, data: &[u8], uncompressed_len: usize) -> io::Result<Vec<u8>> {
+        match self {
+            Compression::Zlib => {
+                decompress_to_vec_zlib_with_limit(data, uncompressed_len).map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("failed to decompress packet: {:?}", e.status),
+                    )
+                })
+            }
+
+            #[cfg(feature = "zstd")]
+            Compression::Zstd(_) => zstd::bulk::decompress(data, uncompressed_len),
+        }
+    }
+}
+
+/// Wraps a socket in the compressed protocol, which frames (and optionally compresses)
+/// the bytes of any number of regular packets in a compressed packet.
+pub(crate) struct CompressedSocket<S> {
+    socket: S,
+    compression: Compression,
+    sequence_id: u8,
+
+    // compressed packets read from the socket that are not yet complete
+    read_buf: BytesMut,
+
+    // decompressed bytes that are not yet read
+    decompressed: BytesMut,
+
+    // compressed packets not yet written to the socket
+    write_buf: Vec<u8>,
+}
+
+impl<S: Socket> CompressedSocket<S> {
+    pub(crate) fn new(socket: S, compression: Compression) -> Self {
+        Self {
+            socket,
+            compression,
+            sequence_id: 0,
+            read_buf: BytesMut::new(),
+            decompressed: BytesMut::new(),
+            write_buf: Vec::new(),
+        }
+    }
+
+    /// Decompress the next compressed packet if all of it has been read.
+    fn decompress_next(&mut self) -> io::Result<bool> {
+        if self.read_buf.len() < HEADER_LEN {
+            return Ok(false);
+        }
+
+        let compressed_len =
+            u32::from_le_bytes([self.read_buf[0], self.read_buf[1], self.read_buf[2], 0]) as usize;
+
+        if self.read_buf.len() < HEADER_LEN + compressed_len {
+            return Ok(false);
+        }
+
+        let sequence_id = self.read_buf[3];
+
+        let uncompressed_len =
+            u32::from_le_bytes([self.read_buf[4], self.read_buf[5], self.read_buf[6], 0]) as usize;
+
+        self.read_buf.advance(HEADER_LEN);
+        let payload = self.read_buf.split_to(compressed_len);
+
+        self.sequence_id = sequence_id.wrapping_add(1);
+
+        // an uncompressed length of 0 means the payload was sent as-is
+        if uncompressed_len == 0 {
+            self.decompressed.extend_from_slice(&payload);
+            return Ok(true);
+        }
+
+        let decompressed = self.compression.decompress(&payload, uncompressed_len)?;
+
+        if decompressed.len() != uncompressed_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "expected packet to decompress to {} bytes but got {} bytes",
+                    uncompressed_len,
+                    decompressed.len()
+                ),
+            ));
+        }
+
+        self.decompressed.extend_from_slice(&decompressed);
+
+        Ok(true)
+    }
+
+    fn write_compressed(&mut self, buf: &[u8]) -> io::Result<()> {
+        // like the sequence of the packets within, the sequence of compressed packets
+        // starts over with each command
+        if buf.get(3) == Some(&0) {
+            self.sequence_id = 0;
+        }
+
+        for chunk in buf.chunks(MAX_PAYLOAD_LEN) {
+            let compressed = if chunk.len() < MIN_COMPRESS_LEN {
+                None
+            } else {
+                // data that does not compress is sent as-is
+                Some(self.compression.compress(chunk)?).filter(|c| c.len() < chunk.len())
+            };
+
+            let (payload, uncompressed_len) = match &compressed {
+                Some(compressed) => (&compressed[..], chunk.len()),
+                None => (chunk, 0),
+            };
+
+            self.write_buf
+                .extend_from_slice(&(payload.len() as u32).to_le_bytes()[..3]);
+            self.write_buf.push(self.sequence_id);
+            self.write_buf
+                .extend_from_slice(&(uncompressed_len as u32).to_le_bytes()[..3]);
+            self.write_buf.extend_from_slice(payload);
+
+            self.sequence_id = self.sequence_id.wrapping_add(1);
+        }
+
+        Ok(())
+    }
+
+    fn poll_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.write_buf.is_empty() {
+            match self.socket.try_write(&self.write_buf) {
+                Ok(0) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+
+                Ok(written) => {
+                    self.write_buf.drain(..written);
+                }
+
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    ready!(self.socket.poll_write_ready(cx))?;
+                }
+
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: Socket> Socket for CompressedSocket<S> {
+    fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+        while self.decompressed.is_empty() {
+            if self.decompress_next()? {
+                continue;
+            }
+
+            let mut chunk = [0; 8192];
+            let read = self.socket.try_read(&mut &mut chunk[..])?;
+
+            if read == 0 {
+                // EOF
+                return Ok(0);
+            }
+
+            self.read_buf.extend_from_slice(&chunk[..read]);
+        }
+
+        let buf = buf.init_mut();
+        let len = cmp::min(buf.len(), self.decompressed.len());
+
+        buf[..len].copy_from_slice(&self.decompressed[..len]);
+        self.decompressed.advance(len);
+
+        Ok(len)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // the compressed packets are written to the socket when flushing
+        self.write_compressed(buf)?;
+
+        Ok(buf.len())
+    }
+
+    fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.decompressed.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+
+        self.socket.poll_read_ready(cx)
+    }
+
+    fn poll_write_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // writes are always buffered
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_buf(cx))?;
+        self.socket.poll_flush(cx)
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_buf(cx))?;
+        self.socket.poll_shutdown(cx)
+    }
+}
+
+#[test]
+fn test_compressed_round_trip() {
+    use crate::connection::stream::MemorySocket;
+    use futures_util::task::noop_waker_ref;
+
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    // COM_QUERY packets, short enough to be sent uncompressed and long enough to be compressed
+    let short = b"\x09\x00\x00\x00\x03SELECT 1".to_vec();
+
+    let mut long = vec![0x65, 0x00, 0x00, 0x00, 0x03];
+    long.extend_from_slice(b"SELECT ");
+    long.extend_from_slice(&[b'1'; 93]);
+
+    let mut writer = CompressedSocket::new(
+        MemorySocket {
+            input: Default::default(),
+            output: Vec::new(),
+        },
+        Compression::Zlib,
+    );
+
+    for packet in [&short, &long] {
+        assert_eq!(writer.try_write(packet).unwrap(), packet.len());
+        assert!(writer.poll_flush(&mut cx).is_ready());
+    }
+
+    let output = writer.socket.output;
+
+    // the short packet is framed as-is, with an uncompressed length of 0
+    assert_eq!(&output[..7], b"\x0d\x00\x00\x00\x00\x00\x00");
+    assert_eq!(&output[7..20], &short[..]);
+
+    // the long packet is compressed
+    assert_eq!(&output[23..27], b"\x00\x69\x00\x00");
+    assert!(output.len() - 20 < HEADER_LEN + long.len());
+
+    let mut reader = CompressedSocket::new(
+        MemorySocket {
+            input: output.into(),
+            output: Vec::new(),
+        },
+        Compression::Zlib,
+    );
+
+    let mut read = Vec::new();
+    let mut buf = [0; 64];
+
+    loop {
+        match reader.try_read(&mut &mut buf[..]).unwrap() {
+            0 => break,
+            len => read.extend_from_slice(&buf[..len]),
+        }
+    }
+
+    assert_eq!(read, [short, long].concat());
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_round_trip() {
+    use crate::connection::stream::MemorySocket;
+    use futures_util::task::noop_waker_ref;
+
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    let mut packet = vec![0x65, 0x00, 0x00, 0x00, 0x03];
+    packet.extend_from_slice(b"SELECT ");
+    packet.extend_from_slice(&[b'1'; 93]);
+
+    let mut writer = CompressedSocket::new(
+        MemorySocket {
+            input: Default::default(),
+            output: Vec::new(),
+        },
+        Compression::Zstd(3),
+    );
+
+    writer.try_write(&packet).unwrap();
+    assert!(writer.poll_flush(&mut cx).is_ready());
+
+    let output = writer.socket.output;
+
+    // the zstd frame magic number follows the header
+    assert_eq!(&output[4..7], b"\x69\x00\x00");
+    assert_eq!(&output[7..11], b"\x28\xb5\x2f\xfd");
+
+    let mut reader = CompressedSocket::new(
+        MemorySocket {
+            input: output.into(),
+            output: Vec::new(),
+        },
+        Compression::Zstd(3),
+    );
+
+    let mut buf = [0; 128];
+    let len = reader.try_read(&mut &mut buf[..]).unwrap();
+
+    assert_eq!(&buf[..len], &packet[..]);
+}
@@ -1,14 +1,19 @@
-use bytes::buf::Buf;
+use std::sync::Arc;
+
+use bytes::buf::{Buf, Chain};
 use bytes::Bytes;
 use futures_core::future::BoxFuture;
 
 use crate::collation::{CharSet, Collation};
 use crate::common::StatementCache;
+#[cfg(feature = "compress")]
+use crate::connection::compression::Compression;
 use crate::connection::{tls, MySqlConnectionInner, MySqlStream, MAX_PACKET_SIZE};
 use crate::error::Error;
 use crate::net::{Socket, WithSocket};
+use crate::protocol::auth::AuthPlugin;
 use crate::protocol::connect::{
-    AuthSwitchRequest, AuthSwitchResponse, Handshake, HandshakeResponse,
+    AuthSwitchRequest, AuthSwitchResponse, ChangeUser, Handshake, HandshakeResponse,
 };
 use crate::protocol::Capabilities;
 use crate::{MySqlConnectOptions, MySqlConnection, MySqlSslMode};
@@ -22,7 +27,7 @@ impl MySqlConnection {
             None => crate::net::connect_tcp(&options.host, options.port, do_handshake).await?,
         };
 
-        let stream = handshake.await?;
+        let (stream, connection_id, auth) = handshake.await?;
 
         Ok(Self {
             inner: Box::new(MySqlConnectionInner {
@@ -30,9 +35,61 @@ impl MySqlConnection {
                 transaction_depth: 0,
                 cache_statement: StatementCache::new(options.statement_cache_capacity),
                 log_settings: options.log_settings.clone(),
+                cursor_fetch_size: options.cursor_fetch_size,
+                validate_rows: options.validate_rows,
+                local_infile_dirs: options.local_infile_dirs.clone(),
+                local_infile_handler: None,
+                session_init: Vec::new(),
+                options: Arc::new(options.clone()),
+                auth,
+                connection_id,
+                query_attributes: Vec::new(),
+                warning_handler: None,
+                max_allowed_packet: None,
             }),
         })
     }
+
+    /// Authenticates the connection again as the same user with `COM_CHANGE_USER`,
+    /// which resets its session state.
+    pub(crate) async fn change_user(&mut self) -> Result<(), Error> {
+        let inner = &mut *self.inner;
+        let options = Arc::clone(&inner.options);
+        let stream = &mut inner.stream;
+
+        // the server expects the response to be scrambled with the nonce of the
+        // initial handshake
+        let plugin = inner.auth.plugin;
+        let nonce = &inner.auth.nonce;
+
+        let auth_response = if let (Some(plugin), Some(password)) = (plugin, &options.password) {
+            plugin.scramble(stream, password, nonce).await?
+        } else {
+            Vec::new()
+        };
+
+        let collation = stream.collation as u8;
+
+        stream
+            .send_packet(ChangeUser {
+                username: &options.username,
+                auth_response: &auth_response,
+                database: options.database.as_deref(),
+                collation,
+                auth_plugin: plugin,
+            })
+            .await?;
+
+        let nonce = nonce.first_ref().clone().chain(nonce.last_ref().clone());
+
+        authenticate(stream, &options, plugin, nonce).await
+    }
+}
+
+/// The authentication plugin and nonce sent by the server in its initial handshake.
+pub(crate) struct InitialAuth {
+    plugin: Option<AuthPlugin>,
+    nonce: Chain<Bytes, Bytes>,
 }
 
 struct DoHandshake<'a> {
@@ -67,7 +124,10 @@ impl<'a> DoHandshake<'a> {
         })
     }
 
-    async fn do_handshake<S: Socket>(self, socket: S) -> Result<MySqlStream, Error> {
+    async fn do_handshake<S: Socket>(
+        self,
+        socket: S,
+    ) -> Result<(MySqlStream, u32, InitialAuth), Error> {
         let DoHandshake {
             options,
             charset,
@@ -81,7 +141,7 @@ impl<'a> DoHandshake<'a> {
 
         let handshake: Handshake = stream.recv_packet().await?.decode()?;
 
-        let mut plugin = handshake.auth_plugin;
+        let plugin = handshake.auth_plugin;
         let nonce = handshake.auth_plugin_data;
 
         // FIXME: server version parse is a bit ugly
@@ -124,6 +184,36 @@ impl<'a> DoHandshake<'a> {
             None
         };
 
+        // identify the client in `performance_schema.session_connect_attrs`
+        let pid = std::process::id().to_string();
+        let program_name = std::env::current_exe()
+            .ok()
+            .and_then(|path| Some(path.file_name()?.to_string_lossy().into_owned()));
+
+        let mut connect_attrs = vec![
+            ("_client_name", "sqlx"),
+            ("_client_version", env!("CARGO_PKG_VERSION")),
+            ("_os", std::env::consts::OS),
+            ("_platform", std::env::consts::ARCH),
+            ("_pid", &*pid),
+        ];
+
+        if let Some(program_name) = program_name.as_deref().filter(|_| {
+            !options
+                .connect_attrs
+                .iter()
+                .any(|(key, _)| key == "program_name")
+        }) {
+            connect_attrs.push(("program_name", program_name));
+        }
+
+        connect_attrs.extend(
+            options
+                .connect_attrs
+                .iter()
+                .map(|(key, value)| (&**key, &**value)),
+        );
+
         stream.write_packet(HandshakeResponse {
             collation: stream.collation as u8,
             max_packet_size: MAX_PACKET_SIZE,
@@ -131,65 +221,190 @@ impl<'a> DoHandshake<'a> {
             database: options.database.as_deref(),
             auth_plugin: plugin,
             auth_response: auth_response.as_deref(),
+            connect_attrs: &connect_attrs,
+            zstd_compression_level: options.zstd_compression_level,
         });
 
         stream.flush().await?;
 
-        loop {
-            let packet = stream.recv_packet().await?;
-            match packet[0] {
-                0x00 => {
-                    let _ok = packet.ok()?;
+        authenticate(
+            &mut stream,
+            options,
+            plugin,
+            nonce.first_ref().clone().chain(nonce.last_ref().clone()),
+        )
+        .await?;
+
+        // the level is only set, and zstd only requested, with the `zstd` feature
+        #[cfg(feature = "zstd")]
+        if let Some(level) = options.zstd_compression_level.filter(|_| {
+            stream
+                .capabilities
+                .contains(Capabilities::ZSTD_COMPRESSION_ALGORITHM)
+        }) {
+            stream = stream.compressed(Compression::Zstd(level));
+        }
 
-                    break;
-                }
+        // as above, with the `compress` feature
+        #[cfg(feature = "compress")]
+        if stream.capabilities.contains(Capabilities::COMPRESS) {
+            stream = stream.compressed(Compression::Zlib);
+        }
 
-                0xfe => {
-                    let switch: AuthSwitchRequest =
-                        packet.decode_with(self.options.enable_cleartext_plugin)?;
+        Ok((
+            stream,
+            handshake.connection_id,
+            InitialAuth { plugin, nonce },
+        ))
+    }
+}
 
-                    plugin = Some(switch.plugin);
-                    let nonce = switch.data.chain(Bytes::new());
+impl<'a> WithSocket for DoHandshake<'a> {
+    type Output = BoxFuture<'a, Result<(MySqlStream, u32, InitialAuth), Error>>;
 
-                    let response = switch
-                        .plugin
-                        .scramble(
-                            &mut stream,
-                            options.password.as_deref().unwrap_or_default(),
-                            &nonce,
-                        )
-                        .await?;
+    fn with_socket<S: Socket>(self, socket: S) -> Self::Output {
+        Box::pin(self.do_handshake(socket))
+    }
+}
 
-                    stream.write_packet(AuthSwitchResponse(response));
-                    stream.flush().await?;
-                }
+/// Completes authentication after the handshake response or `COM_CHANGE_USER` was sent.
+async fn authenticate<S: Socket>(
+    stream: &mut MySqlStream<S>,
+    options: &MySqlConnectOptions,
+    mut plugin: Option<AuthPlugin>,
+    mut nonce: Chain<Bytes, Bytes>,
+) -> Result<(), Error> {
+    loop {
+        let packet = stream.recv_packet().await?;
+        match packet[0] {
+            0x00 => {
+                let _ok = packet.ok()?;
+
+                break;
+            }
+
+            0xfe => {
+                let switch: AuthSwitchRequest =
+                    packet.decode_with(options.enable_cleartext_plugin)?;
+
+                // the new plugin continues with the nonce of the switch request,
+                // including for any further exchange (e.g. full authentication)
+                plugin = Some(switch.plugin);
+                nonce = switch.data.chain(Bytes::new());
+
+                let response = switch
+                    .plugin
+                    .scramble(
+                        stream,
+                        options.password.as_deref().unwrap_or_default(),
+                        &nonce,
+                    )
+                    .await?;
+
+                stream.write_packet(AuthSwitchResponse(response));
+                stream.flush().await?;
+            }
 
-                id => {
-                    if let (Some(plugin), Some(password)) = (plugin, &options.password) {
-                        if plugin.handle(&mut stream, packet, password, &nonce).await? {
-                            // plugin signaled authentication is ok
-                            break;
-                        }
-
-                        // plugin signaled to continue authentication
-                    } else {
-                        return Err(err_protocol!(
-                            "unexpected packet 0x{:02x} during authentication",
-                            id
-                        ));
+            id => {
+                if let (Some(plugin), Some(password)) = (plugin, &options.password) {
+                    if plugin.handle(stream, packet, password, &nonce).await? {
+                        // plugin signaled authentication is ok
+                        break;
                     }
+
+                    // plugin signaled to continue authentication
+                } else {
+                    return Err(err_protocol!(
+                        "unexpected packet 0x{:02x} during authentication",
+                        id
+                    ));
                 }
             }
         }
-
-        Ok(stream)
     }
+
+    Ok(())
 }
 
-impl<'a> WithSocket for DoHandshake<'a> {
-    type Output = BoxFuture<'a, Result<MySqlStream, Error>>;
+#[test]
+fn test_authenticate_with_nonce_of_auth_switch() {
+    use crate::connection::stream::MemorySocket;
+    use futures_util::FutureExt;
+    use rsa::pkcs8::{EncodePublicKey, LineEnding};
+    use rsa::{Oaep, RsaPrivateKey};
+
+    let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 512).unwrap();
+    let public_key = private_key
+        .to_public_key()
+        .to_public_key_pem(LineEnding::LF)
+        .unwrap();
+
+    let switch_nonce = [0x2a; 20];
+
+    let mut input = Vec::new();
+
+    for payload in [
+        // AuthSwitchRequest to caching_sha2_password, with a new nonce
+        [
+            &b"\xfecaching_sha2_password\x00"[..],
+            &switch_nonce,
+            b"\x00",
+        ]
+        .concat(),
+        // fast authentication failed, continue with full authentication
+        b"\x01\x04".to_vec(),
+        // the public key of the server
+        [&b"\x01"[..], public_key.as_bytes()].concat(),
+        // OK
+        b"\x00\x00\x00\x02\x00\x00\x00".to_vec(),
+    ] {
+        input.extend_from_slice(&(payload.len() as u32).to_le_bytes()[..3]);
+        input.push(0);
+        input.extend_from_slice(&payload);
+    }
 
-    fn with_socket<S: Socket>(self, socket: S) -> Self::Output {
-        Box::pin(self.do_handshake(socket))
+    let options = MySqlConnectOptions::new().password("password");
+
+    let mut stream = MySqlStream::with_socket(
+        CharSet::utf8mb4,
+        CharSet::utf8mb4.default_collation(),
+        &options,
+        MemorySocket {
+            input: input.into(),
+            output: Vec::new(),
+        },
+    );
+
+    // the nonce of the initial handshake, which must not be used after the switch
+    let nonce = Bytes::from_static(&[0x11; 20]).chain(Bytes::new());
+
+    authenticate(
+        &mut stream,
+        &options,
+        Some(AuthPlugin::MySqlNativePassword),
+        nonce,
+    )
+    .now_or_never()
+    .unwrap()
+    .unwrap();
+
+    // the last packet sent is the password encrypted with the public key
+    let mut output = Bytes::from(stream.socket.into_inner().output);
+    let mut encrypted = Bytes::new();
+
+    while !output.is_empty() {
+        let len = output.get_uint_le(3) as usize;
+        output.advance(1);
+        encrypted = output.split_to(len);
     }
+
+    let mut password = private_key
+        .decrypt(Oaep::new::<sha1::Sha1>(), &encrypted)
+        .unwrap();
+
+    for (byte, nonce) in password.iter_mut().zip(switch_nonce.iter().cycle()) {
+        *byte ^= nonce;
+    }
+
+    assert_eq!(password, b"password\0");
 }
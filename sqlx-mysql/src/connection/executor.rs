@@ -8,9 +8,11 @@ use crate::io::MySqlBufExt;
 use crate::logger::QueryLogger;
 use crate::protocol::response::Status;
 use crate::protocol::statement::{
-    BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
+    BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, SendLongData, StmtClose, StmtFetch,
+    StmtReset,
 };
-use crate::protocol::text::{ColumnDefinition, ColumnFlags, Query, TextRow};
+use crate::protocol::text::{ColumnDefinition, ColumnFlags, LocalInfileRequest, Query, TextRow};
+use crate::protocol::Capabilities;
 use crate::statement::{MySqlStatement, MySqlStatementMetadata};
 use crate::HashMap;
 use crate::{
@@ -21,10 +23,30 @@ use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
-use futures_util::{pin_mut, TryStreamExt};
+use futures_util::{pin_mut, AsyncReadExt, TryStreamExt};
 use std::{borrow::Cow, sync::Arc};
 
+// values added with `MySqlArguments::add_long_data` are sent in chunks of at most this size
+const MAX_LONG_DATA_CHUNK_LEN: usize = 1024 * 1024;
+
 impl MySqlConnection {
+    /// Fail with a clear error if a command of `len` bytes exceeds the `max_allowed_packet`
+    /// of the server, which would otherwise refuse it by closing the connection.
+    fn check_packet_len(&self, len: usize) -> Result<(), Error> {
+        match self.inner.max_allowed_packet {
+            Some(max) if len as u64 > max => Err(Error::Encode(
+                format!(
+                    "command of {len} bytes exceeds the `max_allowed_packet` of the server \
+                     ({max} bytes); send large values with `MySqlArguments::add_long_data` \
+                     or increase `max_allowed_packet`"
+                )
+                .into(),
+            )),
+
+            _ => Ok(()),
+        }
+    }
+
     async fn prepare_statement<'c>(
         &mut self,
         sql: &str,
@@ -32,6 +54,8 @@ impl MySqlConnection {
         // https://dev.mysql.com/doc/internals/en/com-stmt-prepare.html
         // https://dev.mysql.com/doc/internals/en/com-stmt-prepare-response.html#packet-COM_STMT_PREPARE_OK
 
+        self.check_packet_len(1 + sql.len())?;
+
         self.inner
             .stream
             .send_packet(Prepare { query: sql })
@@ -39,12 +63,17 @@ impl MySqlConnection {
 
         let ok: PrepareOk = self.inner.stream.recv().await?;
 
-        // the parameter definitions are very unreliable so we skip over them
-        // as we have little use
+        // the parameter definitions are very unreliable (most servers report every parameter
+        // as a `VAR_STRING` or `NULL`) so we only keep their types for inspection and
+        // otherwise rely on the types of the bound arguments
+
+        let mut parameter_types = Vec::with_capacity(ok.params as usize);
 
         if ok.params > 0 {
             for _ in 0..ok.params {
-                let _def: ColumnDefinition = self.inner.stream.recv().await?;
+                let def = self.inner.stream.recv_column_definition().await?;
+
+                parameter_types.push(MySqlTypeInfo::from_column(&def));
             }
 
             self.inner.stream.maybe_recv_eof().await?;
@@ -65,6 +94,7 @@ impl MySqlConnection {
         let id = ok.statement_id;
         let metadata = MySqlStatementMetadata {
             parameters: ok.params as usize,
+            parameter_types: Arc::new(parameter_types),
             columns: Arc::new(columns),
             column_names: Arc::new(column_names),
         };
@@ -98,6 +128,85 @@ impl MySqlConnection {
         Ok((id, metadata))
     }
 
+    /// Send the values of the arguments added with [`MySqlArguments::add_long_data`].
+    ///
+    /// If a value cannot be read, the values sent so far are discarded and the statement
+    /// is not executed.
+    async fn send_long_data(
+        &mut self,
+        statement: u32,
+        arguments: &MySqlArguments,
+    ) -> Result<(), Error> {
+        if arguments.long_data.is_empty() {
+            return Ok(());
+        }
+
+        if let Err(error) = self.send_long_data_values(statement, arguments).await {
+            self.inner
+                .stream
+                .send_packet(StmtReset { statement })
+                .await?;
+            self.inner.stream.recv_ok().await?;
+
+            // no result follows since the statement is not executed
+            self.inner.stream.waiting.pop_front();
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    async fn send_long_data_values(
+        &mut self,
+        statement: u32,
+        arguments: &MySqlArguments,
+    ) -> Result<(), Error> {
+        // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_send_long_data.html
+        // each chunk is sent after a 7-byte header
+        let chunk_len = match self.inner.max_allowed_packet {
+            Some(max) => (max as usize)
+                .saturating_sub(7)
+                .clamp(1, MAX_LONG_DATA_CHUNK_LEN),
+            None => MAX_LONG_DATA_CHUNK_LEN,
+        };
+
+        let mut chunk = vec![0; chunk_len];
+
+        for long_data in &arguments.long_data {
+            let mut reader = long_data.take_reader().ok_or_else(|| {
+                Error::Encode(
+                    format!("value of parameter {} was already sent", long_data.index).into(),
+                )
+            })?;
+
+            // the server requires at least one packet, even for an empty value
+            let mut sent = false;
+
+            loop {
+                let read = reader.read(&mut chunk).await?;
+
+                if read == 0 && sent {
+                    break;
+                }
+
+                // the server sends no response to this command
+                self.inner
+                    .stream
+                    .send_packet(SendLongData {
+                        statement,
+                        param: long_data.index,
+                        data: &chunk[..read],
+                    })
+                    .await?;
+
+                sent = true;
+            }
+        }
+
+        Ok(())
+    }
+
     #[allow(clippy::needless_lifetimes)]
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
@@ -117,50 +226,98 @@ impl MySqlConnection {
             // to re-use this memory freely between result sets
             let mut columns = Arc::new(Vec::new());
 
-            let (mut column_names, format, mut needs_metadata) = if let Some(arguments) = arguments {
+            // the statement and the number of rows to fetch at a time, if the rows are read
+            // from a cursor; closing a statement closes its cursor so only cached statements,
+            // which outlive the execution, can use one
+            let mut cursor = None;
+
+            let (mut column_names, num_prepared_columns, format, mut needs_metadata) = if let Some(arguments) = arguments {
+                // the values follow the statement id, flags, types and null bitmap
+                self.check_packet_len(
+                    11 + arguments.null_bitmap.len() + 2 * arguments.types.len() + arguments.values.len(),
+                )?;
+
                 if persistent && self.inner.cache_statement.is_enabled() {
                     let (id, metadata) = self
                         .get_or_prepare_statement(sql)
                         .await?;
 
+                    self.send_long_data(id, &arguments).await?;
+
+                    // a cursor is only opened after the column definitions, which are only
+                    // terminated by an OK packet with `CLIENT_DEPRECATE_EOF`
+                    if self.inner.stream.capabilities.contains(Capabilities::DEPRECATE_EOF) {
+                        cursor = self.inner.cursor_fetch_size.map(|rows| (id, rows));
+                    }
+
                     // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
                     self.inner.stream
                         .send_packet(StatementExecute {
                             statement: id,
                             arguments: &arguments,
+                            cursor: cursor.is_some(),
+                            attributes: &self.inner.query_attributes,
                         })
                         .await?;
 
-                    (metadata.column_names, MySqlValueFormat::Binary, false)
+                    (metadata.column_names, metadata.columns.len(), MySqlValueFormat::Binary, false)
                 } else {
                     let (id, metadata) = self
                         .prepare_statement(sql)
                         .await?;
 
+                    if let Err(error) = self.send_long_data(id, &arguments).await {
+                        self.inner.stream.send_packet(StmtClose { statement: id }).await?;
+                        return Err(error);
+                    }
+
                     // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
                     self.inner.stream
                         .send_packet(StatementExecute {
                             statement: id,
                             arguments: &arguments,
+                            cursor: false,
+                            attributes: &self.inner.query_attributes,
                         })
                         .await?;
 
                     self.inner.stream.send_packet(StmtClose { statement: id }).await?;
 
-                    (metadata.column_names, MySqlValueFormat::Binary, false)
+                    (metadata.column_names, metadata.columns.len(), MySqlValueFormat::Binary, false)
                 }
             } else {
                 // https://dev.mysql.com/doc/internals/en/com-query.html
-                self.inner.stream.send_packet(Query(sql)).await?;
+                self.check_packet_len(1 + sql.len())?;
 
-                (Arc::default(), MySqlValueFormat::Text, true)
+                self.inner
+                    .stream
+                    .send_packet(Query {
+                        sql,
+                        attributes: &self.inner.query_attributes,
+                    })
+                    .await?;
+
+                (Arc::default(), 0, MySqlValueFormat::Text, true)
             };
 
+            // the server only marks a result set as carrying OUT parameters in the packet that
+            // terminates it, which it only does for a prepared `CALL`; so for those, we hold back
+            // the most recent row of each result set until we see the next packet
+            let hold_back_rows = matches!(format, MySqlValueFormat::Binary) && is_call(sql);
+
             loop {
                 // query response is a meta-packet which may be one of:
-                //  Ok, Err, ResultSet, or (unhandled) LocalInfileRequest
+                //  Ok, Err, ResultSet, or LocalInfileRequest
                 let mut packet = self.inner.stream.recv_packet().await?;
 
+                if packet[0] == 0xfb {
+                    let request: LocalInfileRequest = packet.decode()?;
+
+                    // the server responds with OK or ERR once it has received the file
+                    self.send_local_infile(&request.filename).await?;
+                    continue;
+                }
+
                 if packet[0] == 0x00 || packet[0] == 0xff {
                     // first packet in a query response is OK or ERR
                     // this indicates either a successful query with no rows at all or a failed query
@@ -171,6 +328,8 @@ impl MySqlConnection {
                     let done = MySqlQueryResult {
                         rows_affected,
                         last_insert_id: ok.last_insert_id,
+                        warnings: ok.warnings,
+                        out_params: None,
                     };
 
                     r#yield!(Either::Left(done));
@@ -181,15 +340,22 @@ impl MySqlConnection {
                     }
 
                     self.inner.stream.waiting.pop_front();
+
+                    if ok.warnings > 0 {
+                        self.handle_warnings().await?;
+                    }
+
                     return Ok(());
                 }
 
                 // otherwise, this first packet is the start of the result-set metadata,
                 *self.inner.stream.waiting.front_mut().unwrap() = Waiting::Row;
 
-                let num_columns = packet.get_uint_lenenc() as usize; // column count
+                let num_columns = packet.get_uint_lenenc()? as usize; // column count
 
-                if needs_metadata {
+                // a statement may return result sets other than the one it was prepared with
+                // (e.g. `CALL`), in which case the prepared metadata does not apply
+                if needs_metadata || num_prepared_columns != num_columns {
                     column_names = Arc::new(recv_result_metadata(&mut self.inner.stream, num_columns, Arc::make_mut(&mut columns)).await?);
                 } else {
                     // next time we hit here, it'll be a new result set and we'll need the
@@ -200,15 +366,52 @@ impl MySqlConnection {
                 }
 
                 // finally, there will be none or many result-rows
+                let mut pending: Option<MySqlRow> = None;
+
                 loop {
-                    let packet = self.inner.stream.recv_packet().await?;
+                    let packet = match self.inner.stream.recv_packet().await {
+                        Ok(packet) => packet,
+                        Err(error) => {
+                            // the row held back was not one of OUT parameters
+                            if let Some(row) = pending.take() {
+                                r#yield!(Either::Right(row));
+                            }
+
+                            return Err(error);
+                        }
+                    };
 
                     if packet[0] == 0xfe && packet.len() < 9 {
                         let eof = packet.eof(self.inner.stream.capabilities)?;
 
+                        // the first packet after the column definitions of a cursor, and
+                        // the end of every batch but the last
+                        if let Some((statement, rows)) = cursor {
+                            if eof.status.contains(Status::SERVER_STATUS_CURSOR_EXISTS)
+                                && !eof.status.contains(Status::SERVER_STATUS_LAST_ROW_SENT)
+                            {
+                                self.inner.stream.send_packet(StmtFetch { statement, rows }).await?;
+                                continue;
+                            }
+                        }
+
+                        let mut out_params = None;
+
+                        if let Some(mut row) = pending.take() {
+                            row.out_params = eof.status.contains(Status::SERVER_PS_OUT_PARAMS);
+
+                            if row.out_params {
+                                out_params = Some(row.clone());
+                            }
+
+                            r#yield!(Either::Right(row));
+                        }
+
                         r#yield!(Either::Left(MySqlQueryResult {
                             rows_affected: 0,
                             last_insert_id: 0,
+                            warnings: eof.warnings,
+                            out_params,
                         }));
 
                         if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
@@ -218,24 +421,54 @@ impl MySqlConnection {
                         }
 
                         self.inner.stream.waiting.pop_front();
+
+                        if eof.warnings > 0 {
+                            self.handle_warnings().await?;
+                        }
+
                         return Ok(());
                     }
 
+                    #[cfg(not(feature = "profile"))]
                     let row = match format {
                         MySqlValueFormat::Binary => packet.decode_with::<BinaryRow, _>(&columns)?.0,
                         MySqlValueFormat::Text => packet.decode_with::<TextRow, _>(&columns)?.0,
                     };
 
-                    let v = Either::Right(MySqlRow {
+                    #[cfg(feature = "profile")]
+                    let (row, decode_timings) = match format {
+                        MySqlValueFormat::Binary => {
+                            let (row, timings) = BinaryRow::decode_profiled(packet.0, &columns)?;
+                            (row.0, timings)
+                        }
+                        MySqlValueFormat::Text => {
+                            let (row, timings) = TextRow::decode_profiled(packet.0, &columns)?;
+                            (row.0, timings)
+                        }
+                    };
+
+                    // catch decoding bugs as close to their source as possible
+                    if self.inner.validate_rows {
+                        row.validate(&columns, format, false)?;
+                    }
+
+                    let row = MySqlRow {
                         row,
                         format,
                         columns: Arc::clone(&columns),
                         column_names: Arc::clone(&column_names),
-                    });
+                        out_params: false,
+                        #[cfg(feature = "profile")]
+                        decode_timings,
+                    };
 
                     logger.increment_rows_returned();
 
-                    r#yield!(v);
+                    if !hold_back_rows {
+                        r#yield!(Either::Right(row));
+                    } else if let Some(v) = pending.replace(row) {
+                        r#yield!(Either::Right(v));
+                    }
                 }
             }
         }))
@@ -367,7 +600,10 @@ async fn recv_result_columns(
     columns.reserve(num_columns);
 
     for ordinal in 0..num_columns {
-        columns.push(recv_next_result_column(&stream.recv().await?, ordinal)?);
+        columns.push(recv_next_result_column(
+            &stream.recv_column_definition().await?,
+            ordinal,
+        )?);
     }
 
     if num_columns > 0 {
@@ -377,7 +613,10 @@ async fn recv_result_columns(
     Ok(())
 }
 
-fn recv_next_result_column(def: &ColumnDefinition, ordinal: usize) -> Result<MySqlColumn, Error> {
+pub(crate) fn recv_next_result_column(
+    def: &ColumnDefinition,
+    ordinal: usize,
+) -> Result<MySqlColumn, Error> {
     // if the alias is empty, use the alias
     // only then use the name
     let name = match (def.name()?, def.alias()?) {
@@ -409,7 +648,7 @@ async fn recv_result_metadata(
     columns.reserve(num_columns);
 
     for ordinal in 0..num_columns {
-        let def: ColumnDefinition = stream.recv().await?;
+        let def = stream.recv_column_definition().await?;
 
         let column = recv_next_result_column(&def, ordinal)?;
 
@@ -421,3 +660,10 @@ async fn recv_result_metadata(
 
     Ok(column_names)
 }
+
+// whether `sql` is a `CALL` statement, whose last result sets may carry OUT parameters
+fn is_call(sql: &str) -> bool {
+    sql.trim_start()
+        .get(..4)
+        .is_some_and(|keyword| keyword.eq_ignore_ascii_case("call"))
+}
@@ -0,0 +1,163 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use bytes::Bytes;
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+
+use crate::error::Error;
+use crate::protocol::text::LocalInfileData;
+use crate::rt;
+use crate::MySqlConnection;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/page_protocol_com_query_response_local_infile_request.html
+
+// the contents of a file are sent in packets of at most this size so that
+// they stay well within the `max_allowed_packet` of the server
+const MAX_DATA_PACKET_LEN: usize = 64 * 1024;
+
+pub(crate) type LocalInfileHandler =
+    Box<dyn FnMut(&str) -> BoxStream<'static, Result<Bytes, Error>> + Send + Sync>;
+
+impl MySqlConnection {
+    /// Sets the handler which provides the contents of the file named by
+    /// a `LOAD DATA LOCAL INFILE` statement, as a stream of chunks.
+    ///
+    /// The handler takes precedence over the directories allowed with
+    /// [`MySqlConnectOptions::allow_local_infile_dir`][crate::MySqlConnectOptions::allow_local_infile_dir].
+    /// An error from the stream ends the contents early and is returned by the query;
+    /// the server still loads the rows it received before the error.
+    ///
+    /// This has no effect unless [`MySqlConnectOptions::local_infile`][crate::MySqlConnectOptions::local_infile]
+    /// was enabled before connecting.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mysql::MySqlConnection) -> sqlx::Result<()> {
+    /// use futures_util::stream::{self, StreamExt};
+    ///
+    /// conn.set_local_infile_handler(|_filename| {
+    ///     stream::iter([Ok("1,one\n".into()), Ok("2,two\n".into())]).boxed()
+    /// });
+    ///
+    /// sqlx::raw_sql("LOAD DATA LOCAL INFILE 'numbers.csv' INTO TABLE numbers FIELDS TERMINATED BY ','")
+    ///     .execute(&mut *conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_local_infile_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(&str) -> BoxStream<'static, Result<Bytes, Error>> + Send + Sync + 'static,
+    {
+        self.inner.local_infile_handler = Some(Box::new(handler));
+    }
+
+    /// Send the contents of the file requested by the server, followed by an empty packet.
+    pub(crate) async fn send_local_infile(&mut self, filename: &str) -> Result<(), Error> {
+        let mut data = match &mut self.inner.local_infile_handler {
+            Some(handler) => handler(filename),
+            None => read_allowed_file(filename, &self.inner.local_infile_dirs),
+        };
+
+        let mut res = Ok(());
+
+        loop {
+            match data.try_next().await {
+                Ok(Some(chunk)) => {
+                    for part in chunk.chunks(MAX_DATA_PACKET_LEN) {
+                        self.inner.stream.write_packet(LocalInfileData(part));
+                    }
+
+                    self.inner.stream.flush().await?;
+                }
+
+                Ok(None) => break,
+
+                Err(error) => {
+                    res = Err(error);
+                    break;
+                }
+            }
+        }
+
+        // the empty packet also ends the contents if they were cut short by an error,
+        // in which case the server loads what it received and responds as usual
+        self.inner.stream.write_packet(LocalInfileData(&[]));
+        self.inner.stream.flush().await?;
+
+        res
+    }
+}
+
+fn read_allowed_file(filename: &str, dirs: &[PathBuf]) -> BoxStream<'static, Result<Bytes, Error>> {
+    let path = PathBuf::from(filename);
+    let dirs = dirs.to_vec();
+
+    Box::pin(try_stream! {
+        let mut file = rt::spawn_blocking(move || open_allowed_file(&path, &dirs)).await?;
+
+        loop {
+            let (returned, chunk) = rt::spawn_blocking(move || {
+                let mut chunk = vec![0; MAX_DATA_PACKET_LEN];
+                let read = file.read(&mut chunk)?;
+                chunk.truncate(read);
+
+                Ok::<_, Error>((file, chunk))
+            })
+            .await?;
+
+            if chunk.is_empty() {
+                return Ok(());
+            }
+
+            file = returned;
+
+            r#yield!(Bytes::from(chunk));
+        }
+    })
+}
+
+fn open_allowed_file(path: &Path, dirs: &[PathBuf]) -> Result<File, Error> {
+    let refused = || {
+        Error::Configuration(
+            format!("LOAD DATA LOCAL INFILE of {path:?} is not allowed by the connection").into(),
+        )
+    };
+
+    // resolve symbolic links and `..` so that the path cannot escape the allowed directories
+    let path = path.canonicalize().map_err(|_| refused())?;
+
+    let allowed = dirs
+        .iter()
+        .filter_map(|dir| dir.canonicalize().ok())
+        .any(|dir| path.starts_with(dir));
+
+    if !allowed {
+        return Err(refused());
+    }
+
+    Ok(File::open(path)?)
+}
+
+#[test]
+fn test_open_allowed_file() {
+    let dir = std::env::temp_dir().join(format!("sqlx-local-infile-{}", std::process::id()));
+    let allowed = dir.join("allowed");
+
+    std::fs::create_dir_all(&allowed).unwrap();
+    std::fs::write(allowed.join("data.csv"), "1,2\n").unwrap();
+    std::fs::write(dir.join("secret.csv"), "3,4\n").unwrap();
+
+    let dirs = [allowed.clone()];
+
+    assert!(open_allowed_file(&allowed.join("data.csv"), &dirs).is_ok());
+    assert!(open_allowed_file(&allowed.join("missing.csv"), &dirs).is_err());
+    assert!(open_allowed_file(&dir.join("secret.csv"), &dirs).is_err());
+    assert!(open_allowed_file(&allowed.join("../secret.csv"), &dirs).is_err());
+    assert!(open_allowed_file(&allowed.join("data.csv"), &[]).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
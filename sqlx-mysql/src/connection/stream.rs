@@ -4,11 +4,14 @@ use std::ops::{Deref, DerefMut};
 use bytes::{Buf, Bytes, BytesMut};
 
 use crate::collation::{CharSet, Collation};
+#[cfg(feature = "compress")]
+use crate::connection::compression::{CompressedSocket, Compression};
 use crate::error::Error;
 use crate::io::MySqlBufExt;
 use crate::io::{Decode, Encode};
 use crate::net::{BufferedSocket, Socket};
 use crate::protocol::response::{EofPacket, ErrPacket, OkPacket, Status};
+use crate::protocol::text::{ColumnDefinition, LocalInfileData};
 use crate::protocol::{Capabilities, Packet};
 use crate::{MySqlConnectOptions, MySqlDatabaseError};
 
@@ -22,6 +25,7 @@ pub struct MySqlStream<S = Box<dyn Socket>> {
     pub(crate) charset: CharSet,
     pub(crate) collation: Collation,
     pub(crate) is_tls: bool,
+    pub(crate) fallback_lenenc: bool,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -51,12 +55,24 @@ impl<S: Socket> MySqlStream<S> {
             | Capabilities::MULTI_RESULTS
             | Capabilities::PLUGIN_AUTH
             | Capabilities::PS_MULTI_RESULTS
+            | Capabilities::CONNECT_ATTRS
+            | Capabilities::QUERY_ATTRIBUTES
             | Capabilities::SSL;
 
         if options.database.is_some() {
             capabilities |= Capabilities::CONNECT_WITH_DB;
         }
 
+        if options.local_infile {
+            capabilities |= Capabilities::LOCAL_FILES;
+        }
+
+        if options.zstd_compression_level.is_some() {
+            capabilities |= Capabilities::ZSTD_COMPRESSION_ALGORITHM;
+        } else if options.compress {
+            capabilities |= Capabilities::COMPRESS;
+        }
+
         Self {
             waiting: VecDeque::new(),
             capabilities,
@@ -66,6 +82,7 @@ impl<S: Socket> MySqlStream<S> {
             charset,
             socket: BufferedSocket::new(socket),
             is_tls: false,
+            fallback_lenenc: options.fallback_lenenc,
         }
     }
 
@@ -76,7 +93,16 @@ impl<S: Socket> MySqlStream<S> {
 
         while !self.waiting.is_empty() {
             while self.waiting.front() == Some(&Waiting::Row) {
-                let packet = self.recv_packet().await?;
+                let packet = match self.recv_packet().await {
+                    Ok(packet) => packet,
+                    // the abandoned response ended with an error, e.g. a lock wait timeout
+                    // part-way through the rows, which has no bearing on the next command
+                    Err(Error::Database(e)) => {
+                        tracing::debug!("discarding the error of an abandoned query: {e}");
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
 
                 if !packet.is_empty() && packet[0] == 0xfe && packet.len() < 9 {
                     let eof = packet.eof(self.capabilities)?;
@@ -90,7 +116,15 @@ impl<S: Socket> MySqlStream<S> {
             }
 
             while self.waiting.front() == Some(&Waiting::Result) {
-                let packet = self.recv_packet().await?;
+                let packet = match self.recv_packet().await {
+                    Ok(packet) => packet,
+                    // as above
+                    Err(Error::Database(e)) => {
+                        tracing::debug!("discarding the error of an abandoned query: {e}");
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
 
                 if !packet.is_empty() && (packet[0] == 0x00 || packet[0] == 0xff) {
                     let ok = packet.ok()?;
@@ -98,6 +132,11 @@ impl<S: Socket> MySqlStream<S> {
                     if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         self.waiting.pop_front();
                     }
+                } else if !packet.is_empty() && packet[0] == 0xfb {
+                    // the query was abandoned before the file of its `LOAD DATA LOCAL INFILE`
+                    // was requested; send no data, after which the server responds with OK
+                    self.write_packet(LocalInfileData(&[]));
+                    self.socket.flush().await?;
                 } else {
                     *self.waiting.front_mut().unwrap() = Waiting::Row;
                     self.skip_result_metadata(packet).await?;
@@ -139,8 +178,6 @@ impl<S: Socket> MySqlStream<S> {
 
         let payload: Bytes = self.socket.read(packet_size).await?;
 
-        // TODO: packet compression
-
         Ok(payload)
     }
 
@@ -189,6 +226,10 @@ impl<S: Socket> MySqlStream<S> {
         self.recv_packet().await?.decode_with(self.capabilities)
     }
 
+    pub(crate) async fn recv_column_definition(&mut self) -> Result<ColumnDefinition, Error> {
+        ColumnDefinition::decode_with_fallback(self.recv_packet().await?.0, self.fallback_lenenc)
+    }
+
     pub(crate) async fn recv_ok(&mut self) -> Result<OkPacket, Error> {
         self.recv_packet().await?.ok()
     }
@@ -202,7 +243,7 @@ impl<S: Socket> MySqlStream<S> {
     }
 
     async fn skip_result_metadata(&mut self, mut packet: Packet<Bytes>) -> Result<(), Error> {
-        let num_columns: u64 = packet.get_uint_lenenc(); // column count
+        let num_columns: u64 = packet.get_uint_lenenc()?; // column count
 
         for _ in 0..num_columns {
             let _ = self.recv_packet().await?;
@@ -223,6 +264,29 @@ impl<S: Socket> MySqlStream<S> {
             charset: self.charset,
             collation: self.collation,
             is_tls: self.is_tls,
+            fallback_lenenc: self.fallback_lenenc,
+        }
+    }
+}
+
+#[cfg(feature = "compress")]
+impl MySqlStream {
+    /// Switch to the compressed protocol, which is used from the end of authentication
+    /// if both sides support it.
+    pub(crate) fn compressed(self, compression: Compression) -> MySqlStream {
+        MySqlStream {
+            socket: BufferedSocket::new(Box::new(CompressedSocket::new(
+                self.socket.into_inner(),
+                compression,
+            ))),
+            server_version: self.server_version,
+            capabilities: self.capabilities,
+            sequence_id: self.sequence_id,
+            waiting: self.waiting,
+            charset: self.charset,
+            collation: self.collation,
+            is_tls: self.is_tls,
+            fallback_lenenc: self.fallback_lenenc,
         }
     }
 }
@@ -240,3 +304,49 @@ impl<S> DerefMut for MySqlStream<S> {
         &mut self.socket
     }
 }
+
+/// A socket which reads from and writes to memory, for testing.
+#[cfg(test)]
+pub(crate) struct MemorySocket {
+    pub(crate) input: bytes::Bytes,
+    pub(crate) output: Vec<u8>,
+}
+
+#[cfg(test)]
+impl Socket for MemorySocket {
+    fn try_read(&mut self, buf: &mut dyn crate::io::ReadBuf) -> std::io::Result<usize> {
+        let buf = buf.init_mut();
+        let len = std::cmp::min(buf.len(), self.input.len());
+
+        buf[..len].copy_from_slice(&self.input[..len]);
+        self.input.advance(len);
+
+        Ok(len)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.output.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn poll_read_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_write_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
@@ -15,6 +15,7 @@ struct MapStream {
     waiting: VecDeque<Waiting>,
     charset: CharSet,
     collation: Collation,
+    fallback_lenenc: bool,
 }
 
 pub(super) async fn maybe_upgrade<S: Socket>(
@@ -86,6 +87,7 @@ pub(super) async fn maybe_upgrade<S: Socket>(
             waiting: stream.waiting,
             charset: stream.charset,
             collation: stream.collation,
+            fallback_lenenc: stream.fallback_lenenc,
         },
     )
     .await
@@ -104,6 +106,7 @@ impl WithSocket for MapStream {
             charset: self.charset,
             collation: self.collation,
             is_tls: true,
+            fallback_lenenc: self.fallback_lenenc,
         }
     }
 }
@@ -0,0 +1,93 @@
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::row::Row;
+use crate::MySqlConnection;
+
+pub(crate) type WarningHandler = Box<dyn FnMut(&[MySqlWarning]) + Send + Sync>;
+
+/// A note, warning or error raised by the last statement, as returned by `SHOW WARNINGS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MySqlWarning {
+    level: String,
+    code: u32,
+    message: String,
+}
+
+impl MySqlWarning {
+    /// Returns the level of the warning: `Note`, `Warning` or `Error`.
+    pub fn level(&self) -> &str {
+        &self.level
+    }
+
+    /// Returns the error code of the warning, e.g. 1265 (`WARN_DATA_TRUNCATED`).
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// Returns the message of the warning.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl MySqlConnection {
+    /// Returns the warnings raised by the last statement, with `SHOW WARNINGS`.
+    ///
+    /// [`MySqlQueryResult::warnings`][crate::MySqlQueryResult::warnings] returns the number
+    /// of warnings, e.g. to fetch them only when there are any. Only the warnings of the
+    /// last statement of a query are kept by the server.
+    pub async fn show_warnings(&mut self) -> Result<Vec<MySqlWarning>, Error> {
+        self.fetch_all("SHOW WARNINGS")
+            .await?
+            .iter()
+            .map(|row| {
+                Ok(MySqlWarning {
+                    level: row.try_get("Level")?,
+                    code: row.try_get("Code")?,
+                    message: row.try_get("Message")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Sets a handler which is called with the warnings raised by a query, fetched
+    /// with [`show_warnings`][Self::show_warnings] after the query completes.
+    ///
+    /// Warnings are only fetched when the server reports any, so that truncated values or
+    /// deprecated syntax can be logged without a round trip after every query. Only the
+    /// warnings of the last statement of a query are kept by the server.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mysql::MySqlConnection) -> sqlx::Result<()> {
+    /// conn.set_warning_handler(|warnings| {
+    ///     for warning in warnings {
+    ///         eprintln!("{} {}: {}", warning.level(), warning.code(), warning.message());
+    ///     }
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_warning_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(&[MySqlWarning]) + Send + Sync + 'static,
+    {
+        self.inner.warning_handler = Some(Box::new(handler));
+    }
+
+    /// Fetch the warnings raised by the last statement and pass them to the handler, if any.
+    pub(crate) async fn handle_warnings(&mut self) -> Result<(), Error> {
+        if self.inner.warning_handler.is_none() {
+            return Ok(());
+        }
+
+        let warnings = self.show_warnings().await?;
+
+        if let Some(handler) = &mut self.inner.warning_handler {
+            handler(&warnings);
+        }
+
+        Ok(())
+    }
+}
@@ -8,34 +8,96 @@ pub trait MySqlBufExt: Buf {
     // NOTE: 0xfb or NULL is only returned for binary value encoding to indicate NULL.
     // NOTE: 0xff is only returned during a result set to indicate ERR.
     // <https://dev.mysql.com/doc/internals/en/integer.html#packet-Protocol::LengthEncodedInteger>
-    fn get_uint_lenenc(&mut self) -> u64;
+    fn get_uint_lenenc(&mut self) -> Result<u64, Error>;
 
     // Read a length-encoded string.
     #[allow(dead_code)]
     fn get_str_lenenc(&mut self) -> Result<String, Error>;
 
     // Read a length-encoded byte sequence.
-    fn get_bytes_lenenc(&mut self) -> Bytes;
+    fn get_bytes_lenenc(&mut self) -> Result<Bytes, Error>;
 }
 
 impl MySqlBufExt for Bytes {
-    fn get_uint_lenenc(&mut self) -> u64 {
-        match self.get_u8() {
+    fn get_uint_lenenc(&mut self) -> Result<u64, Error> {
+        if self.is_empty() {
+            return Err(err_protocol!("length-encoded value at end of buffer"));
+        }
+
+        let prefix = self[0];
+
+        let width = match prefix {
+            0xfc => 2,
+            0xfd => 3,
+            0xfe => 8,
+
+            _ => 0,
+        };
+
+        if self.len() < 1 + width {
+            return Err(err_protocol!(
+                "length-encoded integer needs {} bytes but only {} remain",
+                1 + width,
+                self.len()
+            ));
+        }
+
+        self.advance(1);
+
+        Ok(match prefix {
             0xfc => u64::from(self.get_u16_le()),
             0xfd => self.get_uint_le(3),
             0xfe => self.get_u64_le(),
 
             v => u64::from(v),
-        }
+        })
     }
 
     fn get_str_lenenc(&mut self) -> Result<String, Error> {
-        let size = self.get_uint_lenenc();
+        let size = self.get_uint_lenenc()?;
         self.get_str(size as usize)
     }
 
-    fn get_bytes_lenenc(&mut self) -> Bytes {
-        let size = self.get_uint_lenenc();
-        self.split_to(size as usize)
+    fn get_bytes_lenenc(&mut self) -> Result<Bytes, Error> {
+        let size = self.get_uint_lenenc()?;
+
+        if size > self.len() as u64 {
+            return Err(err_protocol!(
+                "length-encoded value of {} bytes exceeds remaining {} bytes",
+                size,
+                self.len()
+            ));
+        }
+
+        Ok(self.split_to(size as usize))
     }
 }
+
+#[test]
+fn test_get_uint_lenenc_empty() {
+    let mut buf = Bytes::new();
+
+    let err = buf.get_uint_lenenc().unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "encountered unexpected or invalid data: length-encoded value at end of buffer"
+    );
+}
+
+#[test]
+fn test_get_uint_lenenc_truncated() {
+    let mut buf = Bytes::from_static(b"\xfc\x01");
+
+    assert!(buf.get_uint_lenenc().is_err());
+}
+
+#[test]
+fn test_get_uint_lenenc() {
+    let mut buf = Bytes::from_static(b"\xfa\xfc\x01\x02\xfd\x01\x02\x03");
+
+    assert_eq!(buf.get_uint_lenenc().unwrap(), 0xfa);
+    assert_eq!(buf.get_uint_lenenc().unwrap(), 0x0201);
+    assert_eq!(buf.get_uint_lenenc().unwrap(), 0x03_02_01);
+    assert!(buf.is_empty());
+}
@@ -31,17 +31,26 @@ mod value;
 #[cfg(feature = "migrate")]
 mod migrate;
 
+#[cfg(feature = "replay")]
+pub mod replay;
+
+#[cfg(feature = "xprotocol")]
+pub mod xprotocol;
+
+#[cfg(feature = "replication")]
+pub mod replication;
+
 #[cfg(feature = "migrate")]
 mod testing;
 
 pub use arguments::MySqlArguments;
 pub use column::MySqlColumn;
-pub use connection::MySqlConnection;
+pub use connection::{MySqlCancelHandle, MySqlConnection, MySqlWarning};
 pub use database::MySql;
 pub use error::MySqlDatabaseError;
-pub use options::{MySqlConnectOptions, MySqlSslMode};
+pub use options::{MySqlConnectOptions, MySqlSslMode, MySqlTarget};
 pub use query_result::MySqlQueryResult;
-pub use row::MySqlRow;
+pub use row::{MySqlColumnCursor, MySqlRow};
 pub use statement::MySqlStatement;
 pub use transaction::MySqlTransactionManager;
 pub use type_info::MySqlTypeInfo;
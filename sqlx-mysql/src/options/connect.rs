@@ -1,7 +1,8 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
 use crate::executor::Executor;
-use crate::{MySqlConnectOptions, MySqlConnection};
+use crate::row::Row;
+use crate::{MySqlConnectOptions, MySqlConnection, MySqlTarget};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use sqlx_core::Url;
@@ -23,65 +24,55 @@ impl ConnectOptions for MySqlConnectOptions {
         Self::Connection: Sized,
     {
         Box::pin(async move {
-            let mut conn = MySqlConnection::establish(self).await?;
-
-            // After the connection is established, we initialize by configuring a few
-            // connection parameters
-
-            // https://mariadb.com/kb/en/sql-mode/
-
-            // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
-            //                   This means that "A" || "B" can be used in place of CONCAT("A", "B").
-
-            // NO_ENGINE_SUBSTITUTION - If not set, if the available storage engine specified by a CREATE TABLE is
-            //                          not available, a warning is given and the default storage
-            //                          engine is used instead.
-
-            // NO_ZERO_DATE - Don't allow '0000-00-00'. This is invalid in Rust.
-
-            // NO_ZERO_IN_DATE - Don't allow 'YYYY-00-00'. This is invalid in Rust.
-
-            // --
-
-            // Setting the time zone allows us to assume that the output
-            // from a TIMESTAMP field is UTC
-
-            // --
-
-            // https://mathiasbynens.be/notes/mysql-utf8mb4
-
-            let mut sql_mode = Vec::new();
-            if self.pipes_as_concat {
-                sql_mode.push(r#"PIPES_AS_CONCAT"#);
-            }
-            if self.no_engine_subsitution {
-                sql_mode.push(r#"NO_ENGINE_SUBSTITUTION"#);
+            if self.socket.is_some()
+                || (self.fallback_hosts.is_empty() && self.target == MySqlTarget::Any)
+            {
+                return self.connect_host().await;
             }
 
-            let mut options = Vec::new();
-            if !sql_mode.is_empty() {
-                options.push(format!(
-                    r#"sql_mode=(SELECT CONCAT(@@sql_mode, ',{}'))"#,
-                    sql_mode.join(",")
-                ));
-            }
-            if let Some(timezone) = &self.timezone {
-                options.push(format!(r#"time_zone='{}'"#, timezone));
-            }
-            if self.set_names {
-                options.push(format!(
-                    r#"NAMES {} COLLATE {}"#,
-                    conn.inner.stream.charset.as_str(),
-                    conn.inner.stream.collation.as_str()
-                ))
+            let hosts = std::iter::once((&self.host, self.port))
+                .chain(self.fallback_hosts.iter().map(|(host, port)| (host, *port)));
+
+            let mut last_error = None;
+
+            for (host, port) in hosts {
+                let options = self.clone().host(host).port(port);
+
+                let mut conn = match options.connect_host().await {
+                    Ok(conn) => conn,
+                    Err(error) => {
+                        log::debug!("failed to connect to {host}:{port}: {error}");
+                        last_error = Some(error);
+                        continue;
+                    }
+                };
+
+                let read_only = match self.target {
+                    MySqlTarget::Any => return Ok(conn),
+                    MySqlTarget::Primary | MySqlTarget::Replica => {
+                        // Aurora replicas only set `innodb_read_only`
+                        let read_only: i64 = conn
+                            .fetch_one("SELECT @@global.read_only OR @@global.innodb_read_only")
+                            .await?
+                            .try_get(0)?;
+
+                        read_only != 0
+                    }
+                };
+
+                if read_only == (self.target == MySqlTarget::Replica) {
+                    return Ok(conn);
+                }
+
+                // not the target; try the next host
+                let _ = conn.close().await;
             }
 
-            if !options.is_empty() {
-                conn.execute(&*format!(r#"SET {};"#, options.join(",")))
-                    .await?;
-            }
-
-            Ok(conn)
+            Err(last_error.unwrap_or_else(|| {
+                Error::Configuration(
+                    format!("none of the hosts is a {}", self.target.as_str()).into(),
+                )
+            }))
         })
     }
 
@@ -95,3 +86,91 @@ impl ConnectOptions for MySqlConnectOptions {
         self
     }
 }
+
+impl MySqlConnectOptions {
+    /// Connect to `host` and `port`, ignoring `fallback_hosts`.
+    async fn connect_host(&self) -> Result<MySqlConnection, Error> {
+        let mut conn = MySqlConnection::establish(self).await?;
+
+        // After the connection is established, we initialize by configuring a few
+        // connection parameters
+
+        // https://mariadb.com/kb/en/sql-mode/
+
+        // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
+        //                   This means that "A" || "B" can be used in place of CONCAT("A", "B").
+
+        // NO_ENGINE_SUBSTITUTION - If not set, if the available storage engine specified by a CREATE TABLE is
+        //                          not available, a warning is given and the default storage
+        //                          engine is used instead.
+
+        // NO_ZERO_DATE - Don't allow '0000-00-00'. This is invalid in Rust.
+
+        // NO_ZERO_IN_DATE - Don't allow 'YYYY-00-00'. This is invalid in Rust.
+
+        // --
+
+        // Setting the time zone allows us to assume that the output
+        // from a TIMESTAMP field is UTC
+
+        // --
+
+        // https://mathiasbynens.be/notes/mysql-utf8mb4
+
+        let mut sql_mode = Vec::new();
+        if self.pipes_as_concat {
+            sql_mode.push(r#"PIPES_AS_CONCAT"#);
+        }
+        if self.no_engine_subsitution {
+            sql_mode.push(r#"NO_ENGINE_SUBSTITUTION"#);
+        }
+
+        let mut options = Vec::new();
+        match (&self.sql_mode, sql_mode.is_empty()) {
+            (Some(base), true) => options.push(format!(r#"sql_mode='{}'"#, base)),
+            (Some(base), false) => options.push(format!(
+                r#"sql_mode=(SELECT CONCAT('{}', ',{}'))"#,
+                base,
+                sql_mode.join(",")
+            )),
+            (None, false) => options.push(format!(
+                r#"sql_mode=(SELECT CONCAT(@@sql_mode, ',{}'))"#,
+                sql_mode.join(",")
+            )),
+            (None, true) => {}
+        }
+        if let Some(timezone) = &self.timezone {
+            options.push(format!(r#"time_zone='{}'"#, timezone));
+        }
+        if self.set_names {
+            options.push(format!(
+                r#"NAMES {} COLLATE {}"#,
+                conn.inner.stream.charset.as_str(),
+                conn.inner.stream.collation.as_str()
+            ))
+        }
+
+        let mut session_init = Vec::new();
+        if !options.is_empty() {
+            session_init.push(format!(r#"SET {};"#, options.join(",")));
+        }
+        session_init.extend(self.init_statements.iter().cloned());
+
+        for sql in &session_init {
+            conn.execute(&**sql).await?;
+        }
+
+        // run again when the session is reset
+        conn.inner.session_init = session_init;
+
+        // larger commands are refused by the server, which closes the connection
+        let max_allowed_packet: u64 = conn
+            .fetch_one("SELECT @@max_allowed_packet")
+            .await?
+            .try_get(0)?;
+
+        conn.inner.max_allowed_packet = Some(max_allowed_packet);
+
+        Ok(conn)
+    }
+}
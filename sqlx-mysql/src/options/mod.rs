@@ -3,9 +3,11 @@ use std::path::{Path, PathBuf};
 mod connect;
 mod parse;
 mod ssl_mode;
+mod target;
 
 use crate::{connection::LogSettings, net::tls::CertificateInput};
 pub use ssl_mode::MySqlSslMode;
+pub use target::MySqlTarget;
 
 /// Options and flags which can be used to configure a MySQL connection.
 ///
@@ -18,6 +20,9 @@ pub use ssl_mode::MySqlSslMode;
 /// mysql://[host][/database][?properties]
 /// ```
 ///
+/// Several hosts may be given, separated by commas, e.g. `mysql://a:3306,b:3306/db`; they are
+/// tried in order until a connection to the `target` kind of server succeeds.
+///
 /// This type also implements [`FromStr`][std::str::FromStr] so you can parse it from a string
 /// containing a connection URL and then further adjust options if necessary (see example below).
 ///
@@ -28,7 +33,15 @@ pub use ssl_mode::MySqlSslMode;
 /// | `ssl-mode` | `PREFERRED` | Determines whether or with what priority a secure SSL TCP/IP connection will be negotiated. See [`MySqlSslMode`]. |
 /// | `ssl-ca` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
-/// | `socket` | `None` | Path to the unix domain socket, which will be used instead of TCP if set. |
+/// | `charset` | `utf8mb4` | The character set of the connection; see [`MySqlConnectOptions::charset`]. |
+/// | `collation` | `None` | The collation of the connection, by default that of the `charset`; see [`MySqlConnectOptions::collation`]. |
+/// | `timezone` | `+00:00` | The `time_zone` of the session, or empty for the server default; see [`MySqlConnectOptions::timezone`]. |
+/// | `sql-mode` | `None` | The `sql_mode` of the session instead of the server default; see [`MySqlConnectOptions::sql_mode`]. |
+/// | `set-names` | `true` | Whether to issue `SET NAMES` with the charset and collation after connecting; see [`MySqlConnectOptions::set_names`]. |
+/// | `socket` | `None` | Path to the unix domain socket, which will be used instead of TCP if set. A percent-encoded host starting with `/`, like `mysql://%2Fvar%2Frun%2Fmysqld%2Fmysqld.sock/db`, is also taken as the path of the socket. |
+/// | `cursor-fetch-size` | `None` | Fetches the rows of cached prepared statements from a server-side cursor in batches of this size; see [`MySqlConnectOptions::cursor_fetch_size`]. |
+/// | `local-infile` | `false` | Allows `LOAD DATA LOCAL INFILE`; see [`MySqlConnectOptions::local_infile`]. |
+/// | `target` | `any` | The kind of server to connect to among the hosts: `any`, `primary` or `replica`. See [`MySqlTarget`]. |
 ///
 /// # Example
 ///
@@ -63,6 +76,8 @@ pub use ssl_mode::MySqlSslMode;
 pub struct MySqlConnectOptions {
     pub(crate) host: String,
     pub(crate) port: u16,
+    pub(crate) fallback_hosts: Vec<(String, u16)>,
+    pub(crate) target: MySqlTarget,
     pub(crate) socket: Option<PathBuf>,
     pub(crate) username: String,
     pub(crate) password: Option<String>,
@@ -79,7 +94,17 @@ pub struct MySqlConnectOptions {
     pub(crate) enable_cleartext_plugin: bool,
     pub(crate) no_engine_subsitution: bool,
     pub(crate) timezone: Option<String>,
+    pub(crate) sql_mode: Option<String>,
+    pub(crate) init_statements: Vec<String>,
     pub(crate) set_names: bool,
+    pub(crate) fallback_lenenc: bool,
+    pub(crate) validate_rows: bool,
+    pub(crate) compress: bool,
+    pub(crate) zstd_compression_level: Option<u8>,
+    pub(crate) local_infile: bool,
+    pub(crate) local_infile_dirs: Vec<PathBuf>,
+    pub(crate) cursor_fetch_size: Option<u32>,
+    pub(crate) connect_attrs: Vec<(String, String)>,
 }
 
 impl Default for MySqlConnectOptions {
@@ -94,6 +119,8 @@ impl MySqlConnectOptions {
         Self {
             port: 3306,
             host: String::from("localhost"),
+            fallback_hosts: Vec::new(),
+            target: MySqlTarget::Any,
             socket: None,
             username: String::from("root"),
             password: None,
@@ -110,7 +137,17 @@ impl MySqlConnectOptions {
             enable_cleartext_plugin: false,
             no_engine_subsitution: true,
             timezone: Some(String::from("+00:00")),
+            sql_mode: None,
+            init_statements: Vec::new(),
             set_names: true,
+            fallback_lenenc: false,
+            validate_rows: false,
+            compress: false,
+            zstd_compression_level: None,
+            local_infile: false,
+            local_infile_dirs: Vec::new(),
+            cursor_fetch_size: None,
+            connect_attrs: Vec::new(),
         }
     }
 
@@ -131,6 +168,33 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// Adds a host to try, after the host and any previously added hosts, if connecting
+    /// fails or the server is not of the [`target`][Self::target] kind.
+    ///
+    /// ```rust
+    /// # use sqlx_mysql::{MySqlConnectOptions, MySqlTarget};
+    /// let options = MySqlConnectOptions::new()
+    ///     .host("db-a")
+    ///     .fallback_host("db-b", 3306)
+    ///     .target(MySqlTarget::Primary);
+    /// ```
+    pub fn fallback_host(mut self, host: &str, port: u16) -> Self {
+        self.fallback_hosts.push((host.to_owned(), port));
+        self
+    }
+
+    /// Sets the kind of server to connect to among the hosts, which are tried in order.
+    ///
+    /// Other than [`MySqlTarget::Any`], this checks whether each server is read-only
+    /// after connecting, to find the writable primary of e.g. Aurora or Galera without
+    /// a proxy.
+    ///
+    /// By default set to [`MySqlTarget::Any`].
+    pub fn target(mut self, target: MySqlTarget) -> Self {
+        self.target = target;
+        self
+    }
+
     /// Pass a path to a Unix socket. This changes the connection stream from
     /// TCP to UDS.
     ///
@@ -297,6 +361,22 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// If set, the rows of a prepared statement are read from a read-only cursor on the server,
+    /// fetching this many rows at a time, instead of the server sending all of them at once.
+    ///
+    /// This bounds the memory used by the server and the connection buffers when streaming
+    /// a large result set, at the cost of a round trip per batch. It only applies to queries
+    /// with arguments whose statements are cached (see
+    /// [`statement_cache_capacity`][Self::statement_cache_capacity]), and only to servers
+    /// that support `CLIENT_DEPRECATE_EOF` (MySQL 5.7.5 and later, MariaDB 10.2 and later).
+    /// Statements which cannot use a cursor, such as `CALL`, send their rows as usual.
+    ///
+    /// Not set by default.
+    pub fn cursor_fetch_size(mut self, rows: impl Into<Option<u32>>) -> Self {
+        self.cursor_fetch_size = rows.into().filter(|rows| *rows > 0);
+        self
+    }
+
     /// Sets the character set for the connection.
     ///
     /// The default character set is `utf8mb4`. This is supported from MySQL 5.5.3.
@@ -376,6 +456,30 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// If `Some`, sets the `sql_mode` option to the given comma-separated list of modes
+    /// after connecting to the database, instead of extending the server default.
+    ///
+    /// The modes enabled by [`pipes_as_concat`][Self::pipes_as_concat] and
+    /// [`no_engine_subsitution`][Self::no_engine_subsitution] are still added to it.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// <https://dev.mysql.com/doc/refman/8.0/en/sql-mode.html>
+    pub fn sql_mode(mut self, value: impl Into<Option<String>>) -> Self {
+        self.sql_mode = value.into();
+        self
+    }
+
+    /// Adds a statement to execute after connecting to the database, and after the
+    /// session options like the time zone are set.
+    ///
+    /// May be called more than once; the statements are executed in order. They are also
+    /// executed again when the connection is [reset][crate::MySqlConnection::reset].
+    pub fn init_statement(mut self, sql: impl Into<String>) -> Self {
+        self.init_statements.push(sql.into());
+        self
+    }
+
     /// If enabled, `SET NAMES '{charset}' COLLATE '{collation}'` is passed with the values of
     /// [`.charset()`] and [`.collation()`] after connecting to the database.
     ///
@@ -398,6 +502,95 @@ impl MySqlConnectOptions {
         self.set_names = flag_val;
         self
     }
+
+    /// If enabled, columns of a type unknown to SQLx are treated as length-encoded binary data
+    /// (like `BLOB`) instead of failing the query.
+    ///
+    /// Most types added to newer versions of MySQL and MariaDB are sent as length-encoded
+    /// strings, so this allows such columns to be read as bytes until proper support lands.
+    /// If the guess is wrong the rest of the row will fail to decode.
+    ///
+    /// Disabled by default.
+    pub fn fallback_lenenc(mut self, flag_val: bool) -> Self {
+        self.fallback_lenenc = flag_val;
+        self
+    }
+
+    /// If enabled, every row read from the server is checked against the definitions of its
+    /// columns, e.g. that the value of an `INT` column is 4 bytes long in the binary protocol,
+    /// and a row that does not match fails the query with [`Error::Protocol`].
+    ///
+    /// This catches decoding bugs close to their source at the cost of a pass over every row,
+    /// so it is meant for tests and debugging.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Error::Protocol`]: crate::error::Error::Protocol
+    pub fn validate_rows(mut self, flag_val: bool) -> Self {
+        self.validate_rows = flag_val;
+        self
+    }
+
+    /// If enabled, packets are compressed with zlib if the server supports it.
+    ///
+    /// This trades CPU time for network bandwidth and so mostly benefits large result sets
+    /// over slow links.
+    ///
+    /// Disabled by default.
+    #[cfg(feature = "compress")]
+    pub fn compress(mut self, flag_val: bool) -> Self {
+        self.compress = flag_val;
+        self
+    }
+
+    /// If set, packets are compressed with zstd at the given level (1 to 22)
+    /// if the server supports it (MySQL 8.0.18 and later).
+    ///
+    /// This takes precedence over [`compress`][Self::compress]; the connection
+    /// is not compressed at all if the server does not support zstd.
+    ///
+    /// Not set by default.
+    #[cfg(feature = "zstd")]
+    pub fn zstd_compression_level(mut self, level: impl Into<Option<u8>>) -> Self {
+        self.zstd_compression_level = level.into();
+        self
+    }
+
+    /// If enabled, the server may ask for the contents of a file for a
+    /// `LOAD DATA LOCAL INFILE` statement.
+    ///
+    /// The contents are provided by the handler set with
+    /// [`MySqlConnection::set_local_infile_handler`][crate::MySqlConnection::set_local_infile_handler]
+    /// or, without a handler, read from files in the directories allowed with
+    /// [`allow_local_infile_dir`][Self::allow_local_infile_dir].
+    /// Any other file is refused.
+    ///
+    /// Disabled by default.
+    pub fn local_infile(mut self, flag_val: bool) -> Self {
+        self.local_infile = flag_val;
+        self
+    }
+
+    /// Allows `LOAD DATA LOCAL INFILE` to read files in the given directory or
+    /// its subdirectories, and enables [`local_infile`][Self::local_infile].
+    ///
+    /// May be called more than once to allow several directories.
+    pub fn allow_local_infile_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.local_infile = true;
+        self.local_infile_dirs.push(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds a connection attribute, which the server shows in
+    /// `performance_schema.session_connect_attrs`.
+    ///
+    /// `_client_name`, `_client_version`, `_os`, `_platform`, `_pid` and `program_name`
+    /// (the file name of the executable) are always sent; setting `program_name`
+    /// replaces the default.
+    pub fn connect_attr(mut self, key: &str, value: &str) -> Self {
+        self.connect_attrs.push((key.to_owned(), value.to_owned()));
+        self
+    }
 }
 
 impl MySqlConnectOptions {
@@ -1,9 +1,10 @@
+use std::borrow::Cow;
 use std::str::FromStr;
 
 use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
 use sqlx_core::Url;
 
-use crate::{error::Error, MySqlSslMode};
+use crate::{error::Error, MySqlSslMode, MySqlTarget};
 
 use super::MySqlConnectOptions;
 
@@ -12,7 +13,11 @@ impl MySqlConnectOptions {
         let mut options = Self::new();
 
         if let Some(host) = url.host_str() {
-            options = options.host(host);
+            let host_decoded = percent_decode_str(host);
+            options = match host_decoded.clone().next() {
+                Some(b'/') => options.socket(&*host_decoded.decode_utf8().map_err(Error::config)?),
+                _ => options.host(host),
+            }
         }
 
         if let Some(port) = url.port() {
@@ -59,6 +64,24 @@ impl MySqlConnectOptions {
                     options = options.collation(&value);
                 }
 
+                "timezone" | "time-zone" => {
+                    // an empty value keeps the server time zone
+                    options =
+                        options.timezone(Some(value.into_owned()).filter(|tz| !tz.is_empty()));
+                }
+
+                "sql-mode" => {
+                    options = options.sql_mode(Some(value.into_owned()));
+                }
+
+                "target" => {
+                    options = options.target(value.parse()?);
+                }
+
+                "set-names" => {
+                    options = options.set_names(value.parse().map_err(Error::config)?);
+                }
+
                 "sslcert" | "ssl-cert" => options = options.ssl_client_cert(&*value),
 
                 "sslkey" | "ssl-key" => options = options.ssl_client_key(&*value),
@@ -72,6 +95,26 @@ impl MySqlConnectOptions {
                     options = options.socket(&*value);
                 }
 
+                #[cfg(feature = "compress")]
+                "compress" => {
+                    options = options.compress(value.parse().map_err(Error::config)?);
+                }
+
+                "cursor-fetch-size" => {
+                    options =
+                        options.cursor_fetch_size(value.parse::<u32>().map_err(Error::config)?);
+                }
+
+                "local-infile" => {
+                    options = options.local_infile(value.parse().map_err(Error::config)?);
+                }
+
+                #[cfg(feature = "zstd")]
+                "zstd-compression-level" => {
+                    options =
+                        options.zstd_compression_level(value.parse::<u8>().map_err(Error::config)?);
+                }
+
                 _ => {}
             }
         }
@@ -112,7 +155,20 @@ impl MySqlConnectOptions {
         url.query_pairs_mut().append_pair("charset", &self.charset);
 
         if let Some(collation) = &self.collation {
-            url.query_pairs_mut().append_pair("charset", collation);
+            url.query_pairs_mut().append_pair("collation", collation);
+        }
+
+        if self.timezone.as_deref() != Some("+00:00") {
+            url.query_pairs_mut()
+                .append_pair("timezone", self.timezone.as_deref().unwrap_or_default());
+        }
+
+        if let Some(sql_mode) = &self.sql_mode {
+            url.query_pairs_mut().append_pair("sql-mode", sql_mode);
+        }
+
+        if !self.set_names {
+            url.query_pairs_mut().append_pair("set-names", "false");
         }
 
         if let Some(ssl_client_cert) = &self.ssl_client_cert {
@@ -135,6 +191,29 @@ impl MySqlConnectOptions {
                 .append_pair("socket", &socket.to_string_lossy());
         }
 
+        if self.compress {
+            url.query_pairs_mut().append_pair("compress", "true");
+        }
+
+        if let Some(rows) = self.cursor_fetch_size {
+            url.query_pairs_mut()
+                .append_pair("cursor-fetch-size", &rows.to_string());
+        }
+
+        if self.local_infile {
+            url.query_pairs_mut().append_pair("local-infile", "true");
+        }
+
+        if self.target != MySqlTarget::Any {
+            url.query_pairs_mut()
+                .append_pair("target", self.target.as_str());
+        }
+
+        if let Some(level) = self.zstd_compression_level {
+            url.query_pairs_mut()
+                .append_pair("zstd-compression-level", &level.to_string());
+        }
+
         url
     }
 }
@@ -143,11 +222,65 @@ impl FromStr for MySqlConnectOptions {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Error> {
+        let (s, fallback_hosts) = split_hosts(s)?;
+
         let url: Url = s.parse().map_err(Error::config)?;
-        Self::parse_from_url(&url)
+        let mut options = Self::parse_from_url(&url)?;
+
+        options.fallback_hosts = fallback_hosts;
+
+        Ok(options)
     }
 }
 
+/// The hosts following the first in a URL, with their ports.
+type FallbackHosts = Vec<(String, u16)>;
+
+/// Split a URL with a list of hosts, like `mysql://a:3306,b:3306/db`, which [`Url`] cannot
+/// parse, into the URL with only the first host and the remaining hosts.
+fn split_hosts(s: &str) -> Result<(Cow<'_, str>, FallbackHosts), Error> {
+    let Some(authority_start) = s.find("://").map(|i| i + 3) else {
+        return Ok((Cow::Borrowed(s), Vec::new()));
+    };
+
+    let authority_end = s[authority_start..]
+        .find(['/', '?', '#'])
+        .map_or(s.len(), |i| authority_start + i);
+
+    // a username or password may contain `@`
+    let hosts_start = s[authority_start..authority_end]
+        .rfind('@')
+        .map_or(authority_start, |i| authority_start + i + 1);
+
+    let hosts = &s[hosts_start..authority_end];
+
+    let Some((first, rest)) = hosts.split_once(',') else {
+        return Ok((Cow::Borrowed(s), Vec::new()));
+    };
+
+    let fallback_hosts = rest
+        .split(',')
+        .map(|host| {
+            // the port is optional, and an IPv6 address is within brackets
+            match host.rsplit_once(':') {
+                Some((name, port)) if !port.ends_with(']') => {
+                    let port = port.parse().map_err(|_| {
+                        Error::Configuration(format!("invalid port in host {host:?}").into())
+                    })?;
+
+                    Ok((name.to_owned(), port))
+                }
+
+                _ => Ok((host.to_owned(), 3306)),
+            }
+        })
+        .collect::<Result<_, Error>>()?;
+
+    let url = format!("{}{}{}", &s[..hosts_start], first, &s[authority_end..]);
+
+    Ok((Cow::Owned(url), fallback_hosts))
+}
+
 #[test]
 fn it_parses_username_with_at_sign_correctly() {
     let url = "mysql://user@hostname:password@hostname:5432/database";
@@ -176,3 +309,112 @@ fn it_returns_the_parsed_url() {
 
     assert_eq!(expected_url, opts.build_url());
 }
+
+#[test]
+fn it_parses_ssl_mode() {
+    let ssl_mode = |url: &str| MySqlConnectOptions::from_str(url).unwrap().ssl_mode;
+
+    assert!(matches!(
+        ssl_mode("mysql://localhost?ssl-mode=DISABLED"),
+        MySqlSslMode::Disabled
+    ));
+    assert!(matches!(
+        ssl_mode("mysql://localhost?sslmode=required"),
+        MySqlSslMode::Required
+    ));
+    assert!(matches!(
+        ssl_mode("mysql://localhost?ssl-mode=VERIFY_CA"),
+        MySqlSslMode::VerifyCa
+    ));
+    assert!(matches!(
+        ssl_mode("mysql://localhost?ssl-mode=verify-identity"),
+        MySqlSslMode::VerifyIdentity
+    ));
+
+    assert!(MySqlConnectOptions::from_str("mysql://localhost?ssl-mode=verify").is_err());
+}
+
+#[test]
+fn it_parses_socket_from_parameter() {
+    let url = "mysql://root@localhost/database?socket=/var/run/mysqld/mysqld.sock";
+    let opts = MySqlConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(Some("/var/run/mysqld/mysqld.sock".into()), opts.socket);
+    assert_eq!("localhost", opts.host);
+    assert_eq!(Some("database"), opts.database.as_deref());
+}
+
+#[test]
+fn it_parses_socket_percent_encoded() {
+    let url = "mysql://root@%2Fvar%2Frun%2Fmysqld%2Fmysqld.sock/database";
+    let opts = MySqlConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(Some("/var/run/mysqld/mysqld.sock".into()), opts.socket);
+    assert_eq!("root", opts.username);
+    assert_eq!(Some("database"), opts.database.as_deref());
+}
+
+#[test]
+fn it_parses_charset_and_collation() {
+    let url = "mysql://root@localhost/database?charset=latin1&collation=latin1_bin&set-names=false";
+    let opts = MySqlConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(opts.charset, "latin1");
+    assert_eq!(opts.collation.as_deref(), Some("latin1_bin"));
+    assert!(!opts.set_names);
+
+    let opts = MySqlConnectOptions::parse_from_url(&opts.build_url()).unwrap();
+
+    assert_eq!(opts.charset, "latin1");
+    assert_eq!(opts.collation.as_deref(), Some("latin1_bin"));
+    assert!(!opts.set_names);
+}
+
+#[test]
+fn it_parses_session_options() {
+    let url = "mysql://root@localhost/database?timezone=%2B02:00&sql-mode=ANSI_QUOTES";
+    let opts = MySqlConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(opts.timezone.as_deref(), Some("+02:00"));
+    assert_eq!(opts.sql_mode.as_deref(), Some("ANSI_QUOTES"));
+
+    let opts = MySqlConnectOptions::parse_from_url(&opts.build_url()).unwrap();
+
+    assert_eq!(opts.timezone.as_deref(), Some("+02:00"));
+    assert_eq!(opts.sql_mode.as_deref(), Some("ANSI_QUOTES"));
+
+    let opts = MySqlConnectOptions::from_str("mysql://root@localhost/database?timezone=").unwrap();
+
+    assert_eq!(opts.timezone, None);
+    assert_eq!(
+        MySqlConnectOptions::parse_from_url(&opts.build_url())
+            .unwrap()
+            .timezone,
+        None
+    );
+}
+
+#[test]
+fn it_parses_a_list_of_hosts() {
+    let url = "mysql://user:p@ss@a:3307,b,[::1]:3308/database?target=primary";
+    let opts = MySqlConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(opts.username, "user");
+    assert_eq!(opts.password.as_deref(), Some("p@ss"));
+    assert_eq!(opts.host, "a");
+    assert_eq!(opts.port, 3307);
+    assert_eq!(
+        opts.fallback_hosts,
+        [("b".to_owned(), 3306), ("[::1]".to_owned(), 3308)]
+    );
+    assert_eq!(opts.database.as_deref(), Some("database"));
+    assert_eq!(opts.target, MySqlTarget::Primary);
+
+    let opts = MySqlConnectOptions::from_str("mysql://a/database").unwrap();
+
+    assert!(opts.fallback_hosts.is_empty());
+    assert_eq!(opts.target, MySqlTarget::Any);
+
+    assert!(MySqlConnectOptions::from_str("mysql://a,b:port/database").is_err());
+    assert!(MySqlConnectOptions::from_str("mysql://a/database?target=writer").is_err());
+}
@@ -39,8 +39,8 @@ impl FromStr for MySqlSslMode {
             "disabled" => MySqlSslMode::Disabled,
             "preferred" => MySqlSslMode::Preferred,
             "required" => MySqlSslMode::Required,
-            "verify_ca" => MySqlSslMode::VerifyCa,
-            "verify_identity" => MySqlSslMode::VerifyIdentity,
+            "verify_ca" | "verify-ca" => MySqlSslMode::VerifyCa,
+            "verify_identity" | "verify-identity" => MySqlSslMode::VerifyIdentity,
 
             _ => {
                 return Err(Error::Configuration(
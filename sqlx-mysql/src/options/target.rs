@@ -0,0 +1,49 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// The kind of server to connect to, among the hosts of the options.
+///
+/// It is used by the [`target`](super::MySqlConnectOptions::target) method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MySqlTarget {
+    /// Connect to the first host which accepts the connection.
+    ///
+    /// This is the default if `target` is not specified.
+    #[default]
+    Any,
+
+    /// Connect to the first host which is writable, that is, where neither
+    /// `@@global.read_only` nor `@@global.innodb_read_only` is set.
+    Primary,
+
+    /// Connect to the first host which is read-only.
+    Replica,
+}
+
+impl MySqlTarget {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            MySqlTarget::Any => "any",
+            MySqlTarget::Primary => "primary",
+            MySqlTarget::Replica => "replica",
+        }
+    }
+}
+
+impl FromStr for MySqlTarget {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "any" => MySqlTarget::Any,
+            "primary" => MySqlTarget::Primary,
+            "replica" => MySqlTarget::Replica,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {s:?} for `target`").into(),
+                ));
+            }
+        })
+    }
+}
@@ -10,6 +10,9 @@ pub enum AuthPlugin {
     CachingSha2Password,
     Sha256Password,
     MySqlClearPassword,
+    // [MariaDB]
+    #[cfg(feature = "ed25519")]
+    ClientEd25519,
 }
 
 impl AuthPlugin {
@@ -19,6 +22,8 @@ impl AuthPlugin {
             AuthPlugin::CachingSha2Password => "caching_sha2_password",
             AuthPlugin::Sha256Password => "sha256_password",
             AuthPlugin::MySqlClearPassword => "mysql_clear_password",
+            #[cfg(feature = "ed25519")]
+            AuthPlugin::ClientEd25519 => "client_ed25519",
         }
     }
 }
@@ -32,6 +37,13 @@ impl FromStr for AuthPlugin {
             "caching_sha2_password" => Ok(AuthPlugin::CachingSha2Password),
             "sha256_password" => Ok(AuthPlugin::Sha256Password),
             "mysql_clear_password" => Ok(AuthPlugin::MySqlClearPassword),
+            #[cfg(feature = "ed25519")]
+            "client_ed25519" => Ok(AuthPlugin::ClientEd25519),
+
+            #[cfg(not(feature = "ed25519"))]
+            "client_ed25519" => Err(err_protocol!(
+                "authentication plugin client_ed25519 requires the `ed25519` feature"
+            )),
 
             _ => Err(err_protocol!("unknown authentication plugin: {}", s)),
         }
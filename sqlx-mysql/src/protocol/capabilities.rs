@@ -75,6 +75,10 @@ bitflags::bitflags! {
         // Support ZSTD protocol compression
         const ZSTD_COMPRESSION_ALGORITHM = (1 << 26);
 
+        // Support query attributes with COM_QUERY and COM_STMT_EXECUTE.
+        // Only the requests change; result sets are sent as usual.
+        const QUERY_ATTRIBUTES = (1 << 27);
+
         // Verify server certificate
         const SSL_VERIFY_SERVER_CERT = (1 << 30);
 
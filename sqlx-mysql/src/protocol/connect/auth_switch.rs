@@ -41,6 +41,19 @@ impl Decode<'_, bool> for AuthSwitchRequest {
             });
         }
 
+        #[cfg(feature = "ed25519")]
+        if matches!(plugin, AuthPlugin::ClientEd25519) {
+            // MariaDB sends a 32-byte nonce without a NUL terminator
+            if buf.len() != 32 {
+                return Err(err_protocol!(
+                    "expected 32 bytes but found {} bytes",
+                    buf.len()
+                ));
+            }
+
+            return Ok(Self { plugin, data: buf });
+        }
+
         // See: https://github.com/mysql/mysql-server/blob/ea7d2e2d16ac03afdd9cb72a972a95981107bf51/sql/auth/sha2_password.cc#L942
         if buf.len() != 21 {
             return Err(err_protocol!(
@@ -95,3 +108,14 @@ fn test_decode_auth_switch_packet_no_data() {
     assert!(matches!(p.plugin, AuthPlugin::MySqlClearPassword));
     assert_eq!(p.data, Bytes::new());
 }
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn test_decode_auth_switch_ed25519() {
+    const AUTH_SWITCH_ED25519: &[u8] = b"\xfeclient_ed25519\x00abcdefghijabcdefghijabcdefghij01";
+
+    let p = AuthSwitchRequest::decode_with(AUTH_SWITCH_ED25519.into(), false).unwrap();
+
+    assert!(matches!(p.plugin, AuthPlugin::ClientEd25519));
+    assert_eq!(p.data, &b"abcdefghijabcdefghijabcdefghij01"[..]);
+}
@@ -0,0 +1,67 @@
+use crate::io::{BufMutExt, Encode};
+use crate::protocol::auth::AuthPlugin;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_change_user.html
+
+/// Authenticates the connection again, resetting its session state.
+#[derive(Debug)]
+pub struct ChangeUser<'a> {
+    pub username: &'a str,
+    pub auth_response: &'a [u8],
+    pub database: Option<&'a str>,
+    pub collation: u8,
+    pub auth_plugin: Option<AuthPlugin>,
+}
+
+impl Encode<'_, Capabilities> for ChangeUser<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, capabilities: Capabilities) {
+        buf.push(0x11); // COM_CHANGE_USER
+        buf.put_str_nul(self.username);
+
+        if capabilities.contains(Capabilities::SECURE_CONNECTION) {
+            buf.push(self.auth_response.len() as u8);
+            buf.extend_from_slice(self.auth_response);
+        } else {
+            buf.extend_from_slice(self.auth_response);
+            buf.push(0);
+        }
+
+        buf.put_str_nul(self.database.unwrap_or_default());
+
+        if capabilities.contains(Capabilities::PROTOCOL_41) {
+            buf.extend(&u16::from(self.collation).to_le_bytes());
+        }
+
+        if capabilities.contains(Capabilities::PLUGIN_AUTH) {
+            buf.put_str_nul(self.auth_plugin.map_or("", AuthPlugin::name));
+        }
+
+        if capabilities.contains(Capabilities::CONNECT_ATTRS) {
+            // the attributes sent on connect are kept
+            buf.push(0);
+        }
+    }
+}
+
+#[test]
+fn test_encode_change_user() {
+    let mut buf = Vec::new();
+
+    ChangeUser {
+        username: "root",
+        auth_response: b"\x01\x02",
+        database: Some("db"),
+        collation: 45,
+        auth_plugin: Some(AuthPlugin::MySqlNativePassword),
+    }
+    .encode_with(
+        &mut buf,
+        Capabilities::PROTOCOL_41 | Capabilities::SECURE_CONNECTION | Capabilities::PLUGIN_AUTH,
+    );
+
+    assert_eq!(
+        buf,
+        b"\x11root\x00\x02\x01\x02db\x00\x2d\x00mysql_native_password\x00"
+    );
+}
@@ -2,6 +2,8 @@ pub(crate) mod auth;
 mod capabilities;
 pub(crate) mod connect;
 mod packet;
+#[cfg(feature = "replication")]
+pub(crate) mod replication;
 pub(crate) mod response;
 mod row;
 pub(crate) mod statement;
@@ -108,3 +108,36 @@ impl DerefMut for Packet<Bytes> {
         &mut self.0
     }
 }
+
+#[test]
+fn test_encode_packet_split() {
+    let mut sequence_id = 0;
+    let payload = vec![0x41; 0xFF_FF_FF + 10];
+
+    let mut buf = Vec::new();
+    Packet(&payload[..]).encode_with(&mut buf, (Capabilities::empty(), &mut sequence_id));
+
+    assert_eq!(buf.len(), 0xFF_FF_FF + 10 + 8);
+    assert_eq!(&buf[..4], b"\xff\xff\xff\x00");
+    assert_eq!(&buf[4 + 0xFF_FF_FF..][..4], b"\x0a\x00\x00\x01");
+    assert!(buf[4..4 + 0xFF_FF_FF].iter().all(|&b| b == 0x41));
+    assert!(buf[8 + 0xFF_FF_FF..].iter().all(|&b| b == 0x41));
+    assert_eq!(sequence_id, 2);
+}
+
+#[test]
+fn test_encode_packet_split_exact_multiple() {
+    let mut sequence_id = 0xff;
+    let payload = vec![0x41; 0xFF_FF_FF * 2];
+
+    let mut buf = Vec::new();
+    Packet(&payload[..]).encode_with(&mut buf, (Capabilities::empty(), &mut sequence_id));
+
+    // a payload of a multiple of 0xFF_FF_FF bytes is terminated by an empty packet,
+    // and the sequence id wraps around
+    assert_eq!(buf.len(), 0xFF_FF_FF * 2 + 12);
+    assert_eq!(&buf[..4], b"\xff\xff\xff\xff");
+    assert_eq!(&buf[4 + 0xFF_FF_FF..][..4], b"\xff\xff\xff\x00");
+    assert_eq!(&buf[8 + 0xFF_FF_FF * 2..], b"\x00\x00\x00\x01");
+    assert_eq!(sequence_id, 2);
+}
@@ -0,0 +1,88 @@
+use crate::io::Encode;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/page_protocol_com_binlog_dump.html
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/page_protocol_com_binlog_dump_gtid.html
+
+// the server sends an EOF packet instead of waiting once it has sent every event
+pub(crate) const BINLOG_DUMP_NON_BLOCK: u16 = 0x01;
+
+// the GTIDs already received follow the position
+const BINLOG_THROUGH_GTID: u16 = 0x04;
+
+#[derive(Debug)]
+pub(crate) struct BinlogDump<'a> {
+    pub(crate) position: u32,
+    pub(crate) flags: u16,
+    pub(crate) server_id: u32,
+    pub(crate) filename: &'a str,
+}
+
+impl Encode<'_, Capabilities> for BinlogDump<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.push(0x12); // COM_BINLOG_DUMP
+        buf.extend(&self.position.to_le_bytes());
+        buf.extend(&self.flags.to_le_bytes());
+        buf.extend(&self.server_id.to_le_bytes());
+        buf.extend(self.filename.as_bytes());
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct BinlogDumpGtid<'a> {
+    pub(crate) flags: u16,
+    pub(crate) server_id: u32,
+    pub(crate) filename: &'a str,
+    pub(crate) position: u64,
+
+    /// The encoded set of GTIDs already received, which the server skips
+    pub(crate) gtid_set: &'a [u8],
+}
+
+impl Encode<'_, Capabilities> for BinlogDumpGtid<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.push(0x1e); // COM_BINLOG_DUMP_GTID
+        buf.extend(&(self.flags | BINLOG_THROUGH_GTID).to_le_bytes());
+        buf.extend(&self.server_id.to_le_bytes());
+        buf.extend(&(self.filename.len() as u32).to_le_bytes());
+        buf.extend(self.filename.as_bytes());
+        buf.extend(&self.position.to_le_bytes());
+        buf.extend(&(self.gtid_set.len() as u32).to_le_bytes());
+        buf.extend(self.gtid_set);
+    }
+}
+
+#[test]
+fn test_encode_binlog_dump() {
+    let mut buf = Vec::new();
+    BinlogDump {
+        position: 4,
+        flags: BINLOG_DUMP_NON_BLOCK,
+        server_id: 2,
+        filename: "binlog.000001",
+    }
+    .encode_with(&mut buf, Capabilities::empty());
+
+    assert_eq!(
+        buf,
+        b"\x12\x04\x00\x00\x00\x01\x00\x02\x00\x00\x00binlog.000001"
+    );
+}
+
+#[test]
+fn test_encode_binlog_dump_gtid() {
+    let mut buf = Vec::new();
+    BinlogDumpGtid {
+        flags: 0,
+        server_id: 2,
+        filename: "",
+        position: 4,
+        gtid_set: &0_u64.to_le_bytes(),
+    }
+    .encode_with(&mut buf, Capabilities::empty());
+
+    assert_eq!(
+        buf,
+        &b"\x1e\x04\x00\x02\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x00\x00\x00\x00\x08\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00"[..]
+    );
+}
@@ -0,0 +1,5 @@
+mod binlog_dump;
+mod register_replica;
+
+pub(crate) use binlog_dump::{BinlogDump, BinlogDumpGtid, BINLOG_DUMP_NON_BLOCK};
+pub(crate) use register_replica::RegisterReplica;
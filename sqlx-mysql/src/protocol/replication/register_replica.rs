@@ -0,0 +1,47 @@
+use std::cmp;
+
+use crate::io::Encode;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/page_protocol_com_register_slave.html
+
+#[derive(Debug)]
+pub(crate) struct RegisterReplica<'a> {
+    pub(crate) server_id: u32,
+    pub(crate) hostname: &'a str,
+    pub(crate) port: u16,
+}
+
+impl Encode<'_, Capabilities> for RegisterReplica<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.push(0x15); // COM_REGISTER_SLAVE
+        buf.extend(&self.server_id.to_le_bytes());
+
+        // the lengths are a single byte
+        let hostname = &self.hostname.as_bytes()[..cmp::min(self.hostname.len(), 0xff)];
+        buf.push(hostname.len() as u8);
+        buf.extend(hostname);
+
+        buf.push(0); // user
+        buf.push(0); // password
+        buf.extend(&self.port.to_le_bytes());
+        buf.extend(&0_u32.to_le_bytes()); // replication_rank (ignored)
+        buf.extend(&0_u32.to_le_bytes()); // master_id (usually 0)
+    }
+}
+
+#[test]
+fn test_encode_register_replica() {
+    let mut buf = Vec::new();
+    RegisterReplica {
+        server_id: 2,
+        hostname: "cdc",
+        port: 3306,
+    }
+    .encode_with(&mut buf, Capabilities::empty());
+
+    assert_eq!(
+        buf,
+        b"\x15\x02\x00\x00\x00\x03cdc\x00\x00\xea\x0c\x00\x00\x00\x00\x00\x00\x00\x00"
+    );
+}
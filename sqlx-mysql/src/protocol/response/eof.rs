@@ -13,7 +13,6 @@ use crate::protocol::Capabilities;
 /// prior MySQL versions.
 #[derive(Debug)]
 pub struct EofPacket {
-    #[allow(dead_code)]
     pub warnings: u16,
     pub status: Status,
 }
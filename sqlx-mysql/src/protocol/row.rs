@@ -2,14 +2,391 @@ use std::ops::Range;
 
 use bytes::Bytes;
 
-#[derive(Debug)]
+use crate::error::Error;
+#[cfg(feature = "replay")]
+use crate::protocol::statement::BinaryRow;
+#[cfg(feature = "replay")]
+use crate::protocol::text::TextRow;
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::{MySqlColumn, MySqlValueFormat};
+
+#[derive(Debug, Clone)]
 pub(crate) struct Row {
     pub(crate) storage: Bytes,
     pub(crate) values: Vec<Option<Range<usize>>>,
+    // whether each value was skipped by `decode_filtered`; empty if none were
+    pub(crate) skipped: Vec<bool>,
 }
 
 impl Row {
+    /// Decode a row, only keeping the values of columns for which `keep` returns `true`.
+    ///
+    /// Skipped columns have no value and are marked as such; see [`Row::is_skipped`].
+    ///
+    /// This backs [`MySqlReplay::only_columns`][crate::replay::MySqlReplay::only_columns],
+    /// which is its only caller: rows read from a connection are decoded in full.
+    #[cfg(feature = "replay")]
+    pub(crate) fn decode_filtered(
+        buf: Bytes,
+        columns: &[MySqlColumn],
+        format: MySqlValueFormat,
+        keep: impl Fn(usize, ColumnType) -> bool,
+    ) -> Result<Self, Error> {
+        let mut row = match format {
+            MySqlValueFormat::Binary => BinaryRow::decode_filtered(buf, columns, &keep)?.0,
+            MySqlValueFormat::Text => TextRow::decode_filtered(buf, columns, &keep)?.0,
+        };
+
+        let skipped: Vec<bool> = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| !keep(index, column.type_info.r#type))
+            .collect();
+
+        if skipped.contains(&true) {
+            row.skipped = skipped;
+        }
+
+        Ok(row)
+    }
+
     pub(crate) fn get(&self, index: usize) -> Option<&[u8]> {
         self.values[index].clone().map(|col| &self.storage[col])
     }
+
+    /// Returns `true` if the value at `index` was not recorded when decoding the row.
+    pub(crate) fn is_skipped(&self, index: usize) -> bool {
+        self.skipped.get(index).copied().unwrap_or(false)
+    }
+
+    /// Compute a 64-bit FNV-1a hash over the NULL-ness and raw bytes of every value.
+    pub(crate) fn content_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        fn write(hash: &mut u64, bytes: &[u8]) {
+            for byte in bytes {
+                *hash ^= u64::from(*byte);
+                *hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+
+        let mut hash = FNV_OFFSET_BASIS;
+
+        for index in 0..self.values.len() {
+            match self.get(index) {
+                None => write(&mut hash, &[0]),
+
+                Some(value) => {
+                    // prefix with the length so that adjacent values cannot be confused
+                    // with a different split of the same bytes
+                    write(&mut hash, &[1]);
+                    write(&mut hash, &(value.len() as u64).to_le_bytes());
+                    write(&mut hash, value);
+                }
+            }
+        }
+
+        hash
+    }
+
+    /// Check that every value is consistent with the definition of its column.
+    ///
+    /// This is a self-check for the row decoders; a failure indicates that a value was sliced
+    /// incorrectly, which typically means the size of an earlier value was computed wrong.
+    ///
+    /// If `strict` is set, a `NULL` value in a column flagged `NOT NULL` is also an error.
+    /// This indicates a desync or a server bug but is not checked by default as the flag is not
+    /// always reliable for computed columns.
+    ///
+    /// In `strict` mode the length declared by temporal columns is also checked, as the sizes
+    /// of their binary values are assumed from their type. A server that declares a different
+    /// length may be sending a different encoding.
+    pub(crate) fn validate(
+        &self,
+        columns: &[MySqlColumn],
+        format: MySqlValueFormat,
+        strict: bool,
+    ) -> Result<(), Error> {
+        if self.values.len() != columns.len() {
+            return Err(err_protocol!(
+                "row has {} values but {} columns were defined",
+                self.values.len(),
+                columns.len()
+            ));
+        }
+
+        for (index, (value, column)) in self.values.iter().zip(columns).enumerate() {
+            let value = match value {
+                Some(value) => value,
+
+                None if strict
+                    && !self.is_skipped(index)
+                    && column
+                        .flags
+                        .is_some_and(|flags| flags.contains(ColumnFlags::NOT_NULL)) =>
+                {
+                    return Err(err_protocol!(
+                        "column {} is declared NOT NULL but its value is NULL",
+                        index
+                    ));
+                }
+
+                None => continue,
+            };
+
+            if value.end > self.storage.len() || value.start > value.end {
+                return Err(err_protocol!(
+                    "value for column {} ({}..{}) is out of bounds of the row ({} bytes)",
+                    index,
+                    value.start,
+                    value.end,
+                    self.storage.len()
+                ));
+            }
+
+            let ty = column.type_info.r#type;
+
+            if ty == ColumnType::Null {
+                return Err(err_protocol!(
+                    "column {} has type NULL but a value of {} bytes",
+                    index,
+                    value.len()
+                ));
+            }
+
+            if let (true, Some(declared)) = (strict, column.type_info.max_size) {
+                // the display widths of each type; fractional seconds add a '.' and 1-6 digits
+                let known = match ty {
+                    ColumnType::Date => declared == 10,
+                    ColumnType::Year => declared == 4,
+                    ColumnType::Timestamp | ColumnType::Datetime => {
+                        matches!(declared, 19 | 21..=26)
+                    }
+                    ColumnType::Time => matches!(declared, 10 | 12..=17),
+
+                    // the declared width of an integer is only a display hint
+                    _ => true,
+                };
+
+                if !known {
+                    return Err(err_protocol!(
+                        "column {} of type {:?} declares an unexpected length of {}",
+                        index,
+                        ty,
+                        declared
+                    ));
+                }
+            }
+
+            // the text protocol sends every value as a length-encoded string
+            if matches!(format, MySqlValueFormat::Text) {
+                continue;
+            }
+
+            let expected: &[usize] = match ty {
+                ColumnType::LongLong | ColumnType::Double => &[8],
+                ColumnType::Long | ColumnType::Int24 | ColumnType::Float => &[4],
+                ColumnType::Short | ColumnType::Year => &[2],
+                ColumnType::Tiny => &[1],
+
+                // temporal values keep their one-byte length prefix
+                ColumnType::Date => &[1, 5],
+                ColumnType::Timestamp | ColumnType::Datetime => &[1, 5, 8, 12],
+                ColumnType::Time => &[1, 9, 13],
+
+                // everything else is length-prefixed and so may be any size
+                _ => continue,
+            };
+
+            if !expected.contains(&value.len()) {
+                return Err(err_protocol!(
+                    "value for column {} of type {:?} is {} bytes; expected {:?}",
+                    index,
+                    ty,
+                    value.len(),
+                    expected
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_row_content_hash() {
+    let a = Row {
+        storage: Bytes::from_static(b"\x03abc\x02de"),
+        values: vec![Some(1..4), None, Some(5..7)],
+        skipped: Vec::new(),
+    };
+
+    let b = Row {
+        storage: Bytes::from_static(b"\x03abc\x02de"),
+        values: vec![Some(1..4), None, Some(5..7)],
+        skipped: Vec::new(),
+    };
+
+    let c = Row {
+        storage: Bytes::from_static(b"\x03abc\x02df"),
+        values: vec![Some(1..4), None, Some(5..7)],
+        skipped: Vec::new(),
+    };
+
+    assert_eq!(a.content_hash(), b.content_hash());
+    assert_ne!(a.content_hash(), c.content_hash());
+}
+
+#[test]
+fn test_row_validate() {
+    use crate::ext::ustr::UStr;
+    use crate::MySqlTypeInfo;
+
+    let columns = [
+        MySqlColumn {
+            ordinal: 0,
+            name: UStr::Static("id"),
+            type_info: MySqlTypeInfo::binary(ColumnType::Long),
+            flags: None,
+        },
+        MySqlColumn {
+            ordinal: 1,
+            name: UStr::Static("name"),
+            type_info: MySqlTypeInfo::binary(ColumnType::VarString),
+            flags: None,
+        },
+    ];
+
+    let row = Row {
+        storage: Bytes::from_static(b"\x2a\x00\x00\x00\x03foo"),
+        values: vec![Some(0..4), Some(5..8)],
+        skipped: Vec::new(),
+    };
+
+    assert!(row
+        .validate(&columns, MySqlValueFormat::Binary, false)
+        .is_ok());
+
+    // the INT value was sliced one byte short
+    let corrupted = Row {
+        storage: Bytes::from_static(b"\x2a\x00\x00\x00\x03foo"),
+        values: vec![Some(0..3), Some(5..8)],
+        skipped: Vec::new(),
+    };
+
+    let err = corrupted
+        .validate(&columns, MySqlValueFormat::Binary, false)
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "encountered unexpected or invalid data: value for column 0 of type Long is 3 bytes; expected [4]"
+    );
+}
+
+#[test]
+fn test_row_validate_not_null() {
+    use crate::ext::ustr::UStr;
+    use crate::MySqlTypeInfo;
+
+    let columns = [MySqlColumn {
+        ordinal: 0,
+        name: UStr::Static("id"),
+        type_info: MySqlTypeInfo::binary(ColumnType::Long),
+        flags: Some(ColumnFlags::NOT_NULL),
+    }];
+
+    // the NULL bitmap marked the NOT NULL column as NULL
+    let row = Row {
+        storage: Bytes::from_static(b"\x04"),
+        values: vec![None],
+        skipped: Vec::new(),
+    };
+
+    assert!(row
+        .validate(&columns, MySqlValueFormat::Binary, false)
+        .is_ok());
+
+    let err = row
+        .validate(&columns, MySqlValueFormat::Binary, true)
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "encountered unexpected or invalid data: column 0 is declared NOT NULL but its value is NULL"
+    );
+}
+
+#[cfg(feature = "replay")]
+#[test]
+fn test_row_decode_filtered() {
+    use crate::column::test_columns;
+
+    // INT 42, VARCHAR 'foo' and a NULL TINYINT
+    const DATA: &[u8] = b"\x00\x10\x2a\x00\x00\x00\x03foo";
+
+    let columns = test_columns([ColumnType::Long, ColumnType::VarString, ColumnType::Tiny]);
+
+    let row = Row::decode_filtered(DATA.into(), &columns, MySqlValueFormat::Binary, |_, ty| {
+        matches!(ty, ColumnType::Long | ColumnType::Tiny)
+    })
+    .unwrap();
+
+    assert_eq!(row.values.len(), columns.len());
+    assert_eq!(row.get(0), Some(&b"\x2a\x00\x00\x00"[..]));
+    assert_eq!(row.get(1), None);
+    assert_eq!(row.get(2), None);
+
+    // the skipped value is distinguished from the NULL one
+    assert!(!row.is_skipped(0));
+    assert!(row.is_skipped(1));
+    assert!(!row.is_skipped(2));
+
+    // the skipped value was still walked over
+    let row =
+        Row::decode_filtered(DATA.into(), &columns, MySqlValueFormat::Binary, |_, _| true).unwrap();
+
+    assert_eq!(row.get(1), Some(&b"foo"[..]));
+}
+
+#[test]
+fn test_row_validate_declared_len() {
+    use crate::ext::ustr::UStr;
+    use crate::MySqlTypeInfo;
+
+    let column = |max_size| MySqlColumn {
+        ordinal: 0,
+        name: UStr::Static("created"),
+        type_info: MySqlTypeInfo {
+            max_size: Some(max_size),
+            ..MySqlTypeInfo::binary(ColumnType::Date)
+        },
+        flags: None,
+    };
+
+    // DATE '2010-10-17'
+    let row = Row {
+        storage: Bytes::from_static(b"\x04\xda\x07\x0a\x11"),
+        values: vec![Some(0..5)],
+        skipped: Vec::new(),
+    };
+
+    assert!(row
+        .validate(&[column(10)], MySqlValueFormat::Binary, true)
+        .is_ok());
+
+    // only checked in strict mode
+    assert!(row
+        .validate(&[column(8)], MySqlValueFormat::Binary, false)
+        .is_ok());
+
+    let err = row
+        .validate(&[column(8)], MySqlValueFormat::Binary, true)
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "encountered unexpected or invalid data: column 0 of type Date declares an unexpected length of 8"
+    );
 }
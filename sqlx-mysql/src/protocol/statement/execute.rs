@@ -1,5 +1,5 @@
-use crate::io::Encode;
-use crate::protocol::text::ColumnFlags;
+use crate::io::{Encode, MySqlBufMutExt};
+use crate::protocol::text::{ColumnFlags, ColumnType};
 use crate::protocol::Capabilities;
 use crate::MySqlArguments;
 
@@ -9,17 +9,50 @@ use crate::MySqlArguments;
 pub struct Execute<'q> {
     pub statement: u32,
     pub arguments: &'q MySqlArguments,
+
+    /// Open a read-only cursor instead of sending the rows
+    pub cursor: bool,
+
+    /// Query attributes, sent after the arguments when `CLIENT_QUERY_ATTRIBUTES` is negotiated
+    pub attributes: &'q [(String, String)],
 }
 
 impl<'q> Encode<'_, Capabilities> for Execute<'q> {
-    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+    fn encode_with(&self, buf: &mut Vec<u8>, capabilities: Capabilities) {
+        let query_attributes = capabilities.contains(Capabilities::QUERY_ATTRIBUTES);
+        let attributes: &[(String, String)] = if query_attributes {
+            self.attributes
+        } else {
+            &[]
+        };
+
+        let mut flags = if self.cursor {
+            0x01 // CURSOR_TYPE_READ_ONLY
+        } else {
+            0x00 // CURSOR_TYPE_NO_CURSOR
+        };
+
+        if self.arguments.types.is_empty() && !attributes.is_empty() {
+            flags |= 0x08; // PARAMETER_COUNT_AVAILABLE
+        }
+
         buf.push(0x17); // COM_STMT_EXECUTE
         buf.extend(&self.statement.to_le_bytes());
-        buf.push(0); // NO_CURSOR
+        buf.push(flags);
         buf.extend(&1_u32.to_le_bytes()); // iterations (always 1): int<4>
 
-        if !self.arguments.types.is_empty() {
+        let parameter_count = self.arguments.types.len() + attributes.len();
+
+        if parameter_count > 0 {
+            if query_attributes {
+                buf.put_uint_lenenc(parameter_count as u64);
+            }
+
+            // attribute values are never NULL
+            let null_bitmap_len = buf.len() + (parameter_count + 7) / 8;
             buf.extend_from_slice(&self.arguments.null_bitmap);
+            buf.resize(null_bitmap_len, 0);
+
             buf.push(1); // send type to server
 
             for ty in &self.arguments.types {
@@ -30,9 +63,89 @@ impl<'q> Encode<'_, Capabilities> for Execute<'q> {
                 } else {
                     0
                 });
+
+                if query_attributes {
+                    buf.put_str_lenenc(""); // parameter_name
+                }
+            }
+
+            for (name, _) in attributes {
+                buf.push(ColumnType::VarString as u8);
+                buf.push(0);
+                buf.put_str_lenenc(name);
             }
 
             buf.extend(&*self.arguments.values);
+
+            for (_, value) in attributes {
+                buf.put_str_lenenc(value);
+            }
         }
     }
 }
+
+#[test]
+fn test_encode_execute_cursor() {
+    let arguments = MySqlArguments::default();
+
+    let mut buf = Vec::new();
+    Execute {
+        statement: 1,
+        arguments: &arguments,
+        cursor: true,
+        attributes: &[],
+    }
+    .encode_with(&mut buf, Capabilities::empty());
+
+    assert_eq!(buf, b"\x17\x01\x00\x00\x00\x01\x01\x00\x00\x00");
+}
+
+#[test]
+fn test_encode_execute_query_attributes() {
+    let mut arguments = MySqlArguments::default();
+    arguments.add(1_i32).unwrap();
+
+    let attributes = [("trace_id".to_owned(), "abc".to_owned())];
+    let execute = Execute {
+        statement: 1,
+        arguments: &arguments,
+        cursor: false,
+        attributes: &attributes,
+    };
+
+    let mut buf = Vec::new();
+    execute.encode_with(&mut buf, Capabilities::empty());
+
+    assert_eq!(
+        buf,
+        b"\x17\x01\x00\x00\x00\x00\x01\x00\x00\x00\x00\x01\x03\x00\x01\x00\x00\x00"
+    );
+
+    let mut buf = Vec::new();
+    execute.encode_with(&mut buf, Capabilities::QUERY_ATTRIBUTES);
+
+    assert_eq!(
+        buf,
+        &b"\x17\x01\x00\x00\x00\x00\x01\x00\x00\x00\x02\x00\x01\x03\x00\x00\xfd\x00\x08trace_id\x01\x00\x00\x00\x03abc"[..]
+    );
+}
+
+#[test]
+fn test_encode_execute_query_attributes_without_arguments() {
+    let arguments = MySqlArguments::default();
+    let attributes = [("trace_id".to_owned(), "abc".to_owned())];
+
+    let mut buf = Vec::new();
+    Execute {
+        statement: 1,
+        arguments: &arguments,
+        cursor: false,
+        attributes: &attributes,
+    }
+    .encode_with(&mut buf, Capabilities::QUERY_ATTRIBUTES);
+
+    assert_eq!(
+        buf,
+        &b"\x17\x01\x00\x00\x00\x08\x01\x00\x00\x00\x01\x00\x01\xfd\x00\x08trace_id\x03abc"[..]
+    );
+}
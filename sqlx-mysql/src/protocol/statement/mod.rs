@@ -2,10 +2,18 @@ mod execute;
 mod prepare;
 mod prepare_ok;
 mod row;
+mod send_long_data;
 mod stmt_close;
+mod stmt_fetch;
+mod stmt_reset;
 
 pub(crate) use execute::Execute;
 pub(crate) use prepare::Prepare;
 pub(crate) use prepare_ok::PrepareOk;
+#[cfg(feature = "replay")]
+pub(crate) use row::binary_value_size;
 pub(crate) use row::BinaryRow;
+pub(crate) use send_long_data::SendLongData;
 pub(crate) use stmt_close::StmtClose;
+pub(crate) use stmt_fetch::StmtFetch;
+pub(crate) use stmt_reset::StmtReset;
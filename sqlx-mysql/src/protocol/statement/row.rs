@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use bytes::{Buf, Bytes};
 
 use crate::error::Error;
@@ -14,87 +16,369 @@ use crate::MySqlColumn;
 pub(crate) struct BinaryRow(pub(crate) Row);
 
 impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
-    fn decode_with(mut buf: Bytes, columns: &'de [MySqlColumn]) -> Result<Self, Error> {
-        let header = buf.get_u8();
-        if header != 0 {
+    fn decode_with(buf: Bytes, columns: &'de [MySqlColumn]) -> Result<Self, Error> {
+        decode_binary_row::<false, _>(buf, columns, &mut Vec::new(), |_, _| true)
+    }
+}
+
+impl BinaryRow {
+    /// Decode a row, recording only the values of columns for which `keep` returns `true`.
+    ///
+    /// Fixed-width values are stepped over without reading them.
+    #[cfg(feature = "replay")]
+    pub(crate) fn decode_filtered(
+        buf: Bytes,
+        columns: &[MySqlColumn],
+        keep: impl Fn(usize, ColumnType) -> bool,
+    ) -> Result<Self, Error> {
+        decode_binary_row::<false, _>(buf, columns, &mut Vec::new(), keep)
+    }
+
+    /// Decode a row, recording the time spent locating each value.
+    #[cfg(feature = "profile")]
+    pub(crate) fn decode_profiled(
+        buf: Bytes,
+        columns: &[MySqlColumn],
+    ) -> Result<(Self, Vec<Duration>), Error> {
+        let mut timings = Vec::with_capacity(columns.len());
+        let row = decode_binary_row::<true, _>(buf, columns, &mut timings, |_, _| true)?;
+
+        Ok((row, timings))
+    }
+}
+
+// NOTE: `PROFILE` is a const parameter so that the timing is compiled out entirely
+//       from the unprofiled variant
+fn decode_binary_row<const PROFILE: bool, F>(
+    mut buf: Bytes,
+    columns: &[MySqlColumn],
+    timings: &mut Vec<Duration>,
+    keep: F,
+) -> Result<BinaryRow, Error>
+where
+    F: Fn(usize, ColumnType) -> bool,
+{
+    if buf.is_empty() {
+        return Err(err_protocol!(
+            "expected 0x00 (ROW) but found an empty packet"
+        ));
+    }
+
+    let header = buf.get_u8();
+    if header != 0 {
+        return Err(err_protocol!(
+            "exepcted 0x00 (ROW) but found 0x{:02x}",
+            header
+        ));
+    }
+
+    let storage = buf.clone();
+    let offset = buf.len();
+
+    let null_bitmap_len = (columns.len() + 9) / 8;
+
+    if buf.len() < null_bitmap_len {
+        return Err(err_protocol!(
+            "NULL bitmap of {} columns needs {} bytes but only {} remain",
+            columns.len(),
+            null_bitmap_len,
+            buf.len()
+        ));
+    }
+
+    let null_bitmap = buf.get_bytes(null_bitmap_len);
+
+    let mut values = Vec::with_capacity(columns.len());
+
+    for (column_idx, column) in columns.iter().enumerate() {
+        let started = PROFILE.then(Instant::now);
+
+        // NOTE: the column index starts at the 3rd bit
+        let column_null_idx = column_idx + 2;
+        let is_null = null_bitmap[column_null_idx / 8] & (1 << (column_null_idx % 8) as u8) != 0;
+
+        if is_null {
+            values.push(None);
+
+            if let Some(started) = started {
+                timings.push(started.elapsed());
+            }
+
+            continue;
+        }
+
+        // NOTE: MySQL will never generate NULL types for non-NULL values
+        let type_info = &column.type_info;
+
+        let size = binary_value_size(type_info.r#type, &mut buf)?;
+
+        if size > buf.len() {
             return Err(err_protocol!(
-                "exepcted 0x00 (ROW) but found 0x{:02x}",
-                header
+                "value of column {} needs {} bytes but only {} remain",
+                column_idx,
+                size,
+                buf.len()
             ));
         }
 
-        let storage = buf.clone();
-        let offset = buf.len();
+        let offset = offset - buf.len();
+
+        if keep(column_idx, type_info.r#type) {
+            values.push(Some(offset..(offset + size)));
+        } else {
+            values.push(None);
+        }
+
+        buf.advance(size);
+
+        if let Some(started) = started {
+            timings.push(started.elapsed());
+        }
+    }
 
-        let null_bitmap_len = (columns.len() + 9) / 8;
-        let null_bitmap = buf.get_bytes(null_bitmap_len);
+    Ok(BinaryRow(Row {
+        values,
+        storage,
+        skipped: Vec::new(),
+    }))
+}
 
-        let mut values = Vec::with_capacity(columns.len());
+/// Compute the size of the next value of type `ty` in a binary row,
+/// consuming its length prefix if it has one.
+pub(crate) fn binary_value_size(ty: ColumnType, buf: &mut Bytes) -> Result<usize, Error> {
+    // Unlike Postgres, MySQL does not length-prefix every value in a binary row.
+    // Values are *either* fixed-length or length-prefixed,
+    // so we need to inspect the type code to be sure.
+    Ok(match ty {
+        // All fixed-length types.
+        ColumnType::LongLong => 8,
+        ColumnType::Long | ColumnType::Int24 => 4,
+        ColumnType::Short | ColumnType::Year => 2,
+        ColumnType::Tiny => 1,
+        ColumnType::Float => 4,
+        ColumnType::Double => 8,
 
-        for (column_idx, column) in columns.iter().enumerate() {
-            // NOTE: the column index starts at the 3rd bit
-            let column_null_idx = column_idx + 2;
-            let is_null =
-                null_bitmap[column_null_idx / 8] & (1 << (column_null_idx % 8) as u8) != 0;
+        // Blobs and strings are prefixed with their length,
+        // which is itself a length-encoded integer.
+        ColumnType::String
+        | ColumnType::VarChar
+        | ColumnType::VarString
+        | ColumnType::Enum
+        | ColumnType::Set
+        | ColumnType::LongBlob
+        | ColumnType::MediumBlob
+        | ColumnType::Blob
+        | ColumnType::TinyBlob
+        | ColumnType::Geometry
+        | ColumnType::Bit
+        | ColumnType::Decimal
+        | ColumnType::Json
+        | ColumnType::NewDecimal => buf.get_uint_lenenc()? as usize,
 
-            if is_null {
-                values.push(None);
-                continue;
+        // Like strings and blobs, these values are variable-length.
+        // Unlike strings and blobs, however, they exclusively use one byte for length.
+        ColumnType::Time | ColumnType::Timestamp | ColumnType::Date | ColumnType::Datetime => {
+            // Leave the length byte on the front of the value because decoding uses it.
+            match buf.first() {
+                Some(&len) => len as usize + 1,
+                None => return Err(err_protocol!("temporal value at end of buffer")),
             }
+        }
 
-            // NOTE: MySQL will never generate NULL types for non-NULL values
-            let type_info = &column.type_info;
-
-            // Unlike Postgres, MySQL does not length-prefix every value in a binary row.
-            // Values are *either* fixed-length or length-prefixed,
-            // so we need to inspect the type code to be sure.
-            let size: usize = match type_info.r#type {
-                // All fixed-length types.
-                ColumnType::LongLong => 8,
-                ColumnType::Long | ColumnType::Int24 => 4,
-                ColumnType::Short | ColumnType::Year => 2,
-                ColumnType::Tiny => 1,
-                ColumnType::Float => 4,
-                ColumnType::Double => 8,
-
-                // Blobs and strings are prefixed with their length,
-                // which is itself a length-encoded integer.
-                ColumnType::String
-                | ColumnType::VarChar
-                | ColumnType::VarString
-                | ColumnType::Enum
-                | ColumnType::Set
-                | ColumnType::LongBlob
-                | ColumnType::MediumBlob
-                | ColumnType::Blob
-                | ColumnType::TinyBlob
-                | ColumnType::Geometry
-                | ColumnType::Bit
-                | ColumnType::Decimal
-                | ColumnType::Json
-                | ColumnType::NewDecimal => buf.get_uint_lenenc() as usize,
-
-                // Like strings and blobs, these values are variable-length.
-                // Unlike strings and blobs, however, they exclusively use one byte for length.
-                ColumnType::Time
-                | ColumnType::Timestamp
-                | ColumnType::Date
-                | ColumnType::Datetime => {
-                    // Leave the length byte on the front of the value because decoding uses it.
-                    buf[0] as usize + 1
-                }
-
-                // NOTE: MySQL will never generate NULL types for non-NULL values
-                ColumnType::Null => unreachable!(),
-            };
-
-            let offset = offset - buf.len();
+        // NOTE: MySQL will never generate NULL types for non-NULL values
+        ColumnType::Null => {
+            return Err(err_protocol!(
+                "unexpected non-NULL value for a column of type NULL"
+            ));
+        }
+    })
+}
 
-            values.push(Some(offset..(offset + size)));
+#[test]
+fn test_decode_binary_row_blob() {
+    use crate::ext::ustr::UStr;
+    use crate::protocol::text::ColumnFlags;
+    use crate::MySqlTypeInfo;
+
+    // a plain BLOB (0xfc) column followed by a TINYINT to check the cursor advanced correctly
+    const DATA: &[u8] = b"\x00\x00\x05\x00\x01\x02\x03\x04\x2a";
+
+    let columns = [
+        MySqlColumn {
+            ordinal: 0,
+            name: UStr::Static("data"),
+            type_info: MySqlTypeInfo {
+                r#type: ColumnType::Blob,
+                flags: ColumnFlags::BINARY | ColumnFlags::BLOB,
+                max_size: None,
+            },
+            flags: None,
+        },
+        MySqlColumn {
+            ordinal: 1,
+            name: UStr::Static("n"),
+            type_info: MySqlTypeInfo::binary(ColumnType::Tiny),
+            flags: None,
+        },
+    ];
 
-            buf.advance(size);
+    let row = BinaryRow::decode_with(DATA.into(), &columns[..]).unwrap().0;
+
+    assert_eq!(row.get(0), Some(&b"\x00\x01\x02\x03\x04"[..]));
+    assert_eq!(row.get(1), Some(&b"\x2a"[..]));
+}
+
+#[test]
+fn test_decode_binary_row_all_types() {
+    use crate::column::test_columns;
+
+    let values: &[(ColumnType, &[u8])] = &[
+        (ColumnType::Float, b"\x00\x00\xc0\x3f"),
+        (ColumnType::Double, b"\x00\x00\x00\x00\x00\x00\xf8\x3f"),
+        (ColumnType::NewDecimal, b"\x0412.5"),
+        (ColumnType::Decimal, b"\x031.5"),
+        (ColumnType::Bit, b"\x02\x01\x02"),
+        (ColumnType::Year, b"\xe8\x07"),
+        (ColumnType::Enum, b"\x03red"),
+        (ColumnType::Set, b"\x07a,b,c,d"),
+        (ColumnType::Json, b"\x02{}"),
+        (ColumnType::Geometry, b"\x05\x00\x00\x00\x00\x01"),
+        (ColumnType::Date, b"\x04\xe8\x07\x01\x02"),
+        (ColumnType::Time, b"\x00"),
+        (ColumnType::Int24, b"\x01\x00\x00\x00"),
+        (ColumnType::Tiny, b"\x2a"),
+    ];
+
+    let columns = test_columns(values.iter().map(|(ty, _)| *ty));
+
+    let mut data = vec![0_u8; 1 + (values.len() + 9) / 8];
+
+    for (_, value) in values {
+        data.extend_from_slice(value);
+    }
+
+    let row = BinaryRow::decode_with(data.into(), &columns[..]).unwrap().0;
+
+    // length-prefixed values are returned without their prefix, except for temporal types
+    assert_eq!(row.get(0), Some(values[0].1));
+    assert_eq!(row.get(1), Some(values[1].1));
+    assert_eq!(row.get(2), Some(&b"12.5"[..]));
+    assert_eq!(row.get(3), Some(&b"1.5"[..]));
+    assert_eq!(row.get(4), Some(&b"\x01\x02"[..]));
+    assert_eq!(row.get(5), Some(values[5].1));
+    assert_eq!(row.get(6), Some(&b"red"[..]));
+    assert_eq!(row.get(7), Some(&b"a,b,c,d"[..]));
+    assert_eq!(row.get(8), Some(&b"{}"[..]));
+    assert_eq!(row.get(9), Some(&b"\x00\x00\x00\x00\x01"[..]));
+    assert_eq!(row.get(10), Some(values[10].1));
+    assert_eq!(row.get(11), Some(values[11].1));
+    assert_eq!(row.get(12), Some(values[12].1));
+    assert_eq!(row.get(13), Some(&b"\x2a"[..]));
+}
+
+#[test]
+fn test_decode_binary_row_null_type() {
+    use crate::ext::ustr::UStr;
+    use crate::MySqlTypeInfo;
+
+    let columns = [MySqlColumn {
+        ordinal: 0,
+        name: UStr::Static("n"),
+        type_info: MySqlTypeInfo::binary(ColumnType::Null),
+        flags: None,
+    }];
+
+    // the NULL bit of the column is not set
+    let err = BinaryRow::decode_with(b"\x00\x00\x2a"[..].into(), &columns[..]).unwrap_err();
+
+    assert!(matches!(err, Error::Protocol(_)), "{err}");
+}
+
+#[test]
+fn test_decode_binary_row_truncated() {
+    use crate::column::test_columns;
+
+    // a LONG, a NULL TINY, a DATETIME, a VAR_STRING and a DOUBLE
+    const DATA: &[u8] = b"\x00\x08\x2a\x00\x00\x00\x04\xda\x07\x0a\x11\x03foo\
+                          \x00\x00\x00\x00\x00\x00\xf8\x3f";
+
+    let columns = test_columns([
+        ColumnType::Long,
+        ColumnType::Tiny,
+        ColumnType::Datetime,
+        ColumnType::VarString,
+        ColumnType::Double,
+    ]);
+
+    let row = BinaryRow::decode_with(DATA.into(), &columns[..]).unwrap().0;
+    assert_eq!(row.get(3), Some(&b"foo"[..]));
+
+    for len in 0..DATA.len() {
+        assert!(
+            BinaryRow::decode_with(DATA[..len].into(), &columns[..]).is_err(),
+            "row truncated to {len} bytes"
+        );
+    }
+
+    // corrupting any single byte must never panic
+    for idx in 0..DATA.len() {
+        for byte in [0x00, 0x01, 0x7f, 0xfb, 0xfc, 0xfd, 0xfe, 0xff] {
+            let mut data = DATA.to_vec();
+            data[idx] = byte;
+
+            let _ = BinaryRow::decode_with(data.into(), &columns[..]);
         }
+    }
+}
+
+#[test]
+fn test_decode_binary_row_wide_null_bitmap() {
+    use crate::column::test_columns;
+
+    const NUM_COLUMNS: usize = 300;
+
+    let is_null = |idx: usize| idx % 37 == 0;
+
+    let columns = test_columns([ColumnType::Tiny; NUM_COLUMNS]);
+
+    let null_bitmap_len = (NUM_COLUMNS + 9) / 8;
+    assert_eq!(null_bitmap_len, 38);
+
+    let mut data = vec![0_u8; 1 + null_bitmap_len];
+
+    for idx in (0..NUM_COLUMNS).filter(|idx| is_null(*idx)) {
+        let bit = idx + 2;
+        data[1 + bit / 8] |= 1 << (bit % 8);
+    }
 
-        Ok(BinaryRow(Row { values, storage }))
+    for idx in (0..NUM_COLUMNS).filter(|idx| !is_null(*idx)) {
+        data.push(idx as u8);
     }
+
+    let row = BinaryRow::decode_with(data.into(), &columns[..]).unwrap().0;
+
+    assert_eq!(row.values.len(), NUM_COLUMNS);
+
+    for idx in 0..NUM_COLUMNS {
+        if is_null(idx) {
+            assert_eq!(row.get(idx), None, "column {idx} should be NULL");
+        } else {
+            assert_eq!(row.get(idx), Some(&[idx as u8][..]), "column {idx}");
+        }
+    }
+}
+
+#[cfg(feature = "profile")]
+#[test]
+fn test_decode_binary_row_profiled() {
+    use crate::column::test_columns;
+
+    const DATA: &[u8] = b"\x00\x10\x2a\x00\x00\x00\x03foo";
+
+    let columns = test_columns([ColumnType::Long, ColumnType::VarString, ColumnType::Tiny]);
+
+    let (row, timings) = BinaryRow::decode_profiled(DATA.into(), &columns).unwrap();
+
+    assert_eq!(timings.len(), columns.len());
+    assert_eq!(row.0.get(2), None);
 }
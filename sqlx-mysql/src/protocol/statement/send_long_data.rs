@@ -0,0 +1,34 @@
+use crate::io::Encode;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_send_long_data.html
+
+#[derive(Debug)]
+pub struct SendLongData<'a> {
+    pub statement: u32,
+    pub param: u16,
+    pub data: &'a [u8],
+}
+
+impl Encode<'_, Capabilities> for SendLongData<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.push(0x18); // COM_STMT_SEND_LONG_DATA
+        buf.extend(&self.statement.to_le_bytes());
+        buf.extend(&self.param.to_le_bytes());
+        buf.extend_from_slice(self.data);
+    }
+}
+
+#[test]
+fn test_encode_send_long_data() {
+    let mut buf = Vec::new();
+
+    SendLongData {
+        statement: 1,
+        param: 2,
+        data: b"abc",
+    }
+    .encode_with(&mut buf, Capabilities::empty());
+
+    assert_eq!(buf, b"\x18\x01\x00\x00\x00\x02\x00abc");
+}
@@ -134,21 +134,34 @@ impl ColumnDefinition {
     }
 }
 
-impl Decode<'_, Capabilities> for ColumnDefinition {
-    fn decode_with(mut buf: Bytes, _: Capabilities) -> Result<Self, Error> {
-        let catalog = buf.get_bytes_lenenc();
-        let schema = buf.get_bytes_lenenc();
-        let table_alias = buf.get_bytes_lenenc();
-        let table = buf.get_bytes_lenenc();
-        let alias = buf.get_bytes_lenenc();
-        let name = buf.get_bytes_lenenc();
-        let _next_len = buf.get_uint_lenenc(); // always 0x0c
+impl ColumnDefinition {
+    /// Decode a column definition.
+    ///
+    /// If `fallback_lenenc` is set, a column of an unknown type is decoded as a `BLOB` so that
+    /// its values are read as length-encoded bytes, instead of returning an error.
+    pub(crate) fn decode_with_fallback(
+        mut buf: Bytes,
+        fallback_lenenc: bool,
+    ) -> Result<Self, Error> {
+        let catalog = buf.get_bytes_lenenc()?;
+        let schema = buf.get_bytes_lenenc()?;
+        let table_alias = buf.get_bytes_lenenc()?;
+        let table = buf.get_bytes_lenenc()?;
+        let alias = buf.get_bytes_lenenc()?;
+        let name = buf.get_bytes_lenenc()?;
+        let _next_len = buf.get_uint_lenenc()?; // always 0x0c
         let collation = buf.get_u16_le();
         let max_size = buf.get_u32_le();
         let type_id = buf.get_u8();
         let flags = buf.get_u16_le();
         let decimals = buf.get_u8();
 
+        let r#type = match ColumnType::try_from_u16(type_id) {
+            Ok(ty) => ty,
+            Err(_) if fallback_lenenc => ColumnType::Blob,
+            Err(e) => return Err(e),
+        };
+
         Ok(Self {
             catalog,
             schema,
@@ -158,13 +171,19 @@ impl Decode<'_, Capabilities> for ColumnDefinition {
             name,
             collation,
             max_size,
-            r#type: ColumnType::try_from_u16(type_id)?,
+            r#type,
             flags: ColumnFlags::from_bits_truncate(flags),
             decimals,
         })
     }
 }
 
+impl Decode<'_, Capabilities> for ColumnDefinition {
+    fn decode_with(buf: Bytes, _: Capabilities) -> Result<Self, Error> {
+        Self::decode_with_fallback(buf, false)
+    }
+}
+
 impl ColumnType {
     pub(crate) fn name(self, flags: ColumnFlags, max_size: Option<u32>) -> &'static str {
         let is_binary = flags.contains(ColumnFlags::BINARY);
@@ -259,3 +278,36 @@ impl ColumnType {
         })
     }
 }
+
+#[test]
+fn test_decode_parameter_definition() {
+    // parameter definitions sent in response to COM_STMT_PREPARE use the same layout as
+    // column definitions: catalog "def", empty schema and tables, name "?", binary collation,
+    // type LONGLONG (0x08) with the BINARY flag set
+    const DATA: &[u8] =
+        b"\x03def\x00\x00\x00\x01?\x00\x0c\x3f\x00\x15\x00\x00\x00\x08\x80\x00\x00\x00\x00";
+
+    let def = ColumnDefinition::decode_with(DATA.into(), Capabilities::PROTOCOL_41).unwrap();
+
+    assert_eq!(def.r#type, ColumnType::LongLong);
+    assert_eq!(def.flags, ColumnFlags::BINARY);
+    assert_eq!(def.collation, 63);
+    assert_eq!(def.max_size, 21);
+    assert_eq!(def.alias().unwrap(), "?");
+}
+
+#[test]
+fn test_decode_column_definition_unknown_type() {
+    // a column of type 0x14 which is not a known type id
+    const DATA: &[u8] =
+        b"\x03def\x00\x00\x00\x01v\x00\x0c\x3f\x00\xff\xff\x00\x00\x14\x80\x00\x00\x00\x00";
+
+    let err = ColumnDefinition::decode_with_fallback(DATA.into(), false).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "encountered unexpected or invalid data: unknown column type 0x14"
+    );
+
+    let def = ColumnDefinition::decode_with_fallback(DATA.into(), true).unwrap();
+    assert_eq!(def.r#type, ColumnType::Blob);
+}
@@ -0,0 +1,75 @@
+use std::str::from_utf8;
+
+use bytes::{Buf, Bytes};
+
+use crate::error::Error;
+use crate::io::{Decode, Encode};
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/page_protocol_com_query_response_local_infile_request.html
+
+/// Sent by the server in response to a `LOAD DATA LOCAL INFILE` statement to ask for
+/// the contents of a file.
+#[derive(Debug)]
+pub(crate) struct LocalInfileRequest {
+    pub(crate) filename: String,
+}
+
+impl Decode<'_> for LocalInfileRequest {
+    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        if buf.is_empty() {
+            return Err(err_protocol!(
+                "expected 0xfb (LOCAL INFILE) but found an empty packet"
+            ));
+        }
+
+        let header = buf.get_u8();
+        if header != 0xfb {
+            return Err(err_protocol!(
+                "expected 0xfb (LOCAL INFILE) but found 0x{:02x}",
+                header
+            ));
+        }
+
+        let filename = from_utf8(&buf).map_err(Error::protocol)?.to_owned();
+
+        Ok(Self { filename })
+    }
+}
+
+/// A part of the contents of a file requested by [`LocalInfileRequest`].
+///
+/// The contents are ended by an empty packet.
+#[derive(Debug)]
+pub(crate) struct LocalInfileData<'a>(pub(crate) &'a [u8]);
+
+impl Encode<'_, Capabilities> for LocalInfileData<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.extend_from_slice(self.0);
+    }
+}
+
+#[test]
+fn test_decode_local_infile_request() {
+    let request = LocalInfileRequest::decode(b"\xfb/tmp/data.csv"[..].into()).unwrap();
+
+    assert_eq!(request.filename, "/tmp/data.csv");
+
+    assert!(LocalInfileRequest::decode(b"\x00"[..].into()).is_err());
+    assert!(LocalInfileRequest::decode(Bytes::new()).is_err());
+}
+
+#[test]
+fn test_encode_local_infile_data() {
+    use crate::protocol::Packet;
+
+    let mut buf = Vec::new();
+    let mut sequence_id = 2;
+
+    Packet(LocalInfileData(b"1,2\n"))
+        .encode_with(&mut buf, (Capabilities::empty(), &mut sequence_id));
+    Packet(LocalInfileData(&[])).encode_with(&mut buf, (Capabilities::empty(), &mut sequence_id));
+
+    assert_eq!(buf, b"\x04\x00\x00\x021,2\n\x00\x00\x00\x03");
+    assert_eq!(sequence_id, 4);
+}
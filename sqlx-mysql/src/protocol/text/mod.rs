@@ -1,11 +1,15 @@
 mod column;
+mod local_infile;
 mod ping;
 mod query;
 mod quit;
+mod reset_connection;
 mod row;
 
 pub(crate) use column::{ColumnDefinition, ColumnFlags, ColumnType};
+pub(crate) use local_infile::{LocalInfileData, LocalInfileRequest};
 pub(crate) use ping::Ping;
 pub(crate) use query::Query;
 pub(crate) use quit::Quit;
+pub(crate) use reset_connection::ResetConnection;
 pub(crate) use row::TextRow;
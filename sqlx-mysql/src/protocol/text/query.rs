@@ -1,14 +1,93 @@
-use crate::io::Encode;
+use crate::io::{Encode, MySqlBufMutExt};
+use crate::protocol::text::ColumnType;
 use crate::protocol::Capabilities;
 
 // https://dev.mysql.com/doc/internals/en/com-query.html
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/page_protocol_com_query.html
 
 #[derive(Debug)]
-pub(crate) struct Query<'q>(pub(crate) &'q str);
+pub(crate) struct Query<'q> {
+    pub(crate) sql: &'q str,
+
+    /// Query attributes, sent as string parameters when `CLIENT_QUERY_ATTRIBUTES` is negotiated
+    pub(crate) attributes: &'q [(String, String)],
+}
+
+impl<'q> Query<'q> {
+    pub(crate) fn new(sql: &'q str) -> Self {
+        Self {
+            sql,
+            attributes: &[],
+        }
+    }
+}
 
 impl Encode<'_, Capabilities> for Query<'_> {
-    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+    fn encode_with(&self, buf: &mut Vec<u8>, capabilities: Capabilities) {
         buf.push(0x03); // COM_QUERY
-        buf.extend(self.0.as_bytes())
+
+        if capabilities.contains(Capabilities::QUERY_ATTRIBUTES) {
+            buf.put_uint_lenenc(self.attributes.len() as u64); // parameter_count
+            buf.put_uint_lenenc(1); // parameter_set_count (always 1)
+
+            if !self.attributes.is_empty() {
+                // attribute values are never NULL
+                buf.resize(buf.len() + (self.attributes.len() + 7) / 8, 0);
+                buf.push(1); // new_params_bind_flag
+
+                for (name, _) in self.attributes {
+                    buf.push(ColumnType::VarString as u8);
+                    buf.push(0); // signed
+                    buf.put_str_lenenc(name);
+                }
+
+                for (_, value) in self.attributes {
+                    buf.put_str_lenenc(value);
+                }
+            }
+        }
+
+        buf.extend(self.sql.as_bytes())
     }
 }
+
+#[test]
+fn test_encode_query() {
+    let mut buf = Vec::new();
+    Query::new("SELECT 1").encode_with(&mut buf, Capabilities::PROTOCOL_41);
+
+    assert_eq!(buf, b"\x03SELECT 1");
+
+    let mut buf = Vec::new();
+    Query::new("SELECT 1").encode_with(
+        &mut buf,
+        Capabilities::PROTOCOL_41 | Capabilities::QUERY_ATTRIBUTES,
+    );
+
+    assert_eq!(buf, b"\x03\x00\x01SELECT 1");
+}
+
+#[test]
+fn test_encode_query_attributes() {
+    let attributes = [("trace_id".to_owned(), "abc".to_owned())];
+    let query = Query {
+        sql: "SELECT 1",
+        attributes: &attributes,
+    };
+
+    let mut buf = Vec::new();
+    query.encode_with(&mut buf, Capabilities::PROTOCOL_41);
+
+    assert_eq!(buf, b"\x03SELECT 1");
+
+    let mut buf = Vec::new();
+    query.encode_with(
+        &mut buf,
+        Capabilities::PROTOCOL_41 | Capabilities::QUERY_ATTRIBUTES,
+    );
+
+    assert_eq!(
+        buf,
+        b"\x03\x01\x01\x00\x01\xfd\x00\x08trace_id\x03abcSELECT 1"
+    );
+}
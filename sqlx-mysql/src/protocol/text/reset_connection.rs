@@ -0,0 +1,14 @@
+use crate::io::Encode;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_reset_connection.html
+
+/// Resets the session state of the connection without authenticating it again.
+#[derive(Debug)]
+pub(crate) struct ResetConnection;
+
+impl Encode<'_, Capabilities> for ResetConnection {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.push(0x1f); // COM_RESET_CONNECTION
+    }
+}
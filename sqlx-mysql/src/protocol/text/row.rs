@@ -1,36 +1,177 @@
+use std::time::{Duration, Instant};
+
 use bytes::{Buf, Bytes};
 
 use crate::column::MySqlColumn;
 use crate::error::Error;
 use crate::io::Decode;
 use crate::io::MySqlBufExt;
+use crate::protocol::text::ColumnType;
 use crate::protocol::Row;
 
 #[derive(Debug)]
 pub(crate) struct TextRow(pub(crate) Row);
 
 impl<'de> Decode<'de, &'de [MySqlColumn]> for TextRow {
-    fn decode_with(mut buf: Bytes, columns: &'de [MySqlColumn]) -> Result<Self, Error> {
-        let storage = buf.clone();
-        let offset = buf.len();
+    fn decode_with(buf: Bytes, columns: &'de [MySqlColumn]) -> Result<Self, Error> {
+        decode_text_row::<false, _>(buf, columns, &mut Vec::new(), |_, _| true)
+    }
+}
 
-        let mut values = Vec::with_capacity(columns.len());
+impl TextRow {
+    /// Decode a row, recording only the values of columns for which `keep` returns `true`.
+    #[cfg(feature = "replay")]
+    pub(crate) fn decode_filtered(
+        buf: Bytes,
+        columns: &[MySqlColumn],
+        keep: impl Fn(usize, ColumnType) -> bool,
+    ) -> Result<Self, Error> {
+        decode_text_row::<false, _>(buf, columns, &mut Vec::new(), keep)
+    }
 
-        for _ in columns {
-            if buf[0] == 0xfb {
-                // NULL is sent as 0xfb
-                values.push(None);
-                buf.advance(1);
-            } else {
-                let size = buf.get_uint_lenenc() as usize;
-                let offset = offset - buf.len();
+    /// Decode a row, recording the time spent locating each value.
+    #[cfg(feature = "profile")]
+    pub(crate) fn decode_profiled(
+        buf: Bytes,
+        columns: &[MySqlColumn],
+    ) -> Result<(Self, Vec<Duration>), Error> {
+        let mut timings = Vec::with_capacity(columns.len());
+        let row = decode_text_row::<true, _>(buf, columns, &mut timings, |_, _| true)?;
 
-                values.push(Some(offset..(offset + size)));
+        Ok((row, timings))
+    }
+}
+
+// NOTE: `PROFILE` is a const parameter so that the timing is compiled out entirely
+//       from the unprofiled variant
+fn decode_text_row<const PROFILE: bool, F>(
+    mut buf: Bytes,
+    columns: &[MySqlColumn],
+    timings: &mut Vec<Duration>,
+    keep: F,
+) -> Result<TextRow, Error>
+where
+    F: Fn(usize, ColumnType) -> bool,
+{
+    let storage = buf.clone();
+    let offset = buf.len();
+
+    let mut values = Vec::with_capacity(columns.len());
 
-                buf.advance(size);
+    for (column_idx, column) in columns.iter().enumerate() {
+        let started = PROFILE.then(Instant::now);
+
+        if buf.first() == Some(&0xfb) {
+            // NULL is sent as 0xfb
+            values.push(None);
+            buf.advance(1);
+        } else {
+            let size = buf.get_uint_lenenc()?;
+
+            if size > buf.len() as u64 {
+                return Err(err_protocol!(
+                    "value of column {} needs {} bytes but only {} remain",
+                    column_idx,
+                    size,
+                    buf.len()
+                ));
             }
+
+            let size = size as usize;
+            let offset = offset - buf.len();
+
+            if keep(column_idx, column.type_info.r#type) {
+                values.push(Some(offset..(offset + size)));
+            } else {
+                values.push(None);
+            }
+
+            buf.advance(size);
+        }
+
+        if let Some(started) = started {
+            timings.push(started.elapsed());
         }
+    }
+
+    Ok(TextRow(Row {
+        values,
+        storage,
+        skipped: Vec::new(),
+    }))
+}
+
+#[test]
+fn test_decode_text_row_null() {
+    use std::sync::Arc;
+
+    use crate::row::Row as _;
+
+    use crate::column::test_columns;
+    use crate::{MySqlRow, MySqlValueFormat};
+
+    // NULLs are sent as 0xfb, both between and after other values
+    const DATA: &[u8] = b"\x011\xfb\x03foo\xfb";
+
+    let columns = test_columns([
+        ColumnType::Long,
+        ColumnType::VarString,
+        ColumnType::VarString,
+        ColumnType::Long,
+    ]);
+
+    let row = TextRow::decode_with(DATA.into(), &columns[..]).unwrap().0;
+
+    assert_eq!(row.get(0), Some(&b"1"[..]));
+    assert_eq!(row.get(1), None);
+    assert_eq!(row.get(2), Some(&b"foo"[..]));
+    assert_eq!(row.get(3), None);
+
+    let row = MySqlRow {
+        row,
+        format: MySqlValueFormat::Text,
+        columns: Arc::new(columns),
+        column_names: Arc::default(),
+        out_params: false,
+        #[cfg(feature = "profile")]
+        decode_timings: Vec::new(),
+    };
 
-        Ok(TextRow(Row { values, storage }))
+    assert_eq!(row.try_get::<Option<i32>, _>(0).unwrap(), Some(1));
+    assert_eq!(row.try_get::<Option<String>, _>(1).unwrap(), None);
+    assert_eq!(row.try_get::<Option<i32>, _>(3).unwrap(), None);
+
+    assert!(matches!(
+        row.try_get::<String, _>(1),
+        Err(Error::ColumnDecode { .. })
+    ));
+}
+
+#[test]
+fn test_decode_text_row_truncated() {
+    use crate::column::test_columns;
+
+    const DATA: &[u8] = b"\x0242\xfb\x03foo\xfc\x01\x00b";
+
+    let columns = test_columns([ColumnType::VarString; 4]);
+
+    let row = TextRow::decode_with(DATA.into(), &columns[..]).unwrap().0;
+    assert_eq!(row.get(3), Some(&b"b"[..]));
+
+    for len in 0..DATA.len() {
+        assert!(
+            TextRow::decode_with(DATA[..len].into(), &columns[..]).is_err(),
+            "row truncated to {len} bytes"
+        );
+    }
+
+    // corrupting any single byte must never panic
+    for idx in 0..DATA.len() {
+        for byte in [0x00, 0x01, 0x7f, 0xfb, 0xfc, 0xfd, 0xfe, 0xff] {
+            let mut data = DATA.to_vec();
+            data[idx] = byte;
+
+            let _ = TextRow::decode_with(data.into(), &columns[..]);
+        }
     }
 }
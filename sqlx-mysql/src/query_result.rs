@@ -1,9 +1,13 @@
 use std::iter::{Extend, IntoIterator};
 
+use crate::MySqlRow;
+
 #[derive(Debug, Default)]
 pub struct MySqlQueryResult {
     pub(super) rows_affected: u64,
     pub(super) last_insert_id: u64,
+    pub(super) warnings: u16,
+    pub(super) out_params: Option<MySqlRow>,
 }
 
 impl MySqlQueryResult {
@@ -14,6 +18,22 @@ impl MySqlQueryResult {
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    /// Returns the number of warnings raised by the statement,
+    /// which can be listed with [`MySqlConnection::show_warnings`][crate::MySqlConnection::show_warnings].
+    pub fn warnings(&self) -> u16 {
+        self.warnings
+    }
+
+    /// Returns the values of the `OUT` and `INOUT` parameters of a stored procedure invoked
+    /// with `CALL` through a prepared statement, e.g. `CALL p(?, ?)`, in the order of the
+    /// parameters.
+    ///
+    /// This is the same row as the one for which [`MySqlRow::is_out_params`] returns `true`
+    /// when fetching rows.
+    pub fn out_params(&self) -> Option<&MySqlRow> {
+        self.out_params.as_ref()
+    }
 }
 
 impl Extend<MySqlQueryResult> for MySqlQueryResult {
@@ -21,6 +41,11 @@ impl Extend<MySqlQueryResult> for MySqlQueryResult {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
             self.last_insert_id = elem.last_insert_id;
+            self.warnings = self.warnings.saturating_add(elem.warnings);
+
+            if elem.out_params.is_some() {
+                self.out_params = elem.out_params;
+            }
         }
     }
 }
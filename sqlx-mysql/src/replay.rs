@@ -0,0 +1,652 @@
+//! Replay of recorded MySQL wire traffic through the row decoder.
+//!
+//! This is primarily a debugging aid: a problematic response captured from a live server
+//! (e.g. with Wireshark or `tcpdump`) can be fed back through the same decoding logic used by
+//! [`MySqlConnection`][crate::MySqlConnection] to produce a reproducible test case.
+//!
+//! The input is a sequence of framed packets exactly as they appear on the wire; that is,
+//! a 3-byte little-endian payload length and a 1-byte sequence ID followed by the payload.
+//! Use [`MySqlReplay::from_hex`] to read the same from a hex dump.
+
+use std::io::{self, Read};
+use std::sync::Arc;
+
+use bytes::{Buf, Bytes};
+
+use crate::connection::recv_next_result_column;
+use crate::error::Error;
+use crate::ext::ustr::UStr;
+use crate::io::{Decode, MySqlBufExt};
+use crate::protocol::response::{EofPacket, ErrPacket, OkPacket, Status};
+use crate::protocol::statement::binary_value_size;
+use crate::protocol::text::ColumnDefinition;
+use crate::protocol::{Capabilities, Row};
+use crate::HashMap;
+use crate::{MySqlColumn, MySqlDatabaseError, MySqlRow, MySqlTypeInfo, MySqlValueFormat};
+
+/// Selects the columns whose values are kept, see [`MySqlReplay::only_columns`].
+type KeepColumn = Box<dyn Fn(usize, &MySqlTypeInfo) -> bool>;
+
+/// Drives recorded MySQL packets through the row decoder.
+pub struct MySqlReplay<R> {
+    reader: R,
+    format: MySqlValueFormat,
+    capabilities: Capabilities,
+    columns: Arc<Vec<MySqlColumn>>,
+    column_names: Arc<HashMap<UStr, usize>>,
+    sequence_id: Option<u8>,
+    strict: bool,
+    keep: Option<KeepColumn>,
+}
+
+impl MySqlReplay<io::Cursor<Vec<u8>>> {
+    /// Replay packets from a hex dump of the raw wire bytes.
+    ///
+    /// Any ASCII whitespace in the dump is ignored so that the output of most hex dumping tools
+    /// (without offsets or ASCII columns) can be pasted in directly.
+    pub fn from_hex(dump: &str) -> Result<Self, Error> {
+        let digits: String = dump.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+
+        let bytes =
+            hex::decode(digits).map_err(|e| err_protocol!("invalid hex in packet dump: {}", e))?;
+
+        Ok(Self::new(io::Cursor::new(bytes)))
+    }
+}
+
+impl<R: Read> MySqlReplay<R> {
+    /// Replay packets read from `reader`.
+    ///
+    /// Rows are decoded in the text format by default, and the server is assumed to have
+    /// negotiated `CLIENT_DEPRECATE_EOF` as SQLx always requests it.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            format: MySqlValueFormat::Text,
+            capabilities: Capabilities::PROTOCOL_41 | Capabilities::DEPRECATE_EOF,
+            columns: Arc::default(),
+            column_names: Arc::default(),
+            sequence_id: None,
+            strict: false,
+            keep: None,
+        }
+    }
+
+    /// Set the sequence ID expected on the first packet.
+    ///
+    /// Every packet header carries a sequence ID which increments by one with each packet
+    /// in a response; a gap means a packet was dropped or reordered. By default, the first
+    /// packet read sets the starting point, as captures rarely begin at the start of
+    /// a command.
+    pub fn expect_sequence_id(mut self, sequence_id: u8) -> Self {
+        self.sequence_id = Some(sequence_id);
+        self
+    }
+
+    /// Set whether each decoded row is checked against the definitions of its columns.
+    ///
+    /// In strict mode, a row that does not match its column definitions is an error; this
+    /// includes a `NULL` value in a column declared `NOT NULL`, which typically indicates
+    /// the capture is missing data or the server is misbehaving.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Only keep the values of columns for which `keep` returns `true`.
+    ///
+    /// Every value must still be walked to find the next but skipped values are not recorded.
+    /// Getting a skipped value is an error, see [`MySqlRow::is_skipped`].
+    pub fn only_columns(mut self, keep: impl Fn(usize, &MySqlTypeInfo) -> bool + 'static) -> Self {
+        self.keep = Some(Box::new(keep));
+        self
+    }
+
+    /// The sequence ID expected on the next packet, if known.
+    pub fn sequence_id(&self) -> Option<u8> {
+        self.sequence_id
+    }
+
+    /// Set the format rows are decoded in.
+    ///
+    /// Use [`MySqlValueFormat::Binary`] for the response to `COM_STMT_EXECUTE`
+    /// and [`MySqlValueFormat::Text`] (the default) for the response to `COM_QUERY`.
+    pub fn format(mut self, format: MySqlValueFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set whether the capture was taken from a connection using `CLIENT_DEPRECATE_EOF`.
+    ///
+    /// Servers older than MySQL 5.7.5 do not support this and will send EOF packets
+    /// after the column definitions and at the end of a result set.
+    pub fn deprecate_eof(mut self, deprecate_eof: bool) -> Self {
+        self.capabilities
+            .set(Capabilities::DEPRECATE_EOF, deprecate_eof);
+        self
+    }
+
+    /// Read the next packet payload, or `None` if the input is exhausted.
+    pub fn next_packet(&mut self) -> Result<Option<Bytes>, Error> {
+        let mut payload = match self.read_packet_part()? {
+            Some(part) => part,
+            None => return Ok(None),
+        };
+
+        // payloads of 16 MiB or more are split across several packets
+        if payload.len() == 0xFF_FF_FF {
+            let mut full = payload.to_vec();
+
+            loop {
+                let part = self
+                    .read_packet_part()?
+                    .ok_or_else(|| err_protocol!("capture ended in a split packet"))?;
+
+                full.extend_from_slice(&part);
+
+                if part.len() < 0xFF_FF_FF {
+                    break;
+                }
+            }
+
+            payload = full.into();
+        }
+
+        Ok(Some(payload))
+    }
+
+    /// Read one complete result set: the column count, the column definitions and
+    /// all following rows up to and including the terminating packet.
+    pub fn read_result_set(&mut self) -> Result<Vec<MySqlRow>, Error> {
+        let mut packet = self.expect_packet()?;
+
+        let num_columns = packet.get_uint_lenenc()? as usize;
+
+        let mut columns = Vec::with_capacity(num_columns);
+        let mut column_names = HashMap::with_capacity(num_columns);
+
+        for ordinal in 0..num_columns {
+            let def = ColumnDefinition::decode_with(self.expect_packet()?, self.capabilities)?;
+            let column = recv_next_result_column(&def, ordinal)?;
+
+            column_names.insert(column.name.clone(), ordinal);
+            columns.push(column);
+        }
+
+        if !self.capabilities.contains(Capabilities::DEPRECATE_EOF) {
+            EofPacket::decode_with(self.expect_packet()?, self.capabilities)?;
+        }
+
+        self.columns = Arc::new(columns);
+        self.column_names = Arc::new(column_names);
+
+        self.read_rows()
+    }
+
+    /// Read the next row, streaming the value of its last column in chunks as its packets are
+    /// read instead of buffering the whole row.
+    ///
+    /// The values of the preceding columns are skipped and must fit in the first packet of
+    /// the row. Returns `None` at the terminating packet or the end of the input.
+    ///
+    /// The returned chunks must be read to the end before reading anything else.
+    ///
+    /// This is only available when replaying a capture; rows read from a
+    /// [`MySqlConnection`][crate::MySqlConnection] are always buffered whole.
+    pub fn next_row_streamed(&mut self) -> Result<Option<MySqlValueChunks<'_, R>>, Error> {
+        let mut packet = match self.read_packet_part()? {
+            Some(packet) => packet,
+            None => return Ok(None),
+        };
+
+        let split = packet.len() == 0xFF_FF_FF;
+
+        if !split && self.end_of_rows(&packet)?.is_some() {
+            return Ok(None);
+        }
+
+        let last = match self.columns.len().checked_sub(1) {
+            Some(last) => last,
+            None => return Err(err_protocol!("no columns to stream a value from")),
+        };
+
+        let len = match self.format {
+            MySqlValueFormat::Text => {
+                for _ in 0..last {
+                    let size = match packet.first() {
+                        Some(&0xfb) => 1,
+                        _ => packet.get_uint_lenenc()? as usize,
+                    };
+
+                    skip_preceding(&mut packet, size)?;
+                }
+
+                if packet.first() == Some(&0xfb) {
+                    packet.advance(1);
+                    None
+                } else {
+                    Some(packet.get_uint_lenenc()? as usize)
+                }
+            }
+
+            MySqlValueFormat::Binary => {
+                let null_bitmap_len = (self.columns.len() + 9) / 8;
+
+                if packet.len() < 1 + null_bitmap_len || packet[0] != 0 {
+                    return Err(err_protocol!("expected a binary row"));
+                }
+
+                packet.advance(1);
+                let null_bitmap = packet.split_to(null_bitmap_len);
+
+                // NOTE: the column index starts at the 3rd bit
+                let is_null =
+                    |index: usize| null_bitmap[(index + 2) / 8] & (1 << ((index + 2) % 8)) != 0;
+
+                for (index, column) in self.columns[..last].iter().enumerate() {
+                    if !is_null(index) {
+                        let size = binary_value_size(column.type_info.r#type, &mut packet)?;
+                        skip_preceding(&mut packet, size)?;
+                    }
+                }
+
+                if is_null(last) {
+                    None
+                } else {
+                    Some(binary_value_size(
+                        self.columns[last].type_info.r#type,
+                        &mut packet,
+                    )?)
+                }
+            }
+        };
+
+        Ok(Some(MySqlValueChunks {
+            remaining: len.unwrap_or(0),
+            is_null: len.is_none(),
+            pending: Some(packet),
+            split,
+            replay: self,
+        }))
+    }
+
+    /// Read rows for the current set of columns until the terminating packet
+    /// or the end of the input.
+    pub fn read_rows(&mut self) -> Result<Vec<MySqlRow>, Error> {
+        let mut rows: Vec<MySqlRow> = Vec::new();
+
+        while let Some(packet) = self.next_packet()? {
+            if let Some(status) = self.end_of_rows(&packet)? {
+                if status.contains(Status::SERVER_PS_OUT_PARAMS) {
+                    for row in &mut rows {
+                        row.out_params = true;
+                    }
+                }
+
+                break;
+            }
+
+            rows.push(self.decode_row(packet)?);
+        }
+
+        Ok(rows)
+    }
+
+    /// Check if `packet` ends the rows of a result set, returning its status if so.
+    fn end_of_rows(&self, packet: &Bytes) -> Result<Option<Status>, Error> {
+        if packet.is_empty() {
+            return Err(err_protocol!("Packet empty"));
+        }
+
+        if packet[0] == 0xff {
+            return Err(MySqlDatabaseError(ErrPacket::decode_with(
+                packet.clone(),
+                self.capabilities,
+            )?)
+            .into());
+        }
+
+        if packet[0] == 0xfe && packet.len() < 9 {
+            let status = if self.capabilities.contains(Capabilities::DEPRECATE_EOF) {
+                OkPacket::decode(packet.clone())?.status
+            } else {
+                EofPacket::decode_with(packet.clone(), self.capabilities)?.status
+            };
+
+            return Ok(Some(status));
+        }
+
+        Ok(None)
+    }
+
+    fn decode_row(&self, packet: Bytes) -> Result<MySqlRow, Error> {
+        let row = Row::decode_filtered(packet, &self.columns, self.format, |index, _| {
+            self.keep
+                .as_ref()
+                .map_or(true, |keep| keep(index, &self.columns[index].type_info))
+        })?;
+
+        if self.strict {
+            row.validate(&self.columns, self.format, true)?;
+        }
+
+        Ok(MySqlRow {
+            row,
+            format: self.format,
+            columns: Arc::clone(&self.columns),
+            column_names: Arc::clone(&self.column_names),
+            out_params: false,
+            #[cfg(feature = "profile")]
+            decode_timings: Vec::new(),
+        })
+    }
+
+    fn expect_packet(&mut self) -> Result<Bytes, Error> {
+        self.next_packet()?
+            .ok_or_else(|| err_protocol!("capture ended in the middle of a result set"))
+    }
+
+    fn read_packet_part(&mut self) -> Result<Option<Bytes>, Error> {
+        let mut header = [0_u8; 4];
+
+        match self.reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        let packet_size = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+        let sequence_id = header[3];
+
+        let expected = self.sequence_id.unwrap_or(sequence_id);
+
+        if sequence_id != expected {
+            return Err(err_protocol!(
+                "expected packet with sequence ID {} but found {}; packets were dropped or reordered",
+                expected,
+                sequence_id
+            ));
+        }
+
+        self.sequence_id = Some(sequence_id.wrapping_add(1));
+
+        let mut payload = vec![0_u8; packet_size];
+        self.reader.read_exact(&mut payload)?;
+
+        Ok(Some(payload.into()))
+    }
+}
+
+fn skip_preceding(packet: &mut Bytes, size: usize) -> Result<(), Error> {
+    if packet.len() < size {
+        return Err(err_protocol!(
+            "the values preceding a streamed value must fit in the first packet of the row"
+        ));
+    }
+
+    packet.advance(size);
+
+    Ok(())
+}
+
+/// The value of the last column of a row, read in chunks as the packets of the row are read.
+///
+/// Returned by [`MySqlReplay::next_row_streamed`].
+pub struct MySqlValueChunks<'a, R> {
+    replay: &'a mut MySqlReplay<R>,
+    pending: Option<Bytes>,
+    remaining: usize,
+    is_null: bool,
+    // whether the last packet read was of the maximum size and so is continued by another
+    split: bool,
+}
+
+impl<R: Read> MySqlValueChunks<'_, R> {
+    /// The number of bytes of the value left to read, or `None` if it is `NULL`.
+    pub fn remaining(&self) -> Option<usize> {
+        (!self.is_null).then_some(self.remaining)
+    }
+
+    fn next_chunk(&mut self) -> Result<Option<Bytes>, Error> {
+        loop {
+            if let Some(chunk) = self.pending.take() {
+                if chunk.len() > self.remaining {
+                    return Err(err_protocol!(
+                        "row has {} bytes after its last value",
+                        chunk.len() - self.remaining
+                    ));
+                }
+
+                if !chunk.is_empty() {
+                    self.remaining -= chunk.len();
+                    return Ok(Some(chunk));
+                }
+            }
+
+            if !self.split {
+                if self.remaining > 0 {
+                    return Err(err_protocol!(
+                        "row ended {} bytes short of its last value",
+                        self.remaining
+                    ));
+                }
+
+                return Ok(None);
+            }
+
+            // the row continues in the next packet, which is empty if the row
+            // is an exact multiple of the maximum packet size
+            let packet = self
+                .replay
+                .read_packet_part()?
+                .ok_or_else(|| err_protocol!("capture ended in a split packet"))?;
+
+            self.split = packet.len() == 0xFF_FF_FF;
+            self.pending = Some(packet);
+        }
+    }
+}
+
+impl<R: Read> Iterator for MySqlValueChunks<'_, R> {
+    type Item = Result<Bytes, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.next_chunk().transpose();
+
+        if let Some(Err(_)) = chunk {
+            // nothing more can be read after an error
+            self.pending = None;
+            self.remaining = 0;
+            self.split = false;
+        }
+
+        chunk
+    }
+}
+
+#[test]
+fn test_replay_text_rows() {
+    use crate::column::test_columns;
+    use crate::protocol::text::ColumnType;
+    use sqlx_core::row::Row as _;
+
+    // two text rows ("1", "foo") and ("2", NULL), followed by an OK packet terminator
+    const CAPTURE: &[u8] = b"\x06\x00\x00\x03\x011\x03foo\
+        \x03\x00\x00\x04\x012\xfb\
+        \x07\x00\x00\x05\xfe\x00\x00\x02\x00\x00\x00";
+
+    let mut replay = MySqlReplay::new(CAPTURE);
+
+    replay.columns = Arc::new(test_columns([ColumnType::Long, ColumnType::VarString]));
+
+    let rows = replay.read_rows().unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].row.get(0), Some(&b"1"[..]));
+    assert_eq!(rows[0].row.get(1), Some(&b"foo"[..]));
+    assert_eq!(rows[1].row.get(0), Some(&b"2"[..]));
+    assert_eq!(rows[1].row.get(1), None);
+    assert_eq!(rows[1].columns().len(), 2);
+
+    assert!(replay.next_packet().unwrap().is_none());
+}
+
+#[test]
+fn test_replay_only_columns() {
+    use crate::column::test_columns;
+    use crate::protocol::text::ColumnType;
+    use sqlx_core::row::Row as _;
+
+    // a text row ("1", NULL), followed by an OK packet terminator
+    const CAPTURE: &[u8] = b"\x03\x00\x00\x03\x011\xfb\
+        \x07\x00\x00\x04\xfe\x00\x00\x02\x00\x00\x00";
+
+    let mut replay = MySqlReplay::new(CAPTURE).only_columns(|index, _| index == 1);
+
+    replay.columns = Arc::new(test_columns([ColumnType::Long, ColumnType::VarString]));
+
+    let rows = replay.read_rows().unwrap();
+
+    assert_eq!(rows.len(), 1);
+
+    assert!(rows[0].is_skipped(0));
+    assert!(matches!(
+        rows[0].try_get::<i32, _>(0),
+        Err(Error::ColumnDecode { .. })
+    ));
+
+    assert!(!rows[0].is_skipped(1));
+    assert_eq!(rows[0].try_get::<Option<String>, _>(1).unwrap(), None);
+}
+
+#[test]
+fn test_replay_out_params() {
+    use crate::column::test_columns;
+    use crate::protocol::text::ColumnType;
+
+    // the response to a `CALL` through a prepared statement: a result set with the
+    // OUT parameters (terminated with SERVER_PS_OUT_PARAMS | SERVER_MORE_RESULTS_EXISTS)
+    // followed by a result set produced by the procedure body
+    const CAPTURE: &[u8] = b"\x06\x00\x00\x01\x00\x00\x2a\x00\x00\x00\
+        \x07\x00\x00\x02\xfe\x00\x00\x0a\x10\x00\x00\
+        \x06\x00\x00\x03\x00\x00\x07\x00\x00\x00\
+        \x07\x00\x00\x04\xfe\x00\x00\x02\x00\x00\x00";
+
+    let mut replay = MySqlReplay::new(CAPTURE).format(MySqlValueFormat::Binary);
+
+    replay.columns = Arc::new(test_columns([ColumnType::Long]));
+
+    let out = replay.read_rows().unwrap();
+
+    assert_eq!(out.len(), 1);
+    assert!(out[0].is_out_params());
+    assert_eq!(out[0].row.get(0), Some(&b"\x2a\x00\x00\x00"[..]));
+
+    let rows = replay.read_rows().unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert!(!rows[0].is_out_params());
+    assert_eq!(rows[0].row.get(0), Some(&b"\x07\x00\x00\x00"[..]));
+}
+
+#[test]
+fn test_replay_sequence_id() {
+    use crate::column::test_columns;
+    use crate::protocol::text::ColumnType;
+
+    let columns = Arc::new(test_columns([ColumnType::Long]));
+
+    // two rows with sequence IDs 1 and 2, followed by the terminator with sequence ID 3
+    const IN_ORDER: &[u8] = b"\x02\x00\x00\x01\x011\
+        \x02\x00\x00\x02\x012\
+        \x07\x00\x00\x03\xfe\x00\x00\x02\x00\x00\x00";
+
+    let mut replay = MySqlReplay::new(IN_ORDER).expect_sequence_id(1);
+    replay.columns = Arc::clone(&columns);
+
+    assert_eq!(replay.read_rows().unwrap().len(), 2);
+    assert_eq!(replay.sequence_id(), Some(4));
+
+    // the packet with sequence ID 2 is missing
+    const SKIPPED: &[u8] = b"\x02\x00\x00\x01\x011\
+        \x02\x00\x00\x03\x012";
+
+    let mut replay = MySqlReplay::new(SKIPPED);
+    replay.columns = columns;
+
+    let err = replay.read_rows().unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "encountered unexpected or invalid data: expected packet with sequence ID 2 but found 3; \
+         packets were dropped or reordered"
+    );
+}
+
+#[test]
+fn test_replay_streamed_last_column() {
+    use crate::column::test_columns;
+    use crate::protocol::text::ColumnType;
+
+    const MAX: usize = 0xFF_FF_FF;
+
+    // a text row ("1", <BLOB>) split across a full packet and a 10-byte packet;
+    // the length-encoded prefix of the BLOB takes 9 bytes
+    let blob_len = MAX - 11 + 10;
+
+    let mut capture = Vec::with_capacity(MAX + 32);
+
+    capture.extend_from_slice(&[0xff, 0xff, 0xff, 0x00]);
+    capture.extend_from_slice(b"\x011\xfe");
+    capture.extend_from_slice(&(blob_len as u64).to_le_bytes());
+    capture.resize(4 + MAX, b'x');
+
+    capture.extend_from_slice(&[0x0a, 0x00, 0x00, 0x01]);
+    capture.extend_from_slice(&[b'y'; 10]);
+
+    capture.extend_from_slice(b"\x07\x00\x00\x02\xfe\x00\x00\x02\x00\x00\x00");
+
+    let mut replay = MySqlReplay::new(&capture[..]);
+
+    replay.columns = Arc::new(test_columns([ColumnType::Long, ColumnType::LongBlob]));
+
+    let mut chunks = replay.next_row_streamed().unwrap().unwrap();
+
+    assert_eq!(chunks.remaining(), Some(blob_len));
+
+    let first = chunks.next().unwrap().unwrap();
+    assert_eq!(first.len(), MAX - 11);
+    assert!(first.iter().all(|&b| b == b'x'));
+
+    let second = chunks.next().unwrap().unwrap();
+    assert_eq!(&second[..], &[b'y'; 10]);
+
+    assert!(chunks.next().is_none());
+    assert_eq!(chunks.remaining(), Some(0));
+
+    assert!(replay.next_row_streamed().unwrap().is_none());
+}
+
+#[test]
+fn test_replay_err_mid_result_set() {
+    use crate::column::test_columns;
+    use crate::protocol::text::ColumnType;
+
+    // one text row ("1") followed by ER_QUERY_INTERRUPTED in place of the next row
+    const CAPTURE: &[u8] = b"\x02\x00\x00\x03\x011\
+        \x28\x00\x00\x04\xff\x25\x05#70100Query execution was interrupted";
+
+    let mut replay = MySqlReplay::new(CAPTURE);
+
+    replay.columns = Arc::new(test_columns([ColumnType::Long]));
+
+    let err = replay.read_rows().unwrap_err();
+    let err = err
+        .as_database_error()
+        .unwrap()
+        .downcast_ref::<MySqlDatabaseError>();
+
+    assert_eq!(err.number(), 1317);
+    assert_eq!(err.code(), Some("70100"));
+    assert_eq!(err.message(), "Query execution was interrupted");
+}
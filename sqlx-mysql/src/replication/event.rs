@@ -0,0 +1,821 @@
+use std::sync::Arc;
+
+use bytes::{Buf, Bytes};
+
+use crate::error::Error;
+use crate::io::MySqlBufExt;
+use crate::replication::gtid::format_uuid;
+use crate::HashMap;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/page_protocol_replication_binlog_event.html
+
+const QUERY_EVENT: u8 = 2;
+const ROTATE_EVENT: u8 = 4;
+const FORMAT_DESCRIPTION_EVENT: u8 = 15;
+const XID_EVENT: u8 = 16;
+const TABLE_MAP_EVENT: u8 = 19;
+const WRITE_ROWS_EVENT_V1: u8 = 23;
+const UPDATE_ROWS_EVENT_V1: u8 = 24;
+const DELETE_ROWS_EVENT_V1: u8 = 25;
+const HEARTBEAT_EVENT: u8 = 27;
+const WRITE_ROWS_EVENT: u8 = 30;
+const UPDATE_ROWS_EVENT: u8 = 31;
+const DELETE_ROWS_EVENT: u8 = 32;
+const GTID_EVENT: u8 = 33;
+
+const HEADER_LEN: usize = 19;
+
+/// An event read from the binary log of the server.
+#[derive(Debug, Clone)]
+pub struct MySqlBinlogEvent {
+    timestamp: u32,
+    server_id: u32,
+    log_position: u32,
+    data: MySqlBinlogEventData,
+}
+
+impl MySqlBinlogEvent {
+    /// Returns the time the statement of the event started, in seconds since the Unix epoch.
+    pub fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    /// Returns the `server_id` of the server which created the event.
+    pub fn server_id(&self) -> u32 {
+        self.server_id
+    }
+
+    /// Returns the position of the next event in the binary log file, to resume from.
+    ///
+    /// This is 0 for events which are not in the file, like heartbeats.
+    pub fn log_position(&self) -> u32 {
+        self.log_position
+    }
+
+    /// Returns the decoded contents of the event.
+    pub fn data(&self) -> &MySqlBinlogEventData {
+        &self.data
+    }
+
+    pub fn into_data(self) -> MySqlBinlogEventData {
+        self.data
+    }
+}
+
+/// The decoded contents of a [`MySqlBinlogEvent`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum MySqlBinlogEventData {
+    /// The following events are read from another binary log file.
+    Rotate { filename: String, position: u64 },
+
+    /// The first event of every binary log file.
+    FormatDescription {
+        binlog_version: u16,
+        server_version: String,
+    },
+
+    /// A statement, logged as text: DDL, `BEGIN`, or DML with statement-based logging.
+    Query {
+        thread_id: u32,
+        schema: String,
+        query: String,
+    },
+
+    /// The commit of a transaction.
+    Xid { xid: u64 },
+
+    /// The global transaction identifier of the following transaction, as `uuid:number`.
+    Gtid { gtid: String },
+
+    /// The definition of a table, which precedes the row events for that table.
+    TableMap(Arc<MySqlBinlogTable>),
+
+    /// Rows inserted, updated or deleted with row-based logging.
+    Rows(MySqlBinlogRows),
+
+    /// Sent by the server while there are no new events.
+    Heartbeat,
+
+    /// An event which is not decoded.
+    Other { event_type: u8, data: Bytes },
+}
+
+/// The definition of a table in the binary log, from a `TABLE_MAP_EVENT`.
+#[derive(Debug, Clone)]
+pub struct MySqlBinlogTable {
+    table_id: u64,
+    schema: String,
+    name: String,
+    column_types: Vec<u8>,
+    column_metadata: Vec<u16>,
+}
+
+impl MySqlBinlogTable {
+    /// Returns the id of the table, which is only valid within the binary log.
+    pub fn table_id(&self) -> u64 {
+        self.table_id
+    }
+
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of each column, as its `MYSQL_TYPE_*` code.
+    ///
+    /// Columns are identified by position; the binary log does not name them.
+    pub fn column_types(&self) -> &[u8] {
+        &self.column_types
+    }
+}
+
+/// The kind of change of a [`MySqlBinlogRows`] event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MySqlBinlogRowsKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// The rows changed by a statement in one table, from a `*_ROWS_EVENT`.
+#[derive(Debug, Clone)]
+pub struct MySqlBinlogRows {
+    kind: MySqlBinlogRowsKind,
+    table: Arc<MySqlBinlogTable>,
+    rows: Vec<MySqlBinlogRowChange>,
+}
+
+impl MySqlBinlogRows {
+    pub fn kind(&self) -> MySqlBinlogRowsKind {
+        self.kind
+    }
+
+    /// Returns the table of the rows.
+    pub fn table(&self) -> &Arc<MySqlBinlogTable> {
+        &self.table
+    }
+
+    pub fn rows(&self) -> &[MySqlBinlogRowChange] {
+        &self.rows
+    }
+}
+
+/// A row changed by a [`MySqlBinlogRows`] event.
+///
+/// Each image has a value for every column of the table, which is `None` if the column is
+/// not included, e.g. with `binlog_row_image = MINIMAL`.
+#[derive(Debug, Clone)]
+pub struct MySqlBinlogRowChange {
+    before: Option<Vec<Option<MySqlBinlogValue>>>,
+    after: Option<Vec<Option<MySqlBinlogValue>>>,
+}
+
+impl MySqlBinlogRowChange {
+    /// Returns the row before an update or delete.
+    pub fn before(&self) -> Option<&[Option<MySqlBinlogValue>]> {
+        self.before.as_deref()
+    }
+
+    /// Returns the row after an insert or update.
+    pub fn after(&self) -> Option<&[Option<MySqlBinlogValue>]> {
+        self.after.as_deref()
+    }
+}
+
+/// A column value of a row in the binary log.
+///
+/// The binary log does not record whether integers are signed; the values of `UNSIGNED`
+/// columns must be reinterpreted, e.g. `v as u32` for an `INT UNSIGNED`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MySqlBinlogValue {
+    Null,
+
+    /// Integer, `YEAR`, `BIT`, `ENUM` (the index) and `SET` (the bits) values, and
+    /// `TIMESTAMP` values without fractional seconds of servers before MySQL 5.6.
+    Int(i64),
+
+    Float(f32),
+
+    Double(f64),
+
+    /// `DECIMAL` values as text, e.g. `-12.50`.
+    Decimal(String),
+
+    /// String, binary, `JSON` (in the binary format of the server) and spatial values, and
+    /// temporal values in the packed format of the binary log.
+    Bytes(Bytes),
+}
+
+/// Decode an event, without the OK byte which precedes it in the replication stream
+/// and without its checksum.
+pub(crate) fn decode_event(
+    mut buf: Bytes,
+    tables: &mut HashMap<u64, Arc<MySqlBinlogTable>>,
+) -> Result<MySqlBinlogEvent, Error> {
+    let mut header = take(&mut buf, HEADER_LEN)?;
+
+    let timestamp = header.get_u32_le();
+    let event_type = header.get_u8();
+    let server_id = header.get_u32_le();
+    let _event_size = header.get_u32_le();
+    let log_position = header.get_u32_le();
+    let _flags = header.get_u16_le();
+
+    let data = match event_type {
+        ROTATE_EVENT => {
+            let position = take(&mut buf, 8)?.get_u64_le();
+
+            MySqlBinlogEventData::Rotate {
+                filename: String::from_utf8_lossy(&buf).into_owned(),
+                position,
+            }
+        }
+
+        FORMAT_DESCRIPTION_EVENT => {
+            let binlog_version = take(&mut buf, 2)?.get_u16_le();
+            let server_version = take(&mut buf, 50)?;
+            let len = server_version
+                .iter()
+                .position(|&b| b == 0)
+                .unwrap_or(server_version.len());
+
+            MySqlBinlogEventData::FormatDescription {
+                binlog_version,
+                server_version: String::from_utf8_lossy(&server_version[..len]).into_owned(),
+            }
+        }
+
+        QUERY_EVENT => {
+            let mut post_header = take(&mut buf, 13)?;
+
+            let thread_id = post_header.get_u32_le();
+            let _exec_time = post_header.get_u32_le();
+            let schema_len = post_header.get_u8() as usize;
+            let _error_code = post_header.get_u16_le();
+            let status_vars_len = post_header.get_u16_le() as usize;
+
+            take(&mut buf, status_vars_len)?;
+            let schema = take(&mut buf, schema_len)?;
+            take(&mut buf, 1)?; // NUL
+
+            MySqlBinlogEventData::Query {
+                thread_id,
+                schema: String::from_utf8_lossy(&schema).into_owned(),
+                query: String::from_utf8_lossy(&buf).into_owned(),
+            }
+        }
+
+        XID_EVENT => MySqlBinlogEventData::Xid {
+            xid: take(&mut buf, 8)?.get_u64_le(),
+        },
+
+        GTID_EVENT => {
+            let _flags = take(&mut buf, 1)?;
+            let sid = take(&mut buf, 16)?;
+            let gno = take(&mut buf, 8)?.get_u64_le();
+
+            MySqlBinlogEventData::Gtid {
+                gtid: format!("{}:{}", format_uuid(&sid), gno),
+            }
+        }
+
+        TABLE_MAP_EVENT => {
+            let table = Arc::new(decode_table_map(buf)?);
+            tables.insert(table.table_id, Arc::clone(&table));
+
+            MySqlBinlogEventData::TableMap(table)
+        }
+
+        WRITE_ROWS_EVENT_V1 | UPDATE_ROWS_EVENT_V1 | DELETE_ROWS_EVENT_V1 | WRITE_ROWS_EVENT
+        | UPDATE_ROWS_EVENT | DELETE_ROWS_EVENT => {
+            MySqlBinlogEventData::Rows(decode_rows(event_type, buf, tables)?)
+        }
+
+        HEARTBEAT_EVENT => MySqlBinlogEventData::Heartbeat,
+
+        _ => MySqlBinlogEventData::Other {
+            event_type,
+            data: buf,
+        },
+    };
+
+    Ok(MySqlBinlogEvent {
+        timestamp,
+        server_id,
+        log_position,
+        data,
+    })
+}
+
+fn decode_table_map(mut buf: Bytes) -> Result<MySqlBinlogTable, Error> {
+    let table_id = take(&mut buf, 6)?.get_uint_le(6);
+    let _flags = take(&mut buf, 2)?;
+
+    let schema_len = take(&mut buf, 1)?.get_u8() as usize;
+    let schema = take(&mut buf, schema_len)?;
+    take(&mut buf, 1)?; // NUL
+
+    let name_len = take(&mut buf, 1)?.get_u8() as usize;
+    let name = take(&mut buf, name_len)?;
+    take(&mut buf, 1)?; // NUL
+
+    let column_count = buf.get_uint_lenenc()? as usize;
+    let column_types = take(&mut buf, column_count)?.to_vec();
+
+    let mut metadata = buf.get_bytes_lenenc()?;
+    let mut column_metadata = Vec::with_capacity(column_count);
+
+    for &ty in &column_types {
+        column_metadata.push(match ty {
+            // FLOAT, DOUBLE, TIMESTAMP2, DATETIME2, TIME2, JSON, the BLOBs and GEOMETRY
+            4 | 5 | 17 | 18 | 19 | 245 | 249..=252 | 255 => u16::from(take(&mut metadata, 1)?[0]),
+
+            // VARCHAR, VAR_STRING: the maximum length
+            15 | 253 => take(&mut metadata, 2)?.get_u16_le(),
+
+            // BIT, NEWDECIMAL, ENUM, SET, STRING
+            16 | 246 | 247 | 248 | 254 => take(&mut metadata, 2)?.get_u16(),
+
+            _ => 0,
+        });
+    }
+
+    Ok(MySqlBinlogTable {
+        table_id,
+        schema: String::from_utf8_lossy(&schema).into_owned(),
+        name: String::from_utf8_lossy(&name).into_owned(),
+        column_types,
+        column_metadata,
+    })
+}
+
+fn decode_rows(
+    event_type: u8,
+    mut buf: Bytes,
+    tables: &HashMap<u64, Arc<MySqlBinlogTable>>,
+) -> Result<MySqlBinlogRows, Error> {
+    let kind = match event_type {
+        WRITE_ROWS_EVENT_V1 | WRITE_ROWS_EVENT => MySqlBinlogRowsKind::Insert,
+        UPDATE_ROWS_EVENT_V1 | UPDATE_ROWS_EVENT => MySqlBinlogRowsKind::Update,
+        _ => MySqlBinlogRowsKind::Delete,
+    };
+
+    let table_id = take(&mut buf, 6)?.get_uint_le(6);
+    let _flags = take(&mut buf, 2)?;
+
+    if event_type >= WRITE_ROWS_EVENT {
+        // the length includes its own 2 bytes
+        let extra_len = take(&mut buf, 2)?.get_u16_le() as usize;
+        take(&mut buf, extra_len.saturating_sub(2))?;
+    }
+
+    let table = tables
+        .get(&table_id)
+        .cloned()
+        .ok_or_else(|| err_protocol!("rows event for unknown table id {}", table_id))?;
+
+    let column_count = buf.get_uint_lenenc()? as usize;
+
+    if column_count != table.column_types.len() {
+        return Err(err_protocol!(
+            "rows event has {} columns but table `{}`.`{}` has {}",
+            column_count,
+            table.schema,
+            table.name,
+            table.column_types.len()
+        ));
+    }
+
+    let bitmap_len = (column_count + 7) / 8;
+    let before_columns = take(&mut buf, bitmap_len)?;
+    let after_columns = if kind == MySqlBinlogRowsKind::Update {
+        take(&mut buf, bitmap_len)?
+    } else {
+        before_columns.clone()
+    };
+
+    let mut rows = Vec::new();
+
+    while buf.has_remaining() {
+        let row = match kind {
+            MySqlBinlogRowsKind::Insert => MySqlBinlogRowChange {
+                before: None,
+                after: Some(decode_image(&mut buf, &table, &after_columns)?),
+            },
+
+            MySqlBinlogRowsKind::Update => MySqlBinlogRowChange {
+                before: Some(decode_image(&mut buf, &table, &before_columns)?),
+                after: Some(decode_image(&mut buf, &table, &after_columns)?),
+            },
+
+            MySqlBinlogRowsKind::Delete => MySqlBinlogRowChange {
+                before: Some(decode_image(&mut buf, &table, &before_columns)?),
+                after: None,
+            },
+        };
+
+        rows.push(row);
+    }
+
+    Ok(MySqlBinlogRows { kind, table, rows })
+}
+
+fn is_set(bitmap: &[u8], index: usize) -> bool {
+    bitmap[index / 8] & (1 << (index % 8)) != 0
+}
+
+fn decode_image(
+    buf: &mut Bytes,
+    table: &MySqlBinlogTable,
+    columns: &[u8],
+) -> Result<Vec<Option<MySqlBinlogValue>>, Error> {
+    let column_count = table.column_types.len();
+    let present = (0..column_count).filter(|&i| is_set(columns, i)).count();
+
+    // the null bitmap only covers the columns in the image
+    let nulls = take(buf, (present + 7) / 8)?;
+
+    let mut values = Vec::with_capacity(column_count);
+    let mut index = 0;
+
+    for column in 0..column_count {
+        if !is_set(columns, column) {
+            values.push(None);
+            continue;
+        }
+
+        let value = if is_set(&nulls, index) {
+            MySqlBinlogValue::Null
+        } else {
+            decode_value(
+                buf,
+                table.column_types[column],
+                table.column_metadata[column],
+            )?
+        };
+
+        values.push(Some(value));
+        index += 1;
+    }
+
+    Ok(values)
+}
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/classbinary__log_1_1Table__map__event.html
+fn decode_value(buf: &mut Bytes, ty: u8, meta: u16) -> Result<MySqlBinlogValue, Error> {
+    Ok(match ty {
+        // TINY
+        1 => MySqlBinlogValue::Int(take(buf, 1)?.get_i8().into()),
+
+        // SHORT
+        2 => MySqlBinlogValue::Int(take(buf, 2)?.get_i16_le().into()),
+
+        // LONG
+        3 => MySqlBinlogValue::Int(take(buf, 4)?.get_i32_le().into()),
+
+        // FLOAT
+        4 => MySqlBinlogValue::Float(take(buf, 4)?.get_f32_le()),
+
+        // DOUBLE
+        5 => MySqlBinlogValue::Double(take(buf, 8)?.get_f64_le()),
+
+        // TIMESTAMP
+        7 => MySqlBinlogValue::Int(take(buf, 4)?.get_u32_le().into()),
+
+        // LONGLONG
+        8 => MySqlBinlogValue::Int(take(buf, 8)?.get_i64_le()),
+
+        // INT24
+        9 => MySqlBinlogValue::Int(take(buf, 3)?.get_int_le(3)),
+
+        // DATE, TIME
+        10 | 11 => MySqlBinlogValue::Bytes(take(buf, 3)?),
+
+        // DATETIME
+        12 => MySqlBinlogValue::Bytes(take(buf, 8)?),
+
+        // YEAR
+        13 => match take(buf, 1)?[0] {
+            0 => MySqlBinlogValue::Int(0),
+            year => MySqlBinlogValue::Int(1900 + i64::from(year)),
+        },
+
+        // VARCHAR, VAR_STRING
+        15 | 253 => MySqlBinlogValue::Bytes(take_prefixed(buf, if meta < 256 { 1 } else { 2 })?),
+
+        // BIT: the number of bits of the last byte and the number of whole bytes
+        16 => {
+            let len = usize::from(meta & 0xff) + usize::from((meta >> 8) > 0);
+            MySqlBinlogValue::Int(take(buf, len)?.get_uint(len) as i64)
+        }
+
+        // TIMESTAMP2, DATETIME2, TIME2, with up to 3 bytes of fractional seconds
+        17..=19 => {
+            let len = match ty {
+                17 => 4,
+                18 => 5,
+                _ => 3,
+            };
+
+            MySqlBinlogValue::Bytes(take(buf, len + (usize::from(meta) + 1) / 2)?)
+        }
+
+        // JSON, TINY_BLOB, MEDIUM_BLOB, LONG_BLOB, BLOB, GEOMETRY
+        245 | 249..=252 | 255 => MySqlBinlogValue::Bytes(take_prefixed(buf, usize::from(meta))?),
+
+        // NEWDECIMAL: the precision and the scale
+        246 => MySqlBinlogValue::Decimal(decode_decimal(buf, (meta >> 8) as u8, meta as u8)?),
+
+        // STRING: the real type, which is also used for ENUM and SET, and the length
+        254 | 247 | 248 => {
+            let real_type = (meta >> 8) as u8;
+
+            if real_type == 247 || real_type == 248 {
+                let len = usize::from(meta & 0xff);
+                MySqlBinlogValue::Int(take(buf, len)?.get_uint_le(len) as i64)
+            } else {
+                let max_len = (((meta >> 4) & 0x300) ^ 0x300) | (meta & 0xff);
+                MySqlBinlogValue::Bytes(take_prefixed(buf, if max_len < 256 { 1 } else { 2 })?)
+            }
+        }
+
+        _ => {
+            return Err(err_protocol!(
+                "unsupported column type {} in binlog rows event",
+                ty
+            ))
+        }
+    })
+}
+
+// the number of bytes used by the remaining digits of a group of 9
+const DIG2BYTES: [usize; 10] = [0, 1, 1, 2, 2, 3, 3, 4, 4, 4];
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/decimal_8h.html
+fn decode_decimal(buf: &mut Bytes, precision: u8, scale: u8) -> Result<String, Error> {
+    let intg = usize::from(precision.saturating_sub(scale));
+    let frac = usize::from(scale);
+
+    let len = (intg / 9) * 4 + DIG2BYTES[intg % 9] + (frac / 9) * 4 + DIG2BYTES[frac % 9];
+    let mut bytes = take(buf, len)?.to_vec();
+
+    if bytes.is_empty() {
+        return Ok("0".into());
+    }
+
+    // the sign is the inverted high bit; negative values have every bit inverted
+    let negative = bytes[0] & 0x80 == 0;
+    bytes[0] ^= 0x80;
+
+    if negative {
+        for byte in &mut bytes {
+            *byte = !*byte;
+        }
+    }
+
+    let mut bytes = &bytes[..];
+    let mut read = |len: usize| {
+        let (group, rest) = bytes.split_at(len);
+        bytes = rest;
+        group.iter().fold(0_u32, |v, &b| (v << 8) | u32::from(b))
+    };
+
+    let mut integer = String::new();
+
+    if intg % 9 > 0 {
+        integer.push_str(&read(DIG2BYTES[intg % 9]).to_string());
+    }
+
+    for _ in 0..intg / 9 {
+        integer.push_str(&format!("{:09}", read(4)));
+    }
+
+    let integer = integer.trim_start_matches('0');
+
+    let mut s = String::new();
+
+    if negative {
+        s.push('-');
+    }
+
+    s.push_str(if integer.is_empty() { "0" } else { integer });
+
+    if frac > 0 {
+        s.push('.');
+
+        for _ in 0..frac / 9 {
+            s.push_str(&format!("{:09}", read(4)));
+        }
+
+        if frac % 9 > 0 {
+            let digits = frac % 9;
+            s.push_str(&format!("{:0digits$}", read(DIG2BYTES[digits])));
+        }
+    }
+
+    Ok(s)
+}
+
+fn take(buf: &mut Bytes, len: usize) -> Result<Bytes, Error> {
+    if buf.len() < len {
+        return Err(err_protocol!(
+            "binlog event needs {} more bytes but only {} remain",
+            len,
+            buf.len()
+        ));
+    }
+
+    Ok(buf.split_to(len))
+}
+
+// a value preceded by its length, in `prefix_len` bytes
+fn take_prefixed(buf: &mut Bytes, prefix_len: usize) -> Result<Bytes, Error> {
+    if !(1..=4).contains(&prefix_len) {
+        return Err(err_protocol!(
+            "invalid length prefix of {} bytes in binlog rows event",
+            prefix_len
+        ));
+    }
+
+    let len = take(buf, prefix_len)?.get_uint_le(prefix_len) as usize;
+
+    take(buf, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event_packet(event_type: u8, body: &[u8]) -> Bytes {
+        let mut buf = Vec::new();
+        buf.extend(&1_700_000_000_u32.to_le_bytes()); // timestamp
+        buf.push(event_type);
+        buf.extend(&1_u32.to_le_bytes()); // server_id
+        buf.extend(&((HEADER_LEN + body.len()) as u32).to_le_bytes());
+        buf.extend(&1234_u32.to_le_bytes()); // log_pos
+        buf.extend(&0_u16.to_le_bytes()); // flags
+        buf.extend(body);
+
+        buf.into()
+    }
+
+    #[test]
+    fn it_decodes_query_events() {
+        let mut body = Vec::new();
+        body.extend(&7_u32.to_le_bytes()); // thread_id
+        body.extend(&0_u32.to_le_bytes()); // exec_time
+        body.push(4); // schema length
+        body.extend(&0_u16.to_le_bytes()); // error_code
+        body.extend(&2_u16.to_le_bytes()); // status_vars length
+        body.extend(b"\x00\x00test\x00CREATE TABLE t (id INT)");
+
+        let event = decode_event(event_packet(QUERY_EVENT, &body), &mut HashMap::new()).unwrap();
+
+        assert_eq!(event.timestamp(), 1_700_000_000);
+        assert_eq!(event.server_id(), 1);
+        assert_eq!(event.log_position(), 1234);
+
+        match event.data() {
+            MySqlBinlogEventData::Query {
+                thread_id,
+                schema,
+                query,
+            } => {
+                assert_eq!(*thread_id, 7);
+                assert_eq!(schema, "test");
+                assert_eq!(query, "CREATE TABLE t (id INT)");
+            }
+
+            data => panic!("unexpected event {data:?}"),
+        }
+    }
+
+    #[test]
+    fn it_decodes_rotate_and_gtid_events() {
+        let mut body = 4_u64.to_le_bytes().to_vec();
+        body.extend(b"binlog.000002");
+
+        let event = decode_event(event_packet(ROTATE_EVENT, &body), &mut HashMap::new()).unwrap();
+
+        assert!(matches!(
+            event.data(),
+            MySqlBinlogEventData::Rotate { filename, position: 4 } if filename == "binlog.000002"
+        ));
+
+        let mut body = vec![1];
+        body.extend(b"\x3e\x11\xfa\x47\x71\xca\x11\xe1\x9e\x33\xc8\x0a\xa9\x42\x95\x62");
+        body.extend(&23_u64.to_le_bytes());
+
+        let event = decode_event(event_packet(GTID_EVENT, &body), &mut HashMap::new()).unwrap();
+
+        assert!(matches!(
+            event.data(),
+            MySqlBinlogEventData::Gtid { gtid } if gtid == "3e11fa47-71ca-11e1-9e33-c80aa9429562:23"
+        ));
+    }
+
+    #[test]
+    fn it_decodes_rows_events() {
+        let mut tables = HashMap::new();
+
+        // CREATE TABLE test.t (id INT, name VARCHAR(20), price DECIMAL(5, 2))
+        let mut body = Vec::new();
+        body.extend(&[42, 0, 0, 0, 0, 0]); // table_id
+        body.extend(&1_u16.to_le_bytes()); // flags
+        body.extend(b"\x04test\x00");
+        body.extend(b"\x01t\x00");
+        body.push(3); // column count
+        body.extend(&[3, 15, 246]);
+        body.extend(&[4, 80, 0, 5, 2]); // metadata
+        body.push(0b110); // null bitmap
+
+        let event = decode_event(event_packet(TABLE_MAP_EVENT, &body), &mut tables).unwrap();
+
+        match event.data() {
+            MySqlBinlogEventData::TableMap(table) => {
+                assert_eq!(table.table_id(), 42);
+                assert_eq!(table.schema(), "test");
+                assert_eq!(table.name(), "t");
+                assert_eq!(table.column_types(), [3, 15, 246]);
+            }
+
+            data => panic!("unexpected event {data:?}"),
+        }
+
+        // UPDATE t SET name = NULL, price = -1.5 WHERE id = 1
+        let mut body = Vec::new();
+        body.extend(&[42, 0, 0, 0, 0, 0]); // table_id
+        body.extend(&1_u16.to_le_bytes()); // flags
+        body.extend(&2_u16.to_le_bytes()); // extra data length
+        body.push(3); // column count
+        body.push(0b111); // columns of the before image
+        body.push(0b111); // columns of the after image
+
+        body.push(0b000); // nulls
+        body.extend(&1_i32.to_le_bytes());
+        body.extend(b"\x03one");
+        body.extend(&[0x80, 0x01, 0x0c]); // 1.12
+
+        body.push(0b010); // nulls
+        body.extend(&1_i32.to_le_bytes());
+        body.extend(&[0x7f, 0xfe, 0xcd]); // -1.50
+
+        let event = decode_event(event_packet(UPDATE_ROWS_EVENT, &body), &mut tables).unwrap();
+
+        let rows = match event.data() {
+            MySqlBinlogEventData::Rows(rows) => rows,
+            data => panic!("unexpected event {data:?}"),
+        };
+
+        assert_eq!(rows.kind(), MySqlBinlogRowsKind::Update);
+        assert_eq!(rows.table().name(), "t");
+        assert_eq!(rows.rows().len(), 1);
+
+        let row = &rows.rows()[0];
+
+        assert_eq!(
+            row.before().unwrap(),
+            [
+                Some(MySqlBinlogValue::Int(1)),
+                Some(MySqlBinlogValue::Bytes(Bytes::from_static(b"one"))),
+                Some(MySqlBinlogValue::Decimal("1.12".into())),
+            ]
+        );
+
+        assert_eq!(
+            row.after().unwrap(),
+            [
+                Some(MySqlBinlogValue::Int(1)),
+                Some(MySqlBinlogValue::Null),
+                Some(MySqlBinlogValue::Decimal("-1.50".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_rejects_rows_of_unknown_tables() {
+        let mut body = Vec::new();
+        body.extend(&[42, 0, 0, 0, 0, 0]); // table_id
+        body.extend(&1_u16.to_le_bytes()); // flags
+        body.extend(&2_u16.to_le_bytes()); // extra data length
+        body.push(1); // column count
+        body.push(0b1);
+
+        assert!(decode_event(event_packet(WRITE_ROWS_EVENT, &body), &mut HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn it_decodes_decimals() {
+        // DECIMAL(14, 4): 1234567890.1234, a full group of 9 integer digits and 1 more
+        let mut buf = Bytes::from_static(&[0x81, 0x0d, 0xfb, 0x38, 0xd2, 0x04, 0xd2]);
+        assert_eq!(decode_decimal(&mut buf, 14, 4).unwrap(), "1234567890.1234");
+        assert!(buf.is_empty());
+
+        let mut buf = Bytes::from_static(&[0x80, 0x00]);
+        assert_eq!(decode_decimal(&mut buf, 4, 2).unwrap(), "0.00");
+    }
+}
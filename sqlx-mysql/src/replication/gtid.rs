@@ -0,0 +1,114 @@
+use crate::error::Error;
+
+/// Parse a set of GTIDs as printed by `@@global.gtid_executed`, e.g.
+/// `3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:7,4a2b...:1-3`, into the encoding of
+/// `COM_BINLOG_DUMP_GTID`.
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.26/classGtid__set.html
+pub(crate) fn encode_gtid_set(set: &str) -> Result<Vec<u8>, Error> {
+    let sids: Vec<&str> = set
+        .split(',')
+        .map(str::trim)
+        .filter(|sid| !sid.is_empty())
+        .collect();
+
+    let mut buf = Vec::new();
+    buf.extend(&(sids.len() as u64).to_le_bytes());
+
+    for sid in sids {
+        let mut parts = sid.split(':');
+
+        // `split` always yields at least one part
+        let uuid = parse_uuid(parts.next().unwrap()).ok_or_else(|| {
+            Error::Configuration(format!("invalid source id in GTID set: {sid:?}").into())
+        })?;
+
+        let intervals: Vec<&str> = parts.collect();
+
+        buf.extend(&uuid);
+        buf.extend(&(intervals.len() as u64).to_le_bytes());
+
+        for interval in intervals {
+            let (start, end) = match interval.split_once('-') {
+                Some((start, end)) => (start, end),
+                None => (interval, interval),
+            };
+
+            let (start, end) = match (start.trim().parse::<u64>(), end.trim().parse::<u64>()) {
+                (Ok(start), Ok(end)) if start > 0 && start <= end => (start, end),
+                _ => {
+                    return Err(Error::Configuration(
+                        format!("invalid interval in GTID set: {interval:?}").into(),
+                    ))
+                }
+            };
+
+            // the end of an interval is exclusive
+            buf.extend(&start.to_le_bytes());
+            buf.extend(&(end + 1).to_le_bytes());
+        }
+    }
+
+    Ok(buf)
+}
+
+fn parse_uuid(s: &str) -> Option<[u8; 16]> {
+    let hex: Vec<u8> = s.trim().bytes().filter(|&b| b != b'-').collect();
+
+    if hex.len() != 32 {
+        return None;
+    }
+
+    let mut uuid = [0; 16];
+
+    for (byte, pair) in uuid.iter_mut().zip(hex.chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+
+    Some(uuid)
+}
+
+/// Format a source id as a UUID, e.g. `3e11fa47-71ca-11e1-9e33-c80aa9429562`.
+pub(crate) fn format_uuid(uuid: &[u8]) -> String {
+    let mut s = String::with_capacity(36);
+
+    for (i, byte) in uuid.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            s.push('-');
+        }
+
+        s.push_str(&format!("{byte:02x}"));
+    }
+
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_gtid_sets() {
+        let set = encode_gtid_set("3E11FA47-71CA-11E1-9E33-C80AA9429562:1-5:7").unwrap();
+
+        let mut expected = 1_u64.to_le_bytes().to_vec();
+        expected.extend(parse_uuid("3e11fa4771ca11e19e33c80aa9429562").unwrap());
+        expected.extend(2_u64.to_le_bytes());
+        expected.extend(1_u64.to_le_bytes());
+        expected.extend(6_u64.to_le_bytes());
+        expected.extend(7_u64.to_le_bytes());
+        expected.extend(8_u64.to_le_bytes());
+
+        assert_eq!(set, expected);
+
+        assert_eq!(encode_gtid_set("").unwrap(), 0_u64.to_le_bytes());
+        assert!(encode_gtid_set("3E11FA47-71CA-11E1-9E33-C80AA9429562:5-1").is_err());
+        assert!(encode_gtid_set("not-a-uuid:1").is_err());
+    }
+
+    #[test]
+    fn it_formats_uuids() {
+        let uuid = parse_uuid("3E11FA47-71CA-11E1-9E33-C80AA9429562").unwrap();
+
+        assert_eq!(format_uuid(&uuid), "3e11fa47-71ca-11e1-9e33-c80aa9429562");
+    }
+}
@@ -1,20 +1,357 @@
 use std::sync::Arc;
+#[cfg(feature = "profile")]
+use std::time::Duration;
 
 pub(crate) use sqlx_core::row::*;
 
 use crate::column::ColumnIndex;
+use crate::decode::Decode;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::types::MySqlTime;
 use crate::HashMap;
 use crate::{protocol, MySql, MySqlColumn, MySqlValueFormat, MySqlValueRef};
 
 /// Implementation of [`Row`] for MySQL.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MySqlRow {
     pub(crate) row: protocol::Row,
     pub(crate) format: MySqlValueFormat,
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) out_params: bool,
+    #[cfg(feature = "profile")]
+    pub(crate) decode_timings: Vec<Duration>,
+}
+
+impl MySqlRow {
+    /// Returns `true` if this row carries the values of the `OUT` and `INOUT` parameters
+    /// of a stored procedure invoked with `CALL`, rather than a row of a result set
+    /// produced by the procedure body.
+    ///
+    /// The server only reports this for procedures invoked through a prepared statement
+    /// (i.e. a query with bound arguments).
+    pub fn is_out_params(&self) -> bool {
+        self.out_params
+    }
+
+    /// Returns `true` if the value of the column at `index` was skipped when decoding this row,
+    /// rather than read from the server.
+    ///
+    /// Only rows decoded by a replay limited to some columns skip values. Getting the value
+    /// of a skipped column fails with [`Error::ColumnDecode`] rather than returning `NULL`.
+    pub fn is_skipped(&self, index: usize) -> bool {
+        self.row.is_skipped(index)
+    }
+
+    /// Compute a cheap hash over the contents of this row.
+    ///
+    /// The hash covers the NULL-ness and raw bytes of every column, so two rows with
+    /// the same values in the same format hash equally. This is intended for change detection
+    /// when polling; it is stable within a given version of SQLx but is **not** guaranteed
+    /// to be stable across versions and should not be persisted.
+    pub fn content_hash(&self) -> u64 {
+        self.row.content_hash()
+    }
+
+    /// Returns the raw value of a column, with the space padding of `CHAR(n)` columns removed.
+    ///
+    /// By default, MySQL strips trailing spaces from `CHAR` values on retrieval, but with the
+    /// `PAD_CHAR_TO_FULL_LENGTH` SQL mode enabled the server returns them padded with spaces
+    /// to the full length of the column. This returns the value with trailing spaces removed
+    /// for `CHAR` columns regardless of the SQL mode. Values of any other type (including
+    /// `VARCHAR` and `BINARY(n)`) are returned unchanged.
+    ///
+    /// Returns `None` if the value is `NULL`.
+    ///
+    /// # Panics
+    /// Panics if the column does not exist; see [`Row::get`].
+    #[track_caller]
+    pub fn get_trimmed<I>(&self, index: I) -> Option<&[u8]>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = match index.index(self) {
+            Ok(index) => index,
+            Err(error) => panic!("{error}"),
+        };
+
+        let value = self.row.get(index)?;
+
+        let flags = self.columns[index].type_info.flags;
+        let is_char = self.columns[index].type_info.r#type == ColumnType::String
+            && !flags.intersects(ColumnFlags::BINARY | ColumnFlags::ENUM | ColumnFlags::SET);
+
+        if !is_char {
+            return Some(value);
+        }
+
+        let len = value
+            .iter()
+            .rposition(|b| *b != b' ')
+            .map_or(0, |pos| pos + 1);
+
+        Some(&value[..len])
+    }
+
+    /// Returns the time spent locating each value of this row while decoding it,
+    /// indexed by column.
+    ///
+    /// Only values read from the server are timed; rows decoded any other way
+    /// return an empty slice.
+    #[cfg(feature = "profile")]
+    pub fn decode_timings(&self) -> &[Duration] {
+        &self.decode_timings
+    }
+
+    /// Returns the length of the packet payload this row was decoded from.
+    ///
+    /// Unlike the sum of the lengths of the values, this includes the overhead of
+    /// the encoding: the length prefixes of each value and, in the binary format,
+    /// the leading `0x00` byte and the `NULL` bitmap. It does not include the 4-byte header
+    /// that frames the packet.
+    pub fn payload_len(&self) -> usize {
+        // the row keeps the entire payload as its storage,
+        // except for the leading 0x00 byte of a binary row
+        match self.format {
+            MySqlValueFormat::Binary => self.row.storage.len() + 1,
+            MySqlValueFormat::Text => self.row.storage.len(),
+        }
+    }
+
+    /// Returns a new row with only the columns at `indices`, in the given order.
+    ///
+    /// The new row shares the buffer of this row; the values are not copied.
+    pub fn project(&self, indices: &[usize]) -> Result<MySqlRow, Error> {
+        let mut values = Vec::with_capacity(indices.len());
+        let mut skipped = Vec::new();
+        let mut columns = Vec::with_capacity(indices.len());
+        let mut column_names = HashMap::with_capacity(indices.len());
+
+        for (ordinal, &index) in indices.iter().enumerate() {
+            let column = self
+                .columns
+                .get(index)
+                .ok_or(Error::ColumnIndexOutOfBounds {
+                    index,
+                    len: self.columns.len(),
+                })?;
+
+            values.push(self.row.values[index].clone());
+
+            if self.row.is_skipped(index) {
+                skipped.resize(indices.len(), false);
+                skipped[ordinal] = true;
+            }
+
+            column_names.insert(column.name.clone(), ordinal);
+            columns.push(MySqlColumn {
+                ordinal,
+                ..column.clone()
+            });
+        }
+
+        Ok(MySqlRow {
+            row: protocol::Row {
+                storage: self.row.storage.clone(),
+                values,
+                skipped,
+            },
+            format: self.format,
+            columns: Arc::new(columns),
+            column_names: Arc::new(column_names),
+            out_params: self.out_params,
+            #[cfg(feature = "profile")]
+            decode_timings: indices
+                .iter()
+                .filter_map(|&index| self.decode_timings.get(index).copied())
+                .collect(),
+        })
+    }
+
+    /// Compare the values of this row with those of `other` by what they represent
+    /// rather than how they are encoded.
+    ///
+    /// This allows comparing rows read with the text protocol (by a query without arguments)
+    /// with rows of the same query read with the binary protocol (by a prepared statement);
+    /// for example, the `INT` value `4` is sent as `"4"` in the former but as a 4-byte
+    /// little-endian integer in the latter. Integers, floating-point numbers and temporal
+    /// values are compared by value and any other values by their bytes.
+    ///
+    /// Returns `false` if the rows have a different number of columns or a value cannot be
+    /// read as the type of its column.
+    pub fn values_equal_logical(&self, other: &MySqlRow) -> bool {
+        if self.columns.len() != other.columns.len() {
+            return false;
+        }
+
+        (0..self.columns.len()).all(|index| {
+            let a = LogicalValue::read(self, index);
+            let b = LogicalValue::read(other, index);
+
+            matches!((a, b), (Some(a), Some(b)) if a == b)
+        })
+    }
+
+    /// Returns a cursor over the raw values of this row, in column order.
+    ///
+    /// This is intended for code that consumes every column strictly in order and wants to
+    /// avoid resolving each column index separately. The cursor does not allocate.
+    pub fn column_cursor(&self) -> MySqlColumnCursor<'_> {
+        MySqlColumnCursor {
+            row: &self.row,
+            position: 0,
+        }
+    }
+}
+
+/// A cursor over the raw values of a [`MySqlRow`], created by [`MySqlRow::column_cursor`].
+///
+/// Each step yields `Some(None)` for a `NULL` value and `Some(Some(bytes))` otherwise.
+/// The bytes are in the format of the row, see [`MySqlValueFormat`].
+#[derive(Debug, Clone)]
+pub struct MySqlColumnCursor<'r> {
+    row: &'r protocol::Row,
+    position: usize,
+}
+
+impl<'r> MySqlColumnCursor<'r> {
+    /// The index of the column that will be yielded next.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<'r> Iterator for MySqlColumnCursor<'r> {
+    type Item = Option<&'r [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.row.values.len() {
+            return None;
+        }
+
+        let value = self.row.get(self.position);
+        self.position += 1;
+
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.row.values.len() - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for MySqlColumnCursor<'_> {}
+
+/// A value decoded to a form that is the same for either format.
+#[derive(Debug, PartialEq)]
+enum LogicalValue<'r> {
+    Null,
+    Integer(i128),
+    Float(f64),
+    Time(MySqlTime),
+    // year, month, day, hour, minute, second, microsecond
+    DateTime([u32; 7]),
+    Bytes(&'r [u8]),
+}
+
+impl<'r> LogicalValue<'r> {
+    fn read(row: &'r MySqlRow, index: usize) -> Option<Self> {
+        let value = row.try_get_raw(index).ok()?;
+
+        let bytes = match value.value {
+            Some(bytes) => bytes,
+            None => return Some(Self::Null),
+        };
+
+        let ty = &row.columns[index].type_info;
+
+        Some(match ty.r#type {
+            ColumnType::Tiny
+            | ColumnType::Short
+            | ColumnType::Int24
+            | ColumnType::Long
+            | ColumnType::LongLong
+            | ColumnType::Year => {
+                if ty.flags.contains(ColumnFlags::UNSIGNED) {
+                    Self::Integer(<u64 as Decode<MySql>>::decode(value).ok()?.into())
+                } else {
+                    Self::Integer(<i64 as Decode<MySql>>::decode(value).ok()?.into())
+                }
+            }
+
+            // a FLOAT printed as text only has the precision of an `f32`
+            ColumnType::Float => Self::Float(<f32 as Decode<MySql>>::decode(value).ok()?.into()),
+            ColumnType::Double => Self::Float(<f64 as Decode<MySql>>::decode(value).ok()?),
+
+            ColumnType::Time => Self::Time(<MySqlTime as Decode<MySql>>::decode(value).ok()?),
+
+            ColumnType::Date | ColumnType::Datetime | ColumnType::Timestamp => {
+                Self::DateTime(match row.format {
+                    MySqlValueFormat::Binary => binary_date_time(bytes)?,
+                    MySqlValueFormat::Text => text_date_time(std::str::from_utf8(bytes).ok()?)?,
+                })
+            }
+
+            _ => Self::Bytes(bytes),
+        })
+    }
+}
+
+fn binary_date_time(buf: &[u8]) -> Option<[u32; 7]> {
+    // the length byte is left on the front of the value;
+    // trailing fields that are zero are omitted
+    let (&len, buf) = buf.split_first()?;
+    let buf = buf.get(..len as usize)?;
+
+    let mut fields = [0; 7];
+
+    if buf.len() >= 4 {
+        fields[0] = u16::from_le_bytes([buf[0], buf[1]]).into();
+        fields[1] = buf[2].into();
+        fields[2] = buf[3].into();
+    }
+
+    if buf.len() >= 7 {
+        fields[3] = buf[4].into();
+        fields[4] = buf[5].into();
+        fields[5] = buf[6].into();
+    }
+
+    if buf.len() >= 11 {
+        fields[6] = u32::from_le_bytes([buf[7], buf[8], buf[9], buf[10]]);
+    }
+
+    Some(fields)
+}
+
+fn text_date_time(text: &str) -> Option<[u32; 7]> {
+    // `YYYY-MM-DD`, followed by ` hh:mm:ss` and then `.ffffff` (with 1 to 6 digits)
+    // for DATETIME and TIMESTAMP
+    let (date, time) = text.split_once(' ').unwrap_or((text, "00:00:00"));
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+
+    let mut fields = [0; 7];
+
+    let parts = date.split('-').chain(time.split(':'));
+    let mut count = 0;
+
+    for (field, part) in fields.iter_mut().zip(parts) {
+        *field = part.parse().ok()?;
+        count += 1;
+    }
+
+    if count != 6 || fraction.len() > 6 {
+        return None;
+    }
+
+    if !fraction.is_empty() {
+        fields[6] = fraction.parse::<u32>().ok()? * 10_u32.pow(6 - fraction.len() as u32);
+    }
+
+    Some(fields)
 }
 
 impl Row for MySqlRow {
@@ -29,6 +366,14 @@ impl Row for MySqlRow {
         I: ColumnIndex<Self>,
     {
         let index = index.index(self)?;
+
+        if self.row.is_skipped(index) {
+            return Err(Error::ColumnDecode {
+                index: format!("{index:?}"),
+                source: "the value was skipped when decoding the row".into(),
+            });
+        }
+
         let column = &self.columns[index];
         let value = self.row.get(index);
 
@@ -49,3 +394,224 @@ impl ColumnIndex<MySqlRow> for &'_ str {
             .copied()
     }
 }
+
+#[test]
+fn test_column_cursor() {
+    use bytes::Bytes;
+
+    let row = MySqlRow {
+        row: protocol::Row {
+            storage: Bytes::from_static(b"\x011\xfb\x03foo"),
+            values: vec![Some(1..2), None, Some(4..7)],
+            skipped: Vec::new(),
+        },
+        format: MySqlValueFormat::Text,
+        columns: Arc::default(),
+        column_names: Arc::default(),
+        out_params: false,
+        #[cfg(feature = "profile")]
+        decode_timings: Vec::new(),
+    };
+
+    let mut cursor = row.column_cursor();
+
+    assert_eq!(cursor.len(), 3);
+
+    for index in 0..3 {
+        assert_eq!(cursor.position(), index);
+        assert_eq!(cursor.next(), Some(row.row.get(index)));
+    }
+
+    assert_eq!(cursor.next(), None);
+    assert_eq!(cursor.position(), 3);
+}
+
+#[test]
+fn test_get_trimmed() {
+    use crate::MySqlTypeInfo;
+    use bytes::Bytes;
+
+    let column = |ordinal, name, r#type| MySqlColumn {
+        ordinal,
+        name: UStr::Static(name),
+        type_info: MySqlTypeInfo {
+            r#type,
+            flags: ColumnFlags::empty(),
+            max_size: None,
+        },
+        flags: None,
+    };
+
+    let row = MySqlRow {
+        row: protocol::Row {
+            storage: Bytes::from_static(b"\x05ab   \x05cd   "),
+            values: vec![Some(1..6), Some(7..12)],
+            skipped: Vec::new(),
+        },
+        format: MySqlValueFormat::Text,
+        columns: Arc::new(vec![
+            column(0, "fixed", ColumnType::String),
+            column(1, "variable", ColumnType::VarString),
+        ]),
+        column_names: Arc::default(),
+        out_params: false,
+        #[cfg(feature = "profile")]
+        decode_timings: Vec::new(),
+    };
+
+    assert_eq!(row.get_trimmed(0), Some(&b"ab"[..]));
+    assert_eq!(row.get_trimmed(1), Some(&b"cd   "[..]));
+}
+
+#[test]
+fn test_payload_len() {
+    use crate::io::Decode;
+    use crate::protocol::statement::BinaryRow;
+    use crate::protocol::text::TextRow;
+    use crate::MySqlTypeInfo;
+
+    let columns = Arc::new(vec![
+        MySqlColumn {
+            ordinal: 0,
+            name: UStr::Static("id"),
+            type_info: MySqlTypeInfo::binary(ColumnType::Long),
+            flags: None,
+        },
+        MySqlColumn {
+            ordinal: 1,
+            name: UStr::Static("name"),
+            type_info: MySqlTypeInfo::binary(ColumnType::VarString),
+            flags: None,
+        },
+    ]);
+
+    let row = |row, format| MySqlRow {
+        row,
+        format,
+        columns: Arc::clone(&columns),
+        column_names: Arc::default(),
+        out_params: false,
+        #[cfg(feature = "profile")]
+        decode_timings: Vec::new(),
+    };
+
+    const BINARY: &[u8] = b"\x00\x00\x2a\x00\x00\x00\x03foo";
+    let binary = BinaryRow::decode_with(BINARY.into(), &columns[..])
+        .unwrap()
+        .0;
+    assert_eq!(
+        row(binary, MySqlValueFormat::Binary).payload_len(),
+        BINARY.len()
+    );
+
+    const TEXT: &[u8] = b"\x0242\x03foo";
+    let text = TextRow::decode_with(TEXT.into(), &columns[..]).unwrap().0;
+    assert_eq!(row(text, MySqlValueFormat::Text).payload_len(), TEXT.len());
+}
+
+#[test]
+fn test_values_equal_logical() {
+    use crate::column::test_columns;
+    use crate::io::Decode;
+    use crate::protocol::statement::BinaryRow;
+    use crate::protocol::text::TextRow;
+
+    let columns = Arc::new(test_columns([
+        ColumnType::Long,
+        ColumnType::Double,
+        ColumnType::Datetime,
+        ColumnType::Time,
+        ColumnType::VarString,
+        ColumnType::Long,
+    ]));
+
+    let row = |row, format| MySqlRow {
+        row,
+        format,
+        columns: Arc::clone(&columns),
+        column_names: Arc::default(),
+        out_params: false,
+        #[cfg(feature = "profile")]
+        decode_timings: Vec::new(),
+    };
+
+    // (4, 1.5, '2010-10-17 19:27:30.5', '-12:00:01', 'foo', NULL)
+    const TEXT: &[u8] = b"\x014\x031.5\x152010-10-17 19:27:30.5\x09-12:00:01\x03foo\xfb";
+    let text = TextRow::decode_with(TEXT.into(), &columns[..]).unwrap().0;
+    let text = row(text, MySqlValueFormat::Text);
+
+    const BINARY: &[u8] = b"\x00\x80\
+        \x04\x00\x00\x00\
+        \x00\x00\x00\x00\x00\x00\xf8\x3f\
+        \x0b\xda\x07\x0a\x11\x13\x1b\x1e\x20\xa1\x07\x00\
+        \x08\x01\x00\x00\x00\x00\x0c\x00\x01\
+        \x03foo";
+    let binary = BinaryRow::decode_with(BINARY.into(), &columns[..])
+        .unwrap()
+        .0;
+    let binary = row(binary, MySqlValueFormat::Binary);
+
+    assert!(text.values_equal_logical(&binary));
+    assert!(binary.values_equal_logical(&text));
+
+    // (5, ...)
+    const OTHER: &[u8] = b"\x015\x031.5\x152010-10-17 19:27:30.5\x09-12:00:01\x03foo\xfb";
+    let other = TextRow::decode_with(OTHER.into(), &columns[..]).unwrap().0;
+    let other = row(other, MySqlValueFormat::Text);
+
+    assert!(!other.values_equal_logical(&binary));
+}
+
+#[test]
+fn test_project() {
+    use crate::column::test_columns;
+    use crate::column::Column;
+    use crate::io::Decode;
+    use crate::protocol::text::TextRow;
+
+    let columns = test_columns([ColumnType::Long; 30]);
+
+    let column_names = columns
+        .iter()
+        .map(|column| (column.name.clone(), column.ordinal))
+        .collect();
+
+    // each column holds its own index, except column 13 which is NULL
+    let mut buf = Vec::new();
+
+    for index in 0..30 {
+        if index == 13 {
+            buf.push(0xfb);
+        } else {
+            let value = index.to_string();
+            buf.push(value.len() as u8);
+            buf.extend_from_slice(value.as_bytes());
+        }
+    }
+
+    let row = MySqlRow {
+        row: TextRow::decode_with(buf.into(), &columns[..]).unwrap().0,
+        format: MySqlValueFormat::Text,
+        columns: Arc::new(columns),
+        column_names: Arc::new(column_names),
+        out_params: false,
+        #[cfg(feature = "profile")]
+        decode_timings: Vec::new(),
+    };
+
+    let projected = row.project(&[25, 0, 13]).unwrap();
+
+    assert_eq!(projected.len(), 3);
+    assert_eq!(projected.column(0).name(), "c25");
+    assert_eq!(projected.column(1).name(), "c0");
+    assert_eq!(projected.column(2).ordinal(), 2);
+
+    assert_eq!(projected.get::<i32, _>(0), 25);
+    assert_eq!(projected.get::<i32, _>("c0"), 0);
+    assert_eq!(projected.get::<Option<i32>, _>("c13"), None);
+
+    assert!(matches!(
+        row.project(&[0, 30]),
+        Err(Error::ColumnIndexOutOfBounds { index: 30, len: 30 })
+    ));
+}
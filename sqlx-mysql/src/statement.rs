@@ -21,6 +21,18 @@ pub(crate) struct MySqlStatementMetadata {
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
     pub(crate) parameters: usize,
+    pub(crate) parameter_types: Arc<Vec<MySqlTypeInfo>>,
+}
+
+impl MySqlStatement<'_> {
+    /// The types of the parameters as reported by the server when the statement was prepared.
+    ///
+    /// These are generally not reliable: most servers report the same type for
+    /// every parameter regardless of how it is used in the statement. They are exposed for
+    /// inspection only and have no effect on how arguments are bound.
+    pub fn parameter_types(&self) -> &[MySqlTypeInfo] {
+        &self.metadata.parameter_types
+    }
 }
 
 impl<'q> Statement<'q> for MySqlStatement<'q> {
@@ -25,6 +25,46 @@ impl TransactionManager for MySqlTransactionManager {
         })
     }
 
+    fn begin_with(
+        conn: &mut MySqlConnection,
+        options: TxOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        if options == TxOptions::default() {
+            return Self::begin(conn);
+        }
+
+        Box::pin(async move {
+            if conn.inner.transaction_depth > 0 {
+                return Err(Error::Configuration(
+                    "transaction options cannot be set on a savepoint".into(),
+                ));
+            }
+
+            if options.is_deferrable() {
+                return Err(Error::Configuration(
+                    "MySQL does not support deferrable transactions".into(),
+                ));
+            }
+
+            // the isolation level cannot be given to `START TRANSACTION`, but applies to the
+            // next transaction only when set without `SESSION` or `GLOBAL`
+            if let Some(level) = options.isolation_level() {
+                conn.execute(&*format!("SET TRANSACTION ISOLATION LEVEL {level}"))
+                    .await?;
+            }
+
+            if options.is_read_only() {
+                conn.execute("START TRANSACTION READ ONLY").await?;
+            } else {
+                conn.execute("BEGIN").await?;
+            }
+
+            conn.inner.transaction_depth = 1;
+
+            Ok(())
+        })
+    }
+
     fn commit(conn: &mut MySqlConnection) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.inner.transaction_depth;
@@ -59,7 +99,7 @@ impl TransactionManager for MySqlTransactionManager {
             conn.inner.stream.sequence_id = 0;
             conn.inner
                 .stream
-                .write_packet(Query(&rollback_ansi_transaction_sql(depth)));
+                .write_packet(Query::new(&rollback_ansi_transaction_sql(depth)));
 
             conn.inner.transaction_depth = depth - 1;
         }
@@ -56,6 +56,22 @@ impl MySqlTypeInfo {
         }
     }
 
+    /// The type of the values of this column, taking the column flags into account.
+    ///
+    /// The server reports `ENUM` and `SET` columns with the type of their underlying
+    /// representation (typically `STRING`, but an integer type may be seen as well) and
+    /// only marks the real type with a flag. Decoding should consult this instead of the
+    /// reported type where the distinction matters.
+    pub(crate) fn real_type(&self) -> ColumnType {
+        if self.flags.contains(ColumnFlags::ENUM) {
+            ColumnType::Enum
+        } else if self.flags.contains(ColumnFlags::SET) {
+            ColumnType::Set
+        } else {
+            self.r#type
+        }
+    }
+
     pub(crate) fn from_column(column: &ColumnDefinition) -> Self {
         Self {
             r#type: column.r#type,
@@ -116,3 +132,28 @@ impl PartialEq<MySqlTypeInfo> for MySqlTypeInfo {
 }
 
 impl Eq for MySqlTypeInfo {}
+
+#[test]
+fn test_real_type_integer_backed_enum() {
+    let ty = MySqlTypeInfo {
+        r#type: ColumnType::Tiny,
+        flags: ColumnFlags::ENUM,
+        max_size: None,
+    };
+
+    assert_eq!(ty.r#type, ColumnType::Tiny);
+    assert_eq!(ty.real_type(), ColumnType::Enum);
+
+    let ty = MySqlTypeInfo {
+        r#type: ColumnType::String,
+        flags: ColumnFlags::SET,
+        max_size: None,
+    };
+
+    assert_eq!(ty.real_type(), ColumnType::Set);
+
+    assert_eq!(
+        MySqlTypeInfo::binary(ColumnType::Long).real_type(),
+        ColumnType::Long
+    );
+}
@@ -4,9 +4,9 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::io::MySqlBufMutExt;
-use crate::protocol::text::ColumnType;
+use crate::protocol::text::{ColumnFlags, ColumnType};
 use crate::types::Type;
-use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
 
 impl Type<MySql> for BigDecimal {
     fn type_info() -> MySqlTypeInfo {
@@ -14,7 +14,16 @@ impl Type<MySql> for BigDecimal {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        matches!(ty.r#type, ColumnType::Decimal | ColumnType::NewDecimal)
+        matches!(
+            ty.r#type,
+            ColumnType::Decimal
+                | ColumnType::NewDecimal
+                | ColumnType::Tiny
+                | ColumnType::Short
+                | ColumnType::Long
+                | ColumnType::Int24
+                | ColumnType::LongLong
+        )
     }
 }
 
@@ -28,6 +37,83 @@ impl Encode<'_, MySql> for BigDecimal {
 
 impl Decode<'_, MySql> for BigDecimal {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let ty = &value.type_info;
+
+        // integers are only sent as text in the text protocol
+        if matches!(value.format(), MySqlValueFormat::Binary)
+            && !matches!(ty.r#type, ColumnType::Decimal | ColumnType::NewDecimal)
+        {
+            return Ok(if ty.flags.contains(ColumnFlags::UNSIGNED) {
+                <u64 as Decode<MySql>>::decode(value)?.into()
+            } else {
+                <i64 as Decode<MySql>>::decode(value)?.into()
+            });
+        }
+
         Ok(value.as_str()?.parse()?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bigdecimal::BigDecimal;
+
+    use crate::decode::Decode;
+    use crate::encode::Encode;
+    use crate::protocol::text::{ColumnFlags, ColumnType};
+    use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    fn decode(ty: MySqlTypeInfo, format: MySqlValueFormat, value: &[u8]) -> BigDecimal {
+        <BigDecimal as Decode<MySql>>::decode(MySqlValueRef::test_value(value, ty, format)).unwrap()
+    }
+
+    #[test]
+    fn test_decode_decimal() {
+        let ty = MySqlTypeInfo::binary(ColumnType::NewDecimal);
+
+        // DECIMAL values are sent as strings in both protocols
+        for format in [MySqlValueFormat::Text, MySqlValueFormat::Binary] {
+            assert_eq!(
+                decode(ty.clone(), format, b"-12345678901234567890.0123456789"),
+                BigDecimal::from_str("-12345678901234567890.0123456789").unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_decimal_from_integer() {
+        let ty = MySqlTypeInfo::binary(ColumnType::LongLong);
+        assert_eq!(
+            decode(ty.clone(), MySqlValueFormat::Text, b"-42"),
+            BigDecimal::from(-42)
+        );
+
+        assert_eq!(
+            decode(ty, MySqlValueFormat::Binary, &(-42_i64).to_le_bytes()),
+            BigDecimal::from(-42)
+        );
+
+        let ty = MySqlTypeInfo {
+            flags: ColumnFlags::BINARY | ColumnFlags::UNSIGNED,
+            ..MySqlTypeInfo::binary(ColumnType::LongLong)
+        };
+        assert_eq!(
+            decode(ty, MySqlValueFormat::Binary, &u64::MAX.to_le_bytes()),
+            BigDecimal::from(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_encode_decimal() {
+        let mut buf = Vec::new();
+        let _ = <BigDecimal as Encode<MySql>>::encode_by_ref(
+            &BigDecimal::from_str("-1.50").unwrap(),
+            &mut buf,
+        )
+        .unwrap();
+
+        assert_eq!(buf, b"\x05-1.50");
+    }
+}
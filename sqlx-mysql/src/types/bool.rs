@@ -38,6 +38,59 @@ impl Encode<'_, MySql> for bool {
 
 impl Decode<'_, MySql> for bool {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        Ok(<i8 as Decode<MySql>>::decode(value)? != 0)
+        // any non-zero value is true, whatever the width of the column;
+        // `BIT` values are raw bytes in both protocols
+        if value.type_info.r#type == ColumnType::Bit
+            || value.type_info.flags.contains(ColumnFlags::UNSIGNED)
+        {
+            Ok(<u64 as Decode<MySql>>::decode(value)? != 0)
+        } else {
+            Ok(<i64 as Decode<MySql>>::decode(value)? != 0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decode::Decode;
+    use crate::protocol::text::{ColumnFlags, ColumnType};
+    use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    fn decode(ty: ColumnType, flags: ColumnFlags, format: MySqlValueFormat, value: &[u8]) -> bool {
+        <bool as Decode<MySql>>::decode(MySqlValueRef::test_value(
+            value,
+            MySqlTypeInfo {
+                r#type: ty,
+                flags,
+                max_size: Some(1),
+            },
+            format,
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_bool() {
+        use MySqlValueFormat::{Binary, Text};
+
+        let signed = ColumnFlags::empty();
+        let unsigned = ColumnFlags::UNSIGNED;
+
+        // TINYINT(1)
+        assert!(!decode(ColumnType::Tiny, signed, Text, b"0"));
+        assert!(decode(ColumnType::Tiny, signed, Text, b"1"));
+        assert!(!decode(ColumnType::Tiny, signed, Binary, &[0]));
+        assert!(decode(ColumnType::Tiny, signed, Binary, &[1]));
+        assert!(decode(ColumnType::Tiny, signed, Binary, &[0xff]));
+
+        // any non-zero value is true
+        assert!(decode(ColumnType::Tiny, unsigned, Binary, &[0xff]));
+        assert!(decode(ColumnType::Long, signed, Text, b"256"));
+        assert!(decode(ColumnType::Long, signed, Binary, &[0, 1, 0, 0]));
+
+        // BIT(1)
+        assert!(!decode(ColumnType::Bit, unsigned, Text, &[0]));
+        assert!(decode(ColumnType::Bit, unsigned, Text, &[1]));
+        assert!(decode(ColumnType::Bit, unsigned, Binary, &[1]));
     }
 }
@@ -1,16 +1,19 @@
 use bytes::Buf;
 use chrono::{
-    DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+    DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Timelike, Utc,
 };
 use sqlx_core::database::Database;
 
+use crate::column::ColumnIndex;
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
-use crate::error::{BoxDynError, UnexpectedNullError};
+use crate::error::{BoxDynError, Error, UnexpectedNullError};
 use crate::protocol::text::ColumnType;
+use crate::row::Row;
 use crate::type_info::MySqlTypeInfo;
 use crate::types::{MySqlTime, MySqlTimeSign, Type};
-use crate::{MySql, MySqlValueFormat, MySqlValueRef};
+use crate::{MySql, MySqlRow, MySqlValueFormat, MySqlValueRef};
 
 impl Type<MySql> for DateTime<Utc> {
     fn type_info() -> MySqlTypeInfo {
@@ -62,6 +65,48 @@ impl<'r> Decode<'r, MySql> for DateTime<Local> {
     }
 }
 
+impl MySqlRow {
+    /// Index into this row and decode a `TIMESTAMP` or `DATETIME` value as a UTC instant,
+    /// given the `time_zone` of the session the row was read from.
+    ///
+    /// MySQL stores `TIMESTAMP` values in UTC but sends them converted to the session time zone,
+    /// so the offset is required to recover the instant. `DATETIME` values on the other hand
+    /// are wall-clock times without any time zone; they are returned as-is, as if they were UTC,
+    /// which is the same behavior as decoding them as [`DateTime<Utc>`].
+    ///
+    /// The [`Decode`] implementation for [`DateTime<Utc>`] is equivalent to calling this
+    /// with an offset of zero.
+    ///
+    /// # Errors
+    /// See [`Row::try_get`].
+    pub fn try_get_utc<I>(
+        &self,
+        index: I,
+        session_time_zone: FixedOffset,
+    ) -> Result<DateTime<Utc>, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let is_timestamp = self.try_get_raw(&index)?.type_info.r#type == ColumnType::Timestamp;
+        let naive = self.try_get::<DateTime<Utc>, _>(&index)?.naive_utc();
+
+        if !is_timestamp {
+            return Ok(Utc.from_utc_datetime(&naive));
+        }
+
+        // a fixed offset always maps a local time to exactly one instant
+        let local = session_time_zone
+            .from_local_datetime(&naive)
+            .single()
+            .ok_or_else(|| Error::ColumnDecode {
+                index: format!("{index:?}"),
+                source: format!("{naive} does not exist in {session_time_zone}").into(),
+            })?;
+
+        Ok(local.with_timezone(&Utc))
+    }
+}
+
 impl Type<MySql> for NaiveTime {
     fn type_info() -> MySqlTypeInfo {
         MySqlTime::type_info()
@@ -263,6 +308,15 @@ impl<'r> Decode<'r, MySql> for NaiveDateTime {
                 }
 
                 let len = buf[0];
+
+                if buf.len() - 1 < len as usize {
+                    return Err(format!(
+                        "expected {len} bytes for DATETIME value, got {}",
+                        buf.len() - 1
+                    )
+                    .into());
+                }
+
                 let date = decode_date(&buf[1..])?.ok_or(UnexpectedNullError)?;
 
                 let dt = if len > 4 {
@@ -324,6 +378,10 @@ fn encode_time(time: &NaiveTime, include_micros: bool, buf: &mut Vec<u8>) {
 }
 
 fn decode_time(len: u8, mut buf: &[u8]) -> Result<NaiveTime, BoxDynError> {
+    if buf.len() < 3 {
+        return Err(format!("expected at least 3 bytes for time, got {}", buf.len()).into());
+    }
+
     let hour = buf.get_u8();
     let minute = buf.get_u8();
     let seconds = buf.get_u8();
@@ -338,3 +396,120 @@ fn decode_time(len: u8, mut buf: &[u8]) -> Result<NaiveTime, BoxDynError> {
     NaiveTime::from_hms_micro_opt(hour as u32, minute as u32, seconds as u32, micros as u32)
         .ok_or_else(|| format!("server returned invalid time: {hour:02}:{minute:02}:{seconds:02}; micros: {micros}").into())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use chrono::{FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+    use crate::decode::Decode;
+    use crate::encode::Encode;
+    use crate::ext::ustr::UStr;
+    use crate::protocol::text::ColumnType;
+    use crate::{
+        protocol, MySql, MySqlColumn, MySqlRow, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef,
+    };
+
+    fn row(ty: ColumnType) -> MySqlRow {
+        MySqlRow {
+            // 2024-01-01 12:00:00
+            row: protocol::Row {
+                storage: Bytes::from_static(b"\x07\xe8\x07\x01\x01\x0c\x00\x00"),
+                values: vec![Some(0..8)],
+                skipped: Vec::new(),
+            },
+            format: MySqlValueFormat::Binary,
+            columns: Arc::new(vec![MySqlColumn {
+                ordinal: 0,
+                name: UStr::Static("at"),
+                type_info: MySqlTypeInfo::binary(ty),
+                flags: None,
+            }]),
+            column_names: Arc::default(),
+            out_params: false,
+            #[cfg(feature = "profile")]
+            decode_timings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_try_get_utc_timestamp() {
+        let row = row(ColumnType::Timestamp);
+
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+
+        assert_eq!(
+            row.try_get_utc(0, utc).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()
+        );
+
+        assert_eq!(
+            row.try_get_utc(0, cest).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_get_utc_datetime() {
+        let row = row(ColumnType::Datetime);
+
+        let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+
+        assert_eq!(
+            row.try_get_utc(0, cest).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()
+        );
+    }
+
+    fn decode_datetime(buf: &[u8]) -> Result<NaiveDateTime, crate::error::BoxDynError> {
+        <NaiveDateTime as Decode<MySql>>::decode(MySqlValueRef::test_value(
+            buf,
+            MySqlTypeInfo::binary(ColumnType::Datetime),
+            MySqlValueFormat::Binary,
+        ))
+    }
+
+    #[test]
+    fn test_datetime_binary_forms() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+
+        for (datetime, encoded) in [
+            (
+                date.and_hms_opt(0, 0, 0).unwrap(),
+                &b"\x04\xe8\x07\x01\x02"[..],
+            ),
+            (
+                date.and_hms_opt(3, 4, 5).unwrap(),
+                b"\x07\xe8\x07\x01\x02\x03\x04\x05",
+            ),
+            (
+                date.and_hms_micro_opt(3, 4, 5, 6).unwrap(),
+                b"\x0b\xe8\x07\x01\x02\x03\x04\x05\x06\x00\x00\x00",
+            ),
+        ] {
+            let mut buf = Vec::new();
+            let _ = <NaiveDateTime as Encode<MySql>>::encode_by_ref(&datetime, &mut buf).unwrap();
+
+            assert_eq!(buf, encoded);
+            assert_eq!(decode_datetime(encoded).unwrap(), datetime);
+        }
+
+        // the zero date
+        assert!(decode_datetime(b"\x00").is_err());
+    }
+
+    #[test]
+    fn test_datetime_binary_truncated() {
+        let encoded = b"\x0b\xe8\x07\x01\x02\x03\x04\x05\x06\x00\x00\x00";
+
+        for len in 1..encoded.len() {
+            assert!(decode_datetime(&encoded[..len]).is_err());
+        }
+
+        // a time shorter than its length prefix claims
+        assert!(decode_datetime(b"\x06\xe8\x07\x01\x02\x03\x04").is_err());
+    }
+}
@@ -0,0 +1,409 @@
+use geo_types::{
+    Coord, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+    Point, Polygon,
+};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::io::MySqlBufMutExt;
+use crate::protocol::text::ColumnType;
+use crate::types::Type;
+use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+// MySQL stores a geometry as a 4-byte little-endian SRID followed by its WKB representation
+// https://dev.mysql.com/doc/refman/8.0/en/gis-data-formats.html#gis-internal-format
+
+const WKB_POINT: u32 = 1;
+const WKB_LINE_STRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTI_POINT: u32 = 4;
+const WKB_MULTI_LINE_STRING: u32 = 5;
+const WKB_MULTI_POLYGON: u32 = 6;
+const WKB_GEOMETRY_COLLECTION: u32 = 7;
+
+impl Type<MySql> for Geometry<f64> {
+    fn type_info() -> MySqlTypeInfo {
+        // geometries are sent in the internal format of MySQL, as a blob
+        MySqlTypeInfo::binary(ColumnType::Blob)
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        ty.r#type == ColumnType::Geometry || <&[u8] as Type<MySql>>::compatible(ty)
+    }
+}
+
+/// Encodes the geometry with an SRID of 0.
+impl Encode<'_, MySql> for Geometry<f64> {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        let mut value = 0_u32.to_le_bytes().to_vec();
+        write_geometry(self, &mut value)?;
+
+        buf.put_bytes_lenenc(&value);
+
+        Ok(IsNull::No)
+    }
+}
+
+/// Decodes the geometry, ignoring its SRID.
+impl Decode<'_, MySql> for Geometry<f64> {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let buf = <&[u8] as Decode<MySql>>::decode(value)?;
+
+        let mut wkb = buf
+            .get(4..)
+            .ok_or("expected at least 4 bytes for the SRID of a geometry")?;
+
+        let geometry = read_geometry(&mut wkb)?;
+
+        if !wkb.is_empty() {
+            return Err(format!("{} unexpected bytes after geometry", wkb.len()).into());
+        }
+
+        Ok(geometry)
+    }
+}
+
+fn write_header(ty: u32, buf: &mut Vec<u8>) {
+    // little-endian
+    buf.push(1);
+    buf.extend(&ty.to_le_bytes());
+}
+
+fn write_len(len: usize, buf: &mut Vec<u8>) -> Result<(), BoxDynError> {
+    buf.extend(&u32::try_from(len)?.to_le_bytes());
+
+    Ok(())
+}
+
+fn write_coords(line: &LineString<f64>, buf: &mut Vec<u8>) -> Result<(), BoxDynError> {
+    write_len(line.0.len(), buf)?;
+
+    for coord in &line.0 {
+        buf.extend(&coord.x.to_le_bytes());
+        buf.extend(&coord.y.to_le_bytes());
+    }
+
+    Ok(())
+}
+
+fn write_polygon(polygon: &Polygon<f64>, buf: &mut Vec<u8>) -> Result<(), BoxDynError> {
+    write_header(WKB_POLYGON, buf);
+
+    // an empty polygon has no rings at all
+    if polygon.exterior().0.is_empty() {
+        return write_len(0, buf);
+    }
+
+    write_len(polygon.interiors().len() + 1, buf)?;
+    write_coords(polygon.exterior(), buf)?;
+
+    for ring in polygon.interiors() {
+        write_coords(ring, buf)?;
+    }
+
+    Ok(())
+}
+
+fn write_geometry(geometry: &Geometry<f64>, buf: &mut Vec<u8>) -> Result<(), BoxDynError> {
+    match geometry {
+        Geometry::Point(point) => {
+            write_header(WKB_POINT, buf);
+            buf.extend(&point.x().to_le_bytes());
+            buf.extend(&point.y().to_le_bytes());
+        }
+
+        Geometry::Line(line) => {
+            write_header(WKB_LINE_STRING, buf);
+            write_coords(&LineString::from(*line), buf)?;
+        }
+
+        Geometry::LineString(line) => {
+            write_header(WKB_LINE_STRING, buf);
+            write_coords(line, buf)?;
+        }
+
+        Geometry::Polygon(polygon) => write_polygon(polygon, buf)?,
+
+        Geometry::Rect(rect) => write_polygon(&rect.to_polygon(), buf)?,
+
+        Geometry::Triangle(triangle) => write_polygon(&triangle.to_polygon(), buf)?,
+
+        Geometry::MultiPoint(points) => {
+            write_header(WKB_MULTI_POINT, buf);
+            write_len(points.0.len(), buf)?;
+
+            for point in &points.0 {
+                write_geometry(&Geometry::Point(*point), buf)?;
+            }
+        }
+
+        Geometry::MultiLineString(lines) => {
+            write_header(WKB_MULTI_LINE_STRING, buf);
+            write_len(lines.0.len(), buf)?;
+
+            for line in &lines.0 {
+                write_header(WKB_LINE_STRING, buf);
+                write_coords(line, buf)?;
+            }
+        }
+
+        Geometry::MultiPolygon(polygons) => {
+            write_header(WKB_MULTI_POLYGON, buf);
+            write_len(polygons.0.len(), buf)?;
+
+            for polygon in &polygons.0 {
+                write_polygon(polygon, buf)?;
+            }
+        }
+
+        Geometry::GeometryCollection(geometries) => {
+            write_header(WKB_GEOMETRY_COLLECTION, buf);
+            write_len(geometries.0.len(), buf)?;
+
+            for geometry in &geometries.0 {
+                write_geometry(geometry, buf)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn read_bytes<const N: usize>(buf: &mut &[u8]) -> Result<[u8; N], BoxDynError> {
+    if buf.len() < N {
+        return Err("unexpected end of geometry".into());
+    }
+
+    let mut bytes = [0; N];
+    bytes.copy_from_slice(&buf[..N]);
+    *buf = &buf[N..];
+
+    Ok(bytes)
+}
+
+fn read_u32(buf: &mut &[u8], little_endian: bool) -> Result<u32, BoxDynError> {
+    let bytes = read_bytes(buf)?;
+
+    Ok(if little_endian {
+        u32::from_le_bytes(bytes)
+    } else {
+        u32::from_be_bytes(bytes)
+    })
+}
+
+fn read_coord(buf: &mut &[u8], little_endian: bool) -> Result<Coord<f64>, BoxDynError> {
+    let mut read_f64 = || -> Result<f64, BoxDynError> {
+        let bytes = read_bytes(buf)?;
+
+        Ok(if little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    };
+
+    let x = read_f64()?;
+    let y = read_f64()?;
+
+    Ok(Coord { x, y })
+}
+
+fn read_coords(buf: &mut &[u8], little_endian: bool) -> Result<LineString<f64>, BoxDynError> {
+    let len = read_u32(buf, little_endian)?;
+
+    // not pre-allocated, as the length is not trusted
+    (0..len)
+        .map(|_| read_coord(buf, little_endian))
+        .collect::<Result<Vec<_>, _>>()
+        .map(LineString)
+}
+
+fn read_polygon(buf: &mut &[u8], little_endian: bool) -> Result<Polygon<f64>, BoxDynError> {
+    let mut rings = (0..read_u32(buf, little_endian)?)
+        .map(|_| read_coords(buf, little_endian))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter();
+
+    let exterior = rings.next().unwrap_or_else(|| LineString(Vec::new()));
+
+    Ok(Polygon::new(exterior, rings.collect()))
+}
+
+fn read_members<T>(
+    buf: &mut &[u8],
+    little_endian: bool,
+    mut member: impl FnMut(Geometry<f64>) -> Option<T>,
+) -> Result<Vec<T>, BoxDynError> {
+    (0..read_u32(buf, little_endian)?)
+        .map(|_| -> Result<T, BoxDynError> {
+            let geometry = read_geometry(buf)?;
+            let ty = geometry_type(&geometry);
+
+            member(geometry).ok_or_else(|| format!("unexpected {ty} in collection").into())
+        })
+        .collect()
+}
+
+fn geometry_type(geometry: &Geometry<f64>) -> &'static str {
+    match geometry {
+        Geometry::Point(_) => "POINT",
+        Geometry::Line(_) | Geometry::LineString(_) => "LINESTRING",
+        Geometry::Polygon(_) | Geometry::Rect(_) | Geometry::Triangle(_) => "POLYGON",
+        Geometry::MultiPoint(_) => "MULTIPOINT",
+        Geometry::MultiLineString(_) => "MULTILINESTRING",
+        Geometry::MultiPolygon(_) => "MULTIPOLYGON",
+        Geometry::GeometryCollection(_) => "GEOMETRYCOLLECTION",
+    }
+}
+
+fn read_geometry(buf: &mut &[u8]) -> Result<Geometry<f64>, BoxDynError> {
+    let little_endian = match read_bytes::<1>(buf)? {
+        [0] => false,
+        [1] => true,
+        [order] => return Err(format!("unknown byte order of geometry: {order}").into()),
+    };
+
+    Ok(match read_u32(buf, little_endian)? {
+        WKB_POINT => Geometry::Point(Point(read_coord(buf, little_endian)?)),
+
+        WKB_LINE_STRING => Geometry::LineString(read_coords(buf, little_endian)?),
+
+        WKB_POLYGON => Geometry::Polygon(read_polygon(buf, little_endian)?),
+
+        WKB_MULTI_POINT => {
+            Geometry::MultiPoint(MultiPoint(read_members(buf, little_endian, |geometry| {
+                match geometry {
+                    Geometry::Point(point) => Some(point),
+                    _ => None,
+                }
+            })?))
+        }
+
+        WKB_MULTI_LINE_STRING => Geometry::MultiLineString(MultiLineString(read_members(
+            buf,
+            little_endian,
+            |geometry| match geometry {
+                Geometry::LineString(line) => Some(line),
+                _ => None,
+            },
+        )?)),
+
+        WKB_MULTI_POLYGON => Geometry::MultiPolygon(MultiPolygon(read_members(
+            buf,
+            little_endian,
+            |geometry| match geometry {
+                Geometry::Polygon(polygon) => Some(polygon),
+                _ => None,
+            },
+        )?)),
+
+        WKB_GEOMETRY_COLLECTION => Geometry::GeometryCollection(GeometryCollection(read_members(
+            buf,
+            little_endian,
+            Some,
+        )?)),
+
+        ty => return Err(format!("unknown geometry type: {ty}").into()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{
+        line_string, point, polygon, Geometry, GeometryCollection, MultiLineString, MultiPoint,
+        MultiPolygon,
+    };
+
+    use super::{read_geometry, write_geometry};
+
+    fn round_trip(geometry: Geometry<f64>) {
+        let mut buf = Vec::new();
+        write_geometry(&geometry, &mut buf).unwrap();
+
+        let mut wkb = &buf[..];
+        assert_eq!(read_geometry(&mut wkb).unwrap(), geometry);
+        assert!(wkb.is_empty());
+    }
+
+    #[test]
+    fn test_encode_point() {
+        let mut buf = Vec::new();
+        write_geometry(&Geometry::Point(point!(x: 1.0, y: 2.0)), &mut buf).unwrap();
+
+        // the WKB of `ST_GeomFromText('POINT(1 2)')`
+        assert_eq!(
+            buf,
+            b"\x01\x01\x00\x00\x00\
+              \x00\x00\x00\x00\x00\x00\xf0\x3f\
+              \x00\x00\x00\x00\x00\x00\x00\x40"
+        );
+    }
+
+    #[test]
+    fn test_decode_big_endian_point() {
+        let mut wkb = &b"\x00\x00\x00\x00\x01\
+                         \x3f\xf0\x00\x00\x00\x00\x00\x00\
+                         \x40\x00\x00\x00\x00\x00\x00\x00"[..];
+
+        assert_eq!(
+            read_geometry(&mut wkb).unwrap(),
+            Geometry::Point(point!(x: 1.0, y: 2.0))
+        );
+    }
+
+    #[test]
+    fn test_round_trip_geometries() {
+        let line = line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0), (x: 2.0, y: 0.5)];
+        let polygon = polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 0.0)],
+            interiors: [[(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 2.0, y: 2.0), (x: 1.0, y: 1.0)]],
+        );
+
+        round_trip(Geometry::Point(point!(x: -1.5, y: 2.25)));
+        round_trip(Geometry::LineString(line.clone()));
+        round_trip(Geometry::Polygon(polygon.clone()));
+        round_trip(Geometry::MultiPoint(MultiPoint(vec![
+            point!(x: 1.0, y: 2.0),
+            point!(x: 3.0, y: 4.0),
+        ])));
+        round_trip(Geometry::MultiLineString(MultiLineString(vec![
+            line.clone(),
+            line.clone(),
+        ])));
+        round_trip(Geometry::MultiPolygon(MultiPolygon(vec![polygon.clone()])));
+        round_trip(Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::Point(point!(x: 1.0, y: 2.0)),
+            Geometry::LineString(line),
+            Geometry::Polygon(polygon),
+        ])));
+    }
+
+    #[test]
+    fn test_decode_invalid_geometries() {
+        let mut buf = Vec::new();
+        write_geometry(
+            &Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)]),
+            &mut buf,
+        )
+        .unwrap();
+
+        for len in 0..buf.len() {
+            assert!(read_geometry(&mut &buf[..len]).is_err());
+        }
+
+        // unknown byte order and geometry type
+        assert!(read_geometry(&mut &b"\x02\x01\x00\x00\x00"[..]).is_err());
+        assert!(read_geometry(&mut &b"\x01\x08\x00\x00\x00"[..]).is_err());
+
+        // a line string in a multi-point
+        let mut buf = b"\x01\x04\x00\x00\x00\x01\x00\x00\x00".to_vec();
+        write_geometry(
+            &Geometry::LineString(line_string![(x: 0.0, y: 0.0)]),
+            &mut buf,
+        )
+        .unwrap();
+
+        assert!(read_geometry(&mut &buf[..]).is_err());
+    }
+}
@@ -109,7 +109,12 @@ fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
                 .into());
             }
 
-            LittleEndian::read_int(buf, buf.len())
+            // an UNSIGNED value is zero-extended, and may not fit
+            if value.type_info.flags.contains(ColumnFlags::UNSIGNED) {
+                LittleEndian::read_uint(buf, buf.len()).try_into()?
+            } else {
+                LittleEndian::read_int(buf, buf.len())
+            }
         }
     })
 }
@@ -137,3 +142,64 @@ impl Decode<'_, MySql> for i64 {
         int_decode(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::decode::Decode;
+    use crate::protocol::text::{ColumnFlags, ColumnType};
+    use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    fn binary_value(ty: ColumnType, value: &[u8]) -> MySqlValueRef<'_> {
+        MySqlValueRef::test_value(value, MySqlTypeInfo::binary(ty), MySqlValueFormat::Binary)
+    }
+
+    #[test]
+    fn test_decode_binary_signed_tinyint() {
+        let decode = |byte: u8| {
+            <i8 as Decode<MySql>>::decode(binary_value(ColumnType::Tiny, &[byte])).unwrap()
+        };
+
+        assert_eq!(decode(0xff), -1);
+        assert_eq!(decode(0x80), -128);
+        assert_eq!(decode(0x7f), 127);
+    }
+
+    #[test]
+    fn test_decode_binary_signed_widening() {
+        // a negative TINYINT must sign-extend when read into a wider type
+        let value = binary_value(ColumnType::Tiny, &[0xff]);
+        assert_eq!(<i64 as Decode<MySql>>::decode(value).unwrap(), -1);
+
+        let value = binary_value(ColumnType::Short, &[0x00, 0x80]);
+        assert_eq!(<i32 as Decode<MySql>>::decode(value).unwrap(), -32768);
+
+        let value = binary_value(ColumnType::Long, &[0x2a, 0x00, 0x00, 0x00]);
+        assert_eq!(<i16 as Decode<MySql>>::decode(value).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_decode_binary_unsigned_column() {
+        let unsigned_value = |ty: ColumnType, value: &'static [u8]| {
+            let mut value = binary_value(ty, value);
+            value.type_info.flags |= ColumnFlags::UNSIGNED;
+            value
+        };
+
+        // a TINYINT UNSIGNED above i8::MAX must not be sign-extended
+        let value = unsigned_value(ColumnType::Tiny, &[0xff]);
+        assert_eq!(<i16 as Decode<MySql>>::decode(value).unwrap(), 255);
+
+        let value = unsigned_value(ColumnType::Tiny, &[0xff]);
+        assert!(<i8 as Decode<MySql>>::decode(value).is_err());
+
+        // a BIGINT UNSIGNED above i64::MAX does not fit
+        let value = unsigned_value(ColumnType::LongLong, &[0xff; 8]);
+        assert!(<i64 as Decode<MySql>>::decode(value).is_err());
+
+        let value = unsigned_value(ColumnType::LongLong, &[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+        assert_eq!(
+            <i64 as Decode<MySql>>::decode(value).unwrap(),
+            0xff_ff_ff_ff
+        );
+    }
+}
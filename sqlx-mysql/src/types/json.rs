@@ -66,3 +66,36 @@ where
         Json::decode_from_string(value.as_str()?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::decode::Decode;
+    use crate::encode::Encode;
+    use crate::protocol::text::ColumnType;
+    use crate::types::Json;
+    use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    #[test]
+    fn test_encode_json() {
+        let mut buf = Vec::new();
+        let _ = <Json<_> as Encode<MySql>>::encode_by_ref(&Json(vec![1, 2]), &mut buf).unwrap();
+
+        // the length is always encoded in the 9-byte form
+        assert_eq!(&buf[..9], b"\xfe\x05\x00\x00\x00\x00\x00\x00\x00");
+        assert_eq!(&buf[9..], b"[1,2]");
+    }
+
+    #[test]
+    fn test_decode_json_column() {
+        for format in [MySqlValueFormat::Text, MySqlValueFormat::Binary] {
+            let value = MySqlValueRef::test_value(
+                b"[1, 2]",
+                MySqlTypeInfo::binary(ColumnType::Json),
+                format,
+            );
+
+            let Json(decoded) = <Json<Vec<i64>> as Decode<MySql>>::decode(value).unwrap();
+            assert_eq!(decoded, [1, 2]);
+        }
+    }
+}
@@ -100,14 +100,14 @@
 //!
 //! | Rust type                             | MySQL/MariaDB type(s)                                |
 //! |---------------------------------------|------------------------------------------------------|
-//! | `bigdecimal::BigDecimal`              | DECIMAL                                              |
+//! | `bigdecimal::BigDecimal`              | DECIMAL, TINYINT, SMALLINT, INT, BIGINT              |
 //!
 //! ### [`decimal`](https://crates.io/crates/rust_decimal)
 //! Requires the `decimal` Cargo feature flag.
 //!
 //! | Rust type                             | MySQL/MariaDB type(s)                                |
 //! |---------------------------------------|------------------------------------------------------|
-//! | `rust_decimal::Decimal`               | DECIMAL                                              |
+//! | `rust_decimal::Decimal`               | DECIMAL, TINYINT, SMALLINT, INT, BIGINT              |
 //!
 //! ### [`uuid`](https://crates.io/crates/uuid)
 //!
@@ -119,6 +119,24 @@
 //! | `uuid::fmt::Hyphenated`               | CHAR(36), UUID (MariaDB-only)                        |
 //! | `uuid::fmt::Simple`                   | CHAR(32)                                             |
 //!
+//! ### [`geo-types`](https://crates.io/crates/geo-types)
+//!
+//! Requires the `geo-types` Cargo feature flag.
+//!
+//! | Rust type                             | MySQL/MariaDB type(s)                                |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `geo_types::Geometry<f64>`            | GEOMETRY, POINT, LINESTRING, POLYGON, MULTI\*, GEOMETRYCOLLECTION |
+//!
+//! Geometries are encoded with an SRID of 0; the SRID of decoded geometries is ignored.
+//!
+//! ### `SET`
+//!
+//! Requires the `set` Cargo feature flag.
+//!
+//! | Rust type                             | MySQL/MariaDB type(s)                                |
+//! |---------------------------------------|------------------------------------------------------|
+//! | [`MySqlSet`]                          | SET                                                  |
+//!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
 //! Requires the `json` Cargo feature flag.
@@ -138,6 +156,9 @@ pub(crate) use sqlx_core::types::*;
 
 pub use mysql_time::{MySqlTime, MySqlTimeError, MySqlTimeSign};
 
+#[cfg(feature = "set")]
+pub use set::MySqlSet;
+
 mod bool;
 mod bytes;
 mod float;
@@ -165,3 +186,9 @@ mod time;
 
 #[cfg(feature = "uuid")]
 mod uuid;
+
+#[cfg(feature = "geo-types")]
+mod geo_types;
+
+#[cfg(feature = "set")]
+mod set;
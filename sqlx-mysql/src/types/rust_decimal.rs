@@ -4,9 +4,9 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::io::MySqlBufMutExt;
-use crate::protocol::text::ColumnType;
+use crate::protocol::text::{ColumnFlags, ColumnType};
 use crate::types::Type;
-use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
 
 impl Type<MySql> for Decimal {
     fn type_info() -> MySqlTypeInfo {
@@ -14,7 +14,16 @@ impl Type<MySql> for Decimal {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        matches!(ty.r#type, ColumnType::Decimal | ColumnType::NewDecimal)
+        matches!(
+            ty.r#type,
+            ColumnType::Decimal
+                | ColumnType::NewDecimal
+                | ColumnType::Tiny
+                | ColumnType::Short
+                | ColumnType::Long
+                | ColumnType::Int24
+                | ColumnType::LongLong
+        )
     }
 }
 
@@ -28,6 +37,77 @@ impl Encode<'_, MySql> for Decimal {
 
 impl Decode<'_, MySql> for Decimal {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let ty = &value.type_info;
+
+        // integers are only sent as text in the text protocol
+        if matches!(value.format(), MySqlValueFormat::Binary)
+            && !matches!(ty.r#type, ColumnType::Decimal | ColumnType::NewDecimal)
+        {
+            return Ok(if ty.flags.contains(ColumnFlags::UNSIGNED) {
+                <u64 as Decode<MySql>>::decode(value)?.into()
+            } else {
+                <i64 as Decode<MySql>>::decode(value)?.into()
+            });
+        }
+
         Ok(value.as_str()?.parse()?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rust_decimal::Decimal;
+
+    use crate::decode::Decode;
+    use crate::protocol::text::{ColumnFlags, ColumnType};
+    use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    fn decode(ty: MySqlTypeInfo, format: MySqlValueFormat, value: &[u8]) -> Decimal {
+        <Decimal as Decode<MySql>>::decode(MySqlValueRef::test_value(value, ty, format)).unwrap()
+    }
+
+    fn decimal(value: &str) -> Decimal {
+        let ty = MySqlTypeInfo::binary(ColumnType::NewDecimal);
+
+        decode(ty, MySqlValueFormat::Text, value.as_bytes())
+    }
+
+    #[test]
+    fn test_decode_decimal() {
+        assert_eq!(decimal("123.45"), Decimal::new(12345, 2));
+        assert_eq!(decimal("-0.001"), Decimal::new(-1, 3));
+
+        let large = decimal("1234567890.123456789012345678");
+        assert_eq!(large.scale(), 18);
+        assert_eq!(
+            large,
+            Decimal::from_str("1234567890.123456789012345678").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_decimal_from_integer() {
+        let ty = MySqlTypeInfo::binary(ColumnType::LongLong);
+        assert_eq!(
+            decode(ty.clone(), MySqlValueFormat::Text, b"-42"),
+            Decimal::from(-42)
+        );
+
+        let value = (-9_007_199_254_740_993_i64).to_le_bytes();
+        assert_eq!(
+            decode(ty, MySqlValueFormat::Binary, &value),
+            Decimal::from(-9_007_199_254_740_993_i64)
+        );
+
+        let ty = MySqlTypeInfo {
+            flags: ColumnFlags::BINARY | ColumnFlags::UNSIGNED,
+            ..MySqlTypeInfo::binary(ColumnType::LongLong)
+        };
+        assert_eq!(
+            decode(ty, MySqlValueFormat::Binary, &u64::MAX.to_le_bytes()),
+            Decimal::from(u64::MAX)
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::types::Type;
+use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+// https://dev.mysql.com/doc/refman/8.0/en/set.html
+
+// NOTE: The server always sends SET values as their textual, comma-separated representation,
+//       in both the text and the binary protocol. The bitmask form is only used internally
+//       (and in the binary log), so there is nothing else to handle here.
+
+/// The members of a MySQL `SET` value.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::MySqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mysql::types::MySqlSet;
+///
+/// let (permissions,): (MySqlSet,) = sqlx::query_as("SELECT permissions FROM users LIMIT 1")
+///     .fetch_one(&mut *conn)
+///     .await?;
+///
+/// for permission in permissions.iter() {
+///     println!("{permission}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MySqlSet(pub Vec<String>);
+
+impl Deref for MySqlSet {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for MySqlSet {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<String>> for MySqlSet {
+    fn from(members: Vec<String>) -> Self {
+        Self(members)
+    }
+}
+
+impl FromIterator<String> for MySqlSet {
+    fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for MySqlSet {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Type<MySql> for MySqlSet {
+    fn type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo {
+            r#type: ColumnType::String,
+            flags: ColumnFlags::SET,
+            max_size: None,
+        }
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        ty.real_type() == ColumnType::Set
+    }
+}
+
+impl Encode<'_, MySql> for MySqlSet {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<MySql>>::encode(&*self.0.join(","), buf)
+    }
+}
+
+impl Decode<'_, MySql> for MySqlSet {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(Self(decode_set(value.as_str()?)))
+    }
+}
+
+fn decode_set(s: &str) -> Vec<String> {
+    // the empty set is sent as an empty string
+    if s.is_empty() {
+        return Vec::new();
+    }
+
+    // members of a SET cannot contain commas so a plain split is sufficient
+    s.split(',').map(ToOwned::to_owned).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_set, MySqlSet};
+    use crate::decode::Decode;
+    use crate::protocol::text::{ColumnFlags, ColumnType};
+    use crate::types::Type;
+    use crate::{MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    fn set_value(value: &[u8]) -> MySqlValueRef<'_> {
+        MySqlValueRef::test_value(
+            value,
+            MySqlTypeInfo {
+                r#type: ColumnType::String,
+                flags: ColumnFlags::SET,
+                max_size: None,
+            },
+            MySqlValueFormat::Text,
+        )
+    }
+
+    #[test]
+    fn test_decode_empty_set() {
+        assert!(decode_set("").is_empty());
+
+        let value = MySqlSet::decode(set_value(b"")).unwrap();
+        assert!(value.is_empty());
+    }
+
+    #[test]
+    fn test_decode_single_member_set() {
+        let value = MySqlSet::decode(set_value(b"read")).unwrap();
+        assert_eq!(*value, ["read"]);
+    }
+
+    #[test]
+    fn test_decode_multi_member_set() {
+        let value = MySqlSet::decode(set_value(b"read,write,admin")).unwrap();
+        assert_eq!(*value, ["read", "write", "admin"]);
+    }
+
+    #[test]
+    fn test_set_compatible() {
+        assert!(<MySqlSet as Type<crate::MySql>>::compatible(
+            &set_value(b"").type_info
+        ));
+        assert!(!<MySqlSet as Type<crate::MySql>>::compatible(
+            &MySqlTypeInfo::binary(ColumnType::VarString)
+        ));
+    }
+}
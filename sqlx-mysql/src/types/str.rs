@@ -19,7 +19,7 @@ impl Type<MySql> for str {
     fn compatible(ty: &MySqlTypeInfo) -> bool {
         // TODO: Support more collations being returned from SQL?
         matches!(
-            ty.r#type,
+            ty.real_type(),
             ColumnType::VarChar
                 | ColumnType::Blob
                 | ColumnType::TinyBlob
@@ -28,6 +28,7 @@ impl Type<MySql> for str {
                 | ColumnType::String
                 | ColumnType::VarString
                 | ColumnType::Enum
+                | ColumnType::Set
         ) && !ty.flags.contains(ColumnFlags::BINARY)
     }
 }
@@ -232,6 +232,14 @@ impl<'r> Decode<'r, MySql> for PrimitiveDateTime {
 
                 let len = buf.get_u8();
 
+                if buf.len() < len as usize {
+                    return Err(format!(
+                        "expected {len} bytes for DATETIME value, got {}",
+                        buf.len()
+                    )
+                    .into());
+                }
+
                 let date = decode_date(buf)?.ok_or(UnexpectedNullError)?;
 
                 let dt = if len > 4 {
@@ -285,6 +293,10 @@ fn decode_date(buf: &[u8]) -> Result<Option<Date>, BoxDynError> {
         return Ok(None);
     }
 
+    if buf.len() < 4 {
+        return Err(format!("expected at least 4 bytes for date, got {}", buf.len()).into());
+    }
+
     Date::from_calendar_date(
         LittleEndian::read_u16(buf) as i32,
         time::Month::try_from(buf[2])?,
@@ -305,6 +317,10 @@ fn encode_time(time: &Time, include_micros: bool, buf: &mut Vec<u8>) {
 }
 
 fn decode_time(mut buf: &[u8]) -> Result<Time, BoxDynError> {
+    if buf.len() < 3 {
+        return Err(format!("expected at least 3 bytes for time, got {}", buf.len()).into());
+    }
+
     let hour = buf.get_u8();
     let minute = buf.get_u8();
     let seconds = buf.get_u8();
@@ -319,3 +335,61 @@ fn decode_time(mut buf: &[u8]) -> Result<Time, BoxDynError> {
     Time::from_hms_micro(hour, minute, seconds, micros as u32)
         .map_err(|e| format!("Time out of range for MySQL: {e}").into())
 }
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+    use time::PrimitiveDateTime;
+
+    use crate::decode::Decode;
+    use crate::encode::Encode;
+    use crate::error::BoxDynError;
+    use crate::protocol::text::ColumnType;
+    use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    fn decode_datetime(buf: &[u8]) -> Result<PrimitiveDateTime, BoxDynError> {
+        <PrimitiveDateTime as Decode<MySql>>::decode(MySqlValueRef::test_value(
+            buf,
+            MySqlTypeInfo::binary(ColumnType::Datetime),
+            MySqlValueFormat::Binary,
+        ))
+    }
+
+    #[test]
+    fn test_datetime_binary_forms() {
+        for (datetime, encoded) in [
+            (datetime!(2024-01-02 0:00), &b"\x04\xe8\x07\x01\x02"[..]),
+            (
+                datetime!(2024-01-02 3:04:05),
+                b"\x07\xe8\x07\x01\x02\x03\x04\x05",
+            ),
+            (
+                datetime!(2024-01-02 3:04:05.000006),
+                b"\x0b\xe8\x07\x01\x02\x03\x04\x05\x06\x00\x00\x00",
+            ),
+        ] {
+            let mut buf = Vec::new();
+            let _ =
+                <PrimitiveDateTime as Encode<MySql>>::encode_by_ref(&datetime, &mut buf).unwrap();
+
+            assert_eq!(buf, encoded);
+            assert_eq!(decode_datetime(encoded).unwrap(), datetime);
+        }
+
+        // the zero date
+        assert!(decode_datetime(b"\x00").is_err());
+    }
+
+    #[test]
+    fn test_datetime_binary_truncated() {
+        let encoded = b"\x0b\xe8\x07\x01\x02\x03\x04\x05\x06\x00\x00\x00";
+
+        for len in 1..encoded.len() {
+            assert!(decode_datetime(&encoded[..len]).is_err());
+        }
+
+        // a date or time shorter than the length prefix claims
+        assert!(decode_datetime(b"\x02\xe8\x07").is_err());
+        assert!(decode_datetime(b"\x06\xe8\x07\x01\x02\x03\x04").is_err());
+    }
+}
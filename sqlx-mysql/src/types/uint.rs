@@ -132,7 +132,12 @@ fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
                 .into());
             }
 
-            LittleEndian::read_uint(buf, buf.len())
+            // a signed value is sign-extended, and may be negative
+            if value.type_info.flags.contains(ColumnFlags::UNSIGNED) {
+                LittleEndian::read_uint(buf, buf.len())
+            } else {
+                LittleEndian::read_int(buf, buf.len()).try_into()?
+            }
         }
     })
 }
@@ -160,3 +165,45 @@ impl Decode<'_, MySql> for u64 {
         uint_decode(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::decode::Decode;
+    use crate::protocol::text::{ColumnFlags, ColumnType};
+    use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    fn binary_value(ty: ColumnType, flags: ColumnFlags, value: &[u8]) -> MySqlValueRef<'_> {
+        MySqlValueRef::test_value(
+            value,
+            MySqlTypeInfo {
+                r#type: ty,
+                flags: ColumnFlags::BINARY | flags,
+                max_size: None,
+            },
+            MySqlValueFormat::Binary,
+        )
+    }
+
+    #[test]
+    fn test_decode_binary_unsigned() {
+        // a BIGINT UNSIGNED above i64::MAX
+        let value = binary_value(ColumnType::LongLong, ColumnFlags::UNSIGNED, &[0xff; 8]);
+        assert_eq!(<u64 as Decode<MySql>>::decode(value).unwrap(), u64::MAX);
+
+        let value = binary_value(ColumnType::Tiny, ColumnFlags::UNSIGNED, &[0xff]);
+        assert_eq!(<u8 as Decode<MySql>>::decode(value).unwrap(), 255);
+
+        let value = binary_value(ColumnType::Long, ColumnFlags::UNSIGNED, &[0, 0, 1, 0]);
+        assert!(<u16 as Decode<MySql>>::decode(value).is_err());
+    }
+
+    #[test]
+    fn test_decode_binary_signed_column() {
+        // a negative value of a signed column does not fit
+        let value = binary_value(ColumnType::Tiny, ColumnFlags::empty(), &[0xff]);
+        assert!(<u8 as Decode<MySql>>::decode(value).is_err());
+
+        let value = binary_value(ColumnType::Tiny, ColumnFlags::empty(), &[0x7f]);
+        assert_eq!(<u64 as Decode<MySql>>::decode(value).unwrap(), 127);
+    }
+}
@@ -33,6 +33,11 @@ impl Decode<'_, MySql> for Uuid {
         // delegate to the &[u8] type to decode from MySQL
         let bytes = <&[u8] as Decode<MySql>>::decode(value)?;
 
+        // a UUID stored as text, e.g. in a `CHAR(36)` column
+        if bytes.len() != 16 {
+            return Uuid::parse_str(std::str::from_utf8(bytes)?).map_err(Into::into);
+        }
+
         // construct a Uuid from the returned bytes
         Uuid::from_slice(bytes).map_err(Into::into)
     }
@@ -97,3 +102,36 @@ impl Decode<'_, MySql> for Simple {
             .map(|u| u.simple())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::decode::Decode;
+    use crate::protocol::text::ColumnType;
+    use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
+
+    fn decode(ty: ColumnType, value: &[u8]) -> Uuid {
+        <Uuid as Decode<MySql>>::decode(MySqlValueRef::test_value(
+            value,
+            MySqlTypeInfo::binary(ty),
+            MySqlValueFormat::Binary,
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_uuid() {
+        let uuid = Uuid::parse_str("8f2c2f3e-5b7a-4f43-9b2e-52d1c3a5e7f1").unwrap();
+
+        // BINARY(16)
+        assert_eq!(decode(ColumnType::String, uuid.as_bytes()), uuid);
+
+        // CHAR(36) and CHAR(32)
+        let hyphenated = uuid.hyphenated().to_string();
+        assert_eq!(decode(ColumnType::String, hyphenated.as_bytes()), uuid);
+
+        let simple = uuid.simple().to_string();
+        assert_eq!(decode(ColumnType::VarString, simple.as_bytes()), uuid);
+    }
+}
@@ -33,6 +33,22 @@ pub struct MySqlValueRef<'r> {
 }
 
 impl<'r> MySqlValueRef<'r> {
+    /// Returns a reference to a non-`NULL` value which is not part of a row,
+    /// for testing decoding.
+    #[cfg(test)]
+    pub(crate) fn test_value(
+        value: &'r [u8],
+        type_info: MySqlTypeInfo,
+        format: MySqlValueFormat,
+    ) -> Self {
+        Self {
+            value: Some(value),
+            row: None,
+            type_info,
+            format,
+        }
+    }
+
     pub(crate) fn format(&self) -> MySqlValueFormat {
         self.format
     }
@@ -0,0 +1,278 @@
+//! Decoding of result rows from the MySQL X Protocol (the transport of the Document Store
+//! and the X DevAPI).
+//!
+//! Unlike the classic protocol, the X Protocol encodes messages with Protocol Buffers.
+//! A row is a `Mysqlx.Resultset.Row` message, which is a list of fields, each holding the raw
+//! bytes of one value; the interpretation of those bytes depends on the column metadata.
+//!
+//! <https://dev.mysql.com/doc/dev/mysql-server/latest/page_mysqlx_protocol_messages.html>
+
+use std::ops::Range;
+
+use bytes::Bytes;
+
+use crate::error::Error;
+use crate::protocol::Row;
+use crate::MySqlRow;
+
+// message Row {
+//   repeated bytes field = 1;
+// }
+const FIELD_NUMBER: u64 = 1;
+
+// https://protobuf.dev/programming-guides/encoding/#structure
+const WIRE_TYPE_VARINT: u64 = 0;
+const WIRE_TYPE_I64: u64 = 1;
+const WIRE_TYPE_LEN: u64 = 2;
+const WIRE_TYPE_I32: u64 = 5;
+
+/// The raw values of a row, common to rows of the classic protocol ([`MySqlRow`])
+/// and of the X Protocol ([`MySqlXRow`]).
+///
+/// This allows code which only needs the raw bytes of each value to be generic over
+/// the transport a row was read from.
+///
+/// As [`Row`][sqlx_core::row::Row] has methods of the same names, call these
+/// as `RowLike::get(&row, index)` if both traits are in scope.
+pub trait RowLike {
+    /// Returns the number of values in this row.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if this row has no values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the raw bytes of the value at `index`, or `None` if it is `NULL`
+    /// or out of bounds.
+    fn get(&self, index: usize) -> Option<&[u8]>;
+
+    /// Returns `true` if the value at `index` is `NULL` or out of bounds.
+    fn is_null(&self, index: usize) -> bool {
+        self.get(index).is_none()
+    }
+}
+
+/// A row decoded from an X Protocol `Mysqlx.Resultset.Row` message.
+///
+/// This offers the same view of a row as the classic protocol: an ordered list of
+/// values which are either `NULL` or a slice of raw bytes.
+#[derive(Debug)]
+pub struct MySqlXRow {
+    row: Row,
+}
+
+impl MySqlXRow {
+    /// Decode a row from the payload of a `Mysqlx.Resultset.Row` message, that is,
+    /// without the 4-byte length and 1-byte message type which frame it.
+    pub fn decode(payload: impl Into<Bytes>) -> Result<Self, Error> {
+        let storage = payload.into();
+
+        let mut values = Vec::new();
+        let mut offset = 0;
+
+        while offset < storage.len() {
+            let key = read_varint(&storage, &mut offset)?;
+
+            let field_number = key >> 3;
+            let wire_type = key & 0x07;
+
+            let range = match wire_type {
+                WIRE_TYPE_VARINT => {
+                    read_varint(&storage, &mut offset)?;
+                    None
+                }
+
+                WIRE_TYPE_I64 => Some(skip(&storage, &mut offset, 8)?),
+                WIRE_TYPE_I32 => Some(skip(&storage, &mut offset, 4)?),
+
+                WIRE_TYPE_LEN => {
+                    let len = read_varint(&storage, &mut offset)?;
+                    Some(skip(&storage, &mut offset, len)?)
+                }
+
+                _ => {
+                    return Err(err_protocol!(
+                        "unsupported protobuf wire type {} in X Protocol row",
+                        wire_type
+                    ));
+                }
+            };
+
+            if field_number != FIELD_NUMBER {
+                // unknown fields must be ignored
+                continue;
+            }
+
+            let range = match (wire_type, range) {
+                (WIRE_TYPE_LEN, Some(range)) => range,
+                _ => {
+                    return Err(err_protocol!(
+                        "expected length-delimited field in X Protocol row but found wire type {}",
+                        wire_type
+                    ));
+                }
+            };
+
+            // NULL is sent as an empty field; all other values, including empty strings,
+            // have at least one byte
+            values.push((!range.is_empty()).then_some(range));
+        }
+
+        Ok(Self {
+            row: Row {
+                storage,
+                values,
+                skipped: Vec::new(),
+            },
+        })
+    }
+
+    /// Returns the number of values in this row.
+    pub fn len(&self) -> usize {
+        self.row.values.len()
+    }
+
+    /// Returns `true` if this row has no values.
+    pub fn is_empty(&self) -> bool {
+        self.row.values.is_empty()
+    }
+
+    /// Returns the raw bytes of the value at `index`, or `None` if it is `NULL`
+    /// or out of bounds.
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        if index >= self.len() {
+            return None;
+        }
+
+        self.row.get(index)
+    }
+
+    /// Returns `true` if the value at `index` is `NULL` or out of bounds.
+    pub fn is_null(&self, index: usize) -> bool {
+        self.get(index).is_none()
+    }
+}
+
+impl RowLike for MySqlXRow {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn get(&self, index: usize) -> Option<&[u8]> {
+        self.get(index)
+    }
+}
+
+impl RowLike for MySqlRow {
+    fn len(&self) -> usize {
+        self.row.values.len()
+    }
+
+    fn get(&self, index: usize) -> Option<&[u8]> {
+        if index >= self.row.values.len() {
+            return None;
+        }
+
+        self.row.get(index)
+    }
+}
+
+fn read_varint(buf: &[u8], offset: &mut usize) -> Result<u64, Error> {
+    let mut value = 0_u64;
+
+    for shift in (0..64).step_by(7) {
+        let byte = *buf
+            .get(*offset)
+            .ok_or_else(|| err_protocol!("unexpected end of X Protocol row in varint"))?;
+
+        *offset += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(err_protocol!(
+        "varint in X Protocol row is longer than 10 bytes"
+    ))
+}
+
+fn skip(buf: &[u8], offset: &mut usize, len: u64) -> Result<Range<usize>, Error> {
+    let start = *offset;
+    let end = usize::try_from(len)
+        .ok()
+        .and_then(|len| start.checked_add(len))
+        .filter(|end| *end <= buf.len())
+        .ok_or_else(|| {
+            err_protocol!(
+                "field of {} bytes at offset {} exceeds X Protocol row of {} bytes",
+                len,
+                start,
+                buf.len()
+            )
+        })?;
+
+    *offset = end;
+
+    Ok(start..end)
+}
+
+#[test]
+fn test_decode_x_protocol_row() {
+    // field 1: sint64 `1` (zigzag encoded as 2)
+    // field 2: NULL
+    // field 3: string "foo" (with its trailing NUL)
+    // field 4: empty string
+    const DATA: &[u8] = b"\x0a\x01\x02\x0a\x00\x0a\x04foo\x00\x0a\x01\x00";
+
+    let row = MySqlXRow::decode(DATA).unwrap();
+
+    assert_eq!(row.len(), 4);
+
+    assert_eq!(row.get(0), Some(&b"\x02"[..]));
+    assert!(row.is_null(1));
+    assert_eq!(row.get(2), Some(&b"foo\x00"[..]));
+    assert_eq!(row.get(3), Some(&b"\x00"[..]));
+
+    assert!(row.is_null(4));
+}
+
+#[test]
+fn test_decode_x_protocol_row_truncated() {
+    const DATA: &[u8] = b"\x0a\x05ab";
+
+    assert!(MySqlXRow::decode(DATA).is_err());
+}
+
+#[test]
+fn test_row_like() {
+    use crate::MySqlValueFormat;
+    use std::sync::Arc;
+
+    fn values<R: RowLike>(row: &R) -> Vec<Option<&[u8]>> {
+        (0..row.len()).map(|index| row.get(index)).collect()
+    }
+
+    let x_row = MySqlXRow::decode(&b"\x0a\x04foo\x00\x0a\x00"[..]).unwrap();
+
+    let classic_row = MySqlRow {
+        row: Row {
+            storage: Bytes::from_static(b"\x04foo\x00\xfb"),
+            values: vec![Some(1..5), None],
+            skipped: Vec::new(),
+        },
+        format: MySqlValueFormat::Text,
+        columns: Arc::default(),
+        column_names: Arc::default(),
+        out_params: false,
+        #[cfg(feature = "profile")]
+        decode_timings: Vec::new(),
+    };
+
+    assert_eq!(values(&x_row), values(&classic_row));
+
+    assert!(RowLike::is_null(&classic_row, 1));
+    assert!(RowLike::is_null(&classic_row, 2));
+}
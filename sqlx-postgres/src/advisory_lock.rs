@@ -1,6 +1,7 @@
 use crate::error::Result;
+use crate::transaction::Transaction;
 use crate::Either;
-use crate::PgConnection;
+use crate::{PgConnection, Postgres};
 use hkdf::Hkdf;
 use once_cell::sync::OnceCell;
 use sha2::Sha256;
@@ -16,13 +17,12 @@ use std::ops::{Deref, DerefMut};
 /// advisory locks use, as well as RAII guards for releasing advisory locks when they fall out
 /// of scope.
 ///
-/// This API only handles session-scoped advisory locks (explicitly locked and unlocked, or
-/// automatically released when a connection is closed).
+/// Session-scoped advisory locks are explicitly locked and unlocked, or automatically released
+/// when a connection is closed; see [`Self::acquire()`] and [`Self::try_acquire()`].
 ///
-/// It is also possible to use transaction-scoped locks but those can be used by beginning a
-/// transaction and calling the appropriate lock functions (e.g. `SELECT pg_advisory_xact_lock()`)
-/// manually, and cannot be explicitly released, but are automatically released when a transaction
-/// ends (is committed or rolled back).
+/// Transaction-scoped locks cannot be explicitly released, but are automatically released when
+/// a transaction ends (is committed or rolled back); see [`Self::acquire_for_transaction()`] and
+/// [`Self::try_acquire_for_transaction()`].
 ///
 /// Session-level locks can be acquired either inside or outside a transaction and are not
 /// tied to transaction semantics; a lock acquired inside a transaction is still held when that
@@ -300,6 +300,75 @@ impl PgAdvisoryLock {
         Ok((conn, released))
     }
 
+    /// Acquires an exclusive lock for the rest of the transaction using
+    /// `pg_advisory_xact_lock()`, waiting until the lock is acquired.
+    ///
+    /// For a version that returns immediately instead of waiting, see
+    /// [`Self::try_acquire_for_transaction()`].
+    ///
+    /// The lock is released when the transaction is committed or rolled back, and cannot be
+    /// released before that, so no guard is returned. If the transaction is a savepoint, the lock
+    /// is still held until the whole transaction ends.
+    ///
+    /// See [Postgres' documentation for the Advisory Lock Functions][advisory-funcs] for details.
+    ///
+    /// [advisory-funcs]: https://www.postgresql.org/docs/current/functions-admin.html#FUNCTIONS-ADVISORY-LOCKS
+    pub async fn acquire_for_transaction(&self, tx: &mut Transaction<'_, Postgres>) -> Result<()> {
+        match &self.key {
+            PgAdvisoryLockKey::BigInt(key) => {
+                crate::query::query("SELECT pg_advisory_xact_lock($1)")
+                    .bind(key)
+                    .execute(&mut **tx)
+                    .await?;
+            }
+            PgAdvisoryLockKey::IntPair(key1, key2) => {
+                crate::query::query("SELECT pg_advisory_xact_lock($1, $2)")
+                    .bind(key1)
+                    .bind(key2)
+                    .execute(&mut **tx)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Acquires an exclusive lock for the rest of the transaction using
+    /// `pg_try_advisory_xact_lock()`, returning `false` immediately if the lock could not
+    /// be acquired.
+    ///
+    /// For a version that waits until the lock is acquired, see
+    /// [`Self::acquire_for_transaction()`].
+    ///
+    /// The lock is released when the transaction is committed or rolled back, and cannot be
+    /// released before that.
+    ///
+    /// See [Postgres' documentation for the Advisory Lock Functions][advisory-funcs] for details.
+    ///
+    /// [advisory-funcs]: https://www.postgresql.org/docs/current/functions-admin.html#FUNCTIONS-ADVISORY-LOCKS
+    pub async fn try_acquire_for_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+    ) -> Result<bool> {
+        let locked = match &self.key {
+            PgAdvisoryLockKey::BigInt(key) => {
+                crate::query_scalar::query_scalar("SELECT pg_try_advisory_xact_lock($1)")
+                    .bind(key)
+                    .fetch_one(&mut **tx)
+                    .await?
+            }
+            PgAdvisoryLockKey::IntPair(key1, key2) => {
+                crate::query_scalar::query_scalar("SELECT pg_try_advisory_xact_lock($1, $2)")
+                    .bind(key1)
+                    .bind(key2)
+                    .fetch_one(&mut **tx)
+                    .await?
+            }
+        };
+
+        Ok(locked)
+    }
+
     fn get_release_query(&self) -> &str {
         self.release_query.get_or_init(|| match &self.key {
             PgAdvisoryLockKey::BigInt(key) => format!("SELECT pg_advisory_unlock({key})"),
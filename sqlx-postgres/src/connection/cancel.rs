@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use crate::connection::tls::MaybeUpgradeTls;
+use crate::error::Error;
+use crate::message::CancelRequest;
+use crate::net::{self, BufferedSocket};
+use crate::{PgConnectOptions, PgConnection};
+
+/// A handle to cancel the query running on a [`PgConnection`], from another task.
+///
+/// Dropping the future of a query, e.g. on a timeout, does not stop the query on the server,
+/// and the connection must still wait for it to finish the next time it is used.
+/// [`cancel()`][Self::cancel] asks the server to stop it instead:
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::Executor;
+/// use sqlx_core::rt;
+/// use std::time::Duration;
+///
+/// let cancel = conn.cancel_handle();
+///
+/// rt::spawn(async move {
+///     rt::sleep(Duration::from_secs(5)).await;
+///     let _ = cancel.cancel().await;
+/// });
+///
+/// // fails with `57014` (`query_canceled`) if still running after 5 seconds
+/// conn.execute("SELECT pg_sleep(60)").await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The cancellation is best-effort: the server may have already finished the query, or may
+/// finish it before acting on the request, in which case nothing is canceled.
+#[derive(Debug, Clone)]
+pub struct PgCancelHandle {
+    options: Arc<PgConnectOptions>,
+    process_id: u32,
+    secret_key: u32,
+}
+
+impl PgConnection {
+    /// Returns a handle to cancel the query running on this connection from another task.
+    pub fn cancel_handle(&self) -> PgCancelHandle {
+        PgCancelHandle {
+            options: Arc::clone(&self.options),
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        }
+    }
+}
+
+impl PgCancelHandle {
+    /// Asks the server to cancel the query running on the connection, if any.
+    ///
+    /// This opens a new connection to the server, with the same options as the original one,
+    /// to send the request.
+    pub async fn cancel(&self) -> Result<(), Error> {
+        let options = &*self.options;
+
+        let socket_future = match options.fetch_socket() {
+            Some(ref path) => net::connect_uds(path, MaybeUpgradeTls(options)).await?,
+            None => net::connect_tcp(&options.host, options.port, MaybeUpgradeTls(options)).await?,
+        };
+
+        let mut socket = BufferedSocket::new(socket_future.await?);
+
+        socket.write(CancelRequest {
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        });
+
+        socket.flush().await?;
+
+        // the server closes the connection without a response once it has processed the request;
+        // waiting for that ensures the cancellation is not raced by a following query
+        let _ = socket.read_buffered(1).await;
+
+        Ok(())
+    }
+}
@@ -146,6 +146,29 @@ impl PgConnection {
         Ok(params)
     }
 
+    // whether the server can send values of the type in binary, which is not the case for
+    // user-defined types without a `send` function or for types built from them
+    pub(super) fn has_binary_output(&self, ty: &PgTypeInfo) -> bool {
+        let PgType::Custom(custom) = &ty.0 else {
+            // all built-in types can be sent in binary
+            return true;
+        };
+
+        if self.cache_text_only_types.contains(&custom.oid) {
+            return false;
+        }
+
+        match &custom.kind {
+            PgTypeKind::Domain(base) => self.has_binary_output(base),
+            PgTypeKind::Array(element) => self.has_binary_output(element),
+            PgTypeKind::Range(subtype) => self.has_binary_output(subtype),
+            PgTypeKind::Composite(fields) => {
+                fields.iter().all(|(_, ty)| self.has_binary_output(ty))
+            }
+            _ => true,
+        }
+    }
+
     async fn maybe_fetch_type_info_by_oid(
         &mut self,
         oid: Oid,
@@ -186,13 +209,14 @@ impl PgConnection {
 
     fn fetch_type_by_oid(&mut self, oid: Oid) -> BoxFuture<'_, Result<PgTypeInfo, Error>> {
         Box::pin(async move {
-            let (name, typ_type, category, relation_id, element, base_type): (
+            let (name, typ_type, category, relation_id, element, base_type, binary_output): (
                 String,
                 i8,
                 i8,
                 Oid,
                 Oid,
                 Oid,
+                bool,
             ) = query_as(
                 // Converting the OID to `regtype` and then `text` will give us the name that
                 // the type will need to be found at by search_path.
@@ -201,7 +225,8 @@ impl PgConnection {
                      typcategory, \
                      typrelid, \
                      typelem, \
-                     typbasetype \
+                     typbasetype, \
+                     typsend::oid <> 0 \
                      FROM pg_catalog.pg_type \
                      WHERE oid = $1",
             )
@@ -209,6 +234,11 @@ impl PgConnection {
             .fetch_one(&mut *self)
             .await?;
 
+            if !binary_output {
+                // e.g. extension types without a `send` function
+                self.cache_text_only_types.insert(oid);
+            }
+
             let typ_type = TypType::try_from(typ_type as u8);
             let category = TypCategory::try_from(category as u8);
 
@@ -373,14 +403,16 @@ WHERE rngtypid = $1
             return Ok(*oid);
         }
 
+        // `to_regtype()` returns `NULL` for an unknown type where a cast to `regtype` fails
         // language=SQL
-        let (oid,): (Oid,) = query_as("SELECT $1::regtype::oid")
+        let (oid,): (Option<Oid>,) = query_as("SELECT to_regtype($1)::oid")
             .bind(name)
-            .fetch_optional(&mut *self)
-            .await?
-            .ok_or_else(|| Error::TypeNotFound {
-                type_name: name.into(),
-            })?;
+            .fetch_one(&mut *self)
+            .await?;
+
+        let oid = oid.ok_or_else(|| Error::TypeNotFound {
+            type_name: name.into(),
+        })?;
 
         self.cache_type_oid.insert(name.to_string().into(), oid);
         Ok(oid)
@@ -397,7 +429,7 @@ WHERE rngtypid = $1
 
         // language=SQL
         let (elem_oid, array_oid): (Oid, Oid) =
-            query_as("SELECT oid, typarray FROM pg_catalog.pg_type WHERE oid = $1::regtype::oid")
+            query_as("SELECT oid, typarray FROM pg_catalog.pg_type WHERE oid = to_regtype($1)")
                 .bind(&*array.elem_name)
                 .fetch_optional(&mut *self)
                 .await?
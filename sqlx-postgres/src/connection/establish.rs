@@ -1,6 +1,11 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
 use crate::HashMap;
 
 use crate::common::StatementCache;
+#[cfg(feature = "gssapi")]
+use crate::connection::gss;
 use crate::connection::{sasl, stream::PgStream};
 use crate::error::Error;
 use crate::io::Decode;
@@ -44,6 +49,11 @@ impl PgConnection {
             params.push(("options", options));
         }
 
+        if options.replication {
+            // a walsender which accepts replication commands as well as SQL
+            params.push(("replication", "database"));
+        }
+
         stream
             .send(Startup {
                 username: Some(&options.username),
@@ -100,6 +110,22 @@ impl PgConnection {
                         sasl::authenticate(&mut stream, options, body).await?;
                     }
 
+                    // like libpq without SSPI support, SSPI is negotiated with GSSAPI,
+                    // which is compatible when the server uses Kerberos
+                    #[cfg(feature = "gssapi")]
+                    Authentication::Gss | Authentication::Sspi => {
+                        gss::authenticate(&mut stream, options).await?;
+                    }
+
+                    #[cfg(not(feature = "gssapi"))]
+                    Authentication::Gss | Authentication::Sspi => {
+                        return Err(Error::Configuration(
+                            "the server requested GSSAPI authentication, \
+                             which requires the `gssapi` feature of `sqlx-postgres`"
+                                .into(),
+                        ));
+                    }
+
                     method => {
                         return Err(err_protocol!(
                             "unsupported authentication method: {:?}",
@@ -139,14 +165,17 @@ impl PgConnection {
             stream,
             process_id,
             secret_key,
+            options: Arc::new(options.clone()),
             transaction_status,
             transaction_depth: 0,
             pending_ready_for_query_count: 0,
+            open_portal: None,
             next_statement_id: Oid(1),
             cache_statement: StatementCache::new(options.statement_cache_capacity),
             cache_type_oid: HashMap::new(),
             cache_type_info: HashMap::new(),
             cache_elem_type_to_array: HashMap::new(),
+            cache_text_only_types: HashSet::new(),
             log_settings: options.log_settings.clone(),
         })
     }
@@ -84,10 +84,31 @@ async fn prepare(
         // continuing
         conn.wait_until_ready().await?;
 
+        // columns the server cannot send in binary are requested in text, which the decoders
+        // of such types would have to accept anyway
+        let result_formats = if columns
+            .iter()
+            .all(|column| conn.has_binary_output(&column.type_info))
+        {
+            Vec::new()
+        } else {
+            columns
+                .iter()
+                .map(|column| {
+                    if conn.has_binary_output(&column.type_info) {
+                        PgValueFormat::Binary
+                    } else {
+                        PgValueFormat::Text
+                    }
+                })
+                .collect()
+        };
+
         Arc::new(PgStatementMetadata {
             parameters,
             columns,
             column_names: Arc::new(column_names),
+            result_formats,
         })
     };
 
@@ -147,6 +168,49 @@ impl PgConnection {
         Ok(())
     }
 
+    // closes the portal of a query fetched in batches once all of its rows have been read,
+    // and asks the server for the [ReadyForQuery] that ends the query
+    async fn close_open_portal(&mut self) -> Result<(), Error> {
+        if let Some((portal, one_off)) = self.open_portal.take() {
+            self.stream.write(message::Close::Portal(Some(portal)));
+            self.write_sync();
+            self.write_close_one_off(one_off);
+
+            self.stream.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    // statements which are not stored in the cache (`persistent(false)` or a disabled cache)
+    // are only used once and must be closed, or they are leaked until the connection closes
+    pub(crate) fn one_off_statement(&mut self, sql: &str, statement: Oid) -> Option<Oid> {
+        (!self.cache_statement.contains_key(sql)).then_some(statement)
+    }
+
+    // the statement is closed after the [Sync] of the query that used it, as the server skips
+    // every message up to the next [Sync] if the query fails
+    pub(crate) fn write_close_one_off(&mut self, one_off: Option<Oid>) {
+        if let Some(statement) = one_off {
+            self.stream.write(Close::Statement(statement));
+            self.write_sync();
+        }
+    }
+
+    async fn close_one_off_statement(&mut self, sql: &str, statement: Oid) -> Result<(), Error> {
+        if let Some(statement) = self.one_off_statement(sql, statement) {
+            self.stream.write(Close::Statement(statement));
+            self.write_sync();
+
+            self.stream.flush().await?;
+
+            self.wait_for_close_complete(1).await?;
+            self.recv_ready_for_query().await?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn write_sync(&mut self) {
         self.stream.write(message::Sync);
 
@@ -154,7 +218,7 @@ impl PgConnection {
         self.pending_ready_for_query_count += 1;
     }
 
-    async fn get_or_prepare<'a>(
+    pub(crate) async fn get_or_prepare<'a>(
         &mut self,
         sql: &str,
         parameters: &[PgTypeInfo],
@@ -209,45 +273,75 @@ impl PgConnection {
 
             metadata = metadata_;
 
+            let one_off = self.one_off_statement(query, statement);
+
             // patch holes created during encoding
             arguments.apply_patches(self, &metadata.parameters).await?;
 
             // consume messages till `ReadyForQuery` before bind and execute
             self.wait_until_ready().await?;
 
+            // rows are fetched from a named portal in batches if a fetch size is set,
+            // unless the caller only wants a limited number of rows anyway
+            let fetch_size = self.options.cursor_fetch_size.filter(|_| limit == 0);
+
+            let portal = if fetch_size.is_some() {
+                let id = self.next_statement_id;
+                self.next_statement_id.incr_one();
+
+                Some(id)
+            } else {
+                None
+            };
+
             // bind to attach the arguments to the statement and create a portal
             self.stream.write(Bind {
-                portal: None,
+                portal,
                 statement,
                 formats: &[PgValueFormat::Binary],
                 num_params: arguments.types.len() as i16,
                 params: &arguments.buffer,
-                result_formats: &[PgValueFormat::Binary],
+                result_formats: metadata.bind_result_formats(),
             });
 
-            // executes the portal up to the passed limit
-            // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
-            self.stream.write(message::Execute {
-                portal: None,
-                limit: limit.into(),
-            });
-            // From https://www.postgresql.org/docs/current/protocol-flow.html:
-            //
-            // "An unnamed portal is destroyed at the end of the transaction, or as
-            // soon as the next Bind statement specifying the unnamed portal as
-            // destination is issued. (Note that a simple Query message also
-            // destroys the unnamed portal."
-
-            // we ask the database server to close the unnamed portal and free the associated resources
-            // earlier - after the execution of the current query.
-            self.stream.write(message::Close::Portal(None));
-
-            // finally, [Sync] asks postgres to process the messages that we sent and respond with
-            // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
-            // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
-            // is still serial but it would reduce round-trips. Some kind of builder pattern that is
-            // termed batching might suit this.
-            self.write_sync();
+            if let Some(fetch_size) = fetch_size {
+                // executes the portal up to the fetch size; the server suspends the portal
+                // with [PortalSuspended] once the batch is sent and we ask for the next one
+                self.stream.write(message::Execute {
+                    portal,
+                    limit: fetch_size,
+                });
+
+                // a [Sync] would end the implicit transaction and destroy the portal,
+                // so we only ask the server to send what it has so far
+                self.stream.write(message::Flush);
+                self.open_portal = portal.map(|portal| (portal, one_off));
+            } else {
+                // executes the portal up to the passed limit
+                // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
+                self.stream.write(message::Execute {
+                    portal: None,
+                    limit: limit.into(),
+                });
+                // From https://www.postgresql.org/docs/current/protocol-flow.html:
+                //
+                // "An unnamed portal is destroyed at the end of the transaction, or as
+                // soon as the next Bind statement specifying the unnamed portal as
+                // destination is issued. (Note that a simple Query message also
+                // destroys the unnamed portal."
+
+                // we ask the database server to close the unnamed portal and free the associated resources
+                // earlier - after the execution of the current query.
+                self.stream.write(message::Close::Portal(None));
+
+                // finally, [Sync] asks postgres to process the messages that we sent and respond with
+                // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
+                // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
+                // is still serial but it would reduce round-trips. Some kind of builder pattern that is
+                // termed batching might suit this.
+                self.write_sync();
+                self.write_close_one_off(one_off);
+            }
 
             // prepared statements are binary
             PgValueFormat::Binary
@@ -288,6 +382,8 @@ impl PgConnection {
                         // a SQL command completed normally
                         let cc: CommandComplete = message.decode()?;
 
+                        self.close_open_portal().await?;
+
                         let rows_affected = cc.rows_affected();
                         logger.increase_rows_affected(rows_affected);
                         r#yield!(Either::Left(PgQueryResult {
@@ -297,12 +393,26 @@ impl PgConnection {
 
                     MessageFormat::EmptyQueryResponse => {
                         // empty query string passed to an unprepared execute
+                        self.close_open_portal().await?;
                     }
 
                     // Message::ErrorResponse is handled in self.stream.recv()
 
-                    // incomplete query execution has finished
-                    MessageFormat::PortalSuspended => {}
+                    MessageFormat::PortalSuspended => {
+                        if let (Some((portal, _)), Some(fetch_size)) =
+                            (self.open_portal, self.options.cursor_fetch_size)
+                        {
+                            // the batch has been sent, ask for the next one
+                            self.stream.write(message::Execute {
+                                portal: Some(portal),
+                                limit: fetch_size,
+                            });
+                            self.stream.write(message::Flush);
+                            self.stream.flush().await?;
+                        }
+
+                        // otherwise, incomplete query execution has finished
+                    }
 
                     MessageFormat::RowDescription => {
                         // indicates that a *new* set of rows are about to be returned
@@ -314,6 +424,7 @@ impl PgConnection {
                             column_names: Arc::new(column_names),
                             columns,
                             parameters: Vec::default(),
+                            result_formats: Vec::default(),
                         });
                     }
 
@@ -429,7 +540,10 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         Box::pin(async move {
             self.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, parameters, true, None).await?;
+            let (statement, metadata) = self.get_or_prepare(sql, parameters, true, None).await?;
+
+            // the statement is prepared again when it is executed if it was not cached
+            self.close_one_off_statement(sql, statement).await?;
 
             Ok(PgStatement {
                 sql: Cow::Borrowed(sql),
@@ -452,6 +566,8 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
 
             let nullable = self.get_nullable_for_columns(stmt_id, &metadata).await?;
 
+            self.close_one_off_statement(sql, stmt_id).await?;
+
             Ok(Describe {
                 columns: metadata.columns.clone(),
                 nullable,
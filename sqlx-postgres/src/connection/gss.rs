@@ -0,0 +1,55 @@
+use libgssapi::context::{ClientCtx, CtxFlags, SecurityContext};
+use libgssapi::name::Name;
+use libgssapi::oid::GSS_NT_HOSTBASED_SERVICE;
+use sqlx_core::bytes::Bytes;
+
+use crate::connection::stream::PgStream;
+use crate::error::Error;
+use crate::message::{Authentication, GssResponse, MessageFormat};
+use crate::PgConnectOptions;
+
+// https://www.postgresql.org/docs/current/gssapi-auth.html
+
+pub(crate) async fn authenticate(
+    stream: &mut PgStream,
+    options: &PgConnectOptions,
+) -> Result<(), Error> {
+    // like libpq, the server principal is `<krbsrvname>@<host>`,
+    // which the GSSAPI library resolves to e.g. `postgres/<host>@<REALM>`
+    let service_name = options.krb_service_name.as_deref().unwrap_or("postgres");
+    let target = format!("{service_name}@{}", options.host);
+
+    let target = Name::new(target.as_bytes(), Some(&GSS_NT_HOSTBASED_SERVICE))
+        .map_err(|e| err_protocol!("GSSAPI: invalid service name {:?}: {}", target, e))?;
+
+    // the default credentials of the process, e.g. the Kerberos ticket cache
+    let mut context = ClientCtx::new(None, target, CtxFlags::GSS_C_MUTUAL_FLAG, None);
+    let mut server_token: Option<Bytes> = None;
+
+    loop {
+        let token = context
+            .step(server_token.as_deref(), None)
+            .map_err(|e| err_protocol!("GSSAPI authentication failed: {}", e))?
+            .map(|token| token.to_vec());
+
+        if let Some(token) = token {
+            stream.send(GssResponse(&token)).await?;
+        }
+
+        if context.is_complete() {
+            // the server will next send `AuthenticationOk`
+            return Ok(());
+        }
+
+        match stream.recv_expect(MessageFormat::Authentication).await? {
+            Authentication::GssContinue(data) => server_token = Some(data),
+
+            message => {
+                return Err(err_protocol!(
+                    "expected GssContinue but received {:?}",
+                    message
+                ));
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
 
@@ -15,15 +16,21 @@ use crate::message::{
 use crate::statement::PgStatementMetadata;
 use crate::transaction::Transaction;
 use crate::types::Oid;
-use crate::{PgConnectOptions, PgTypeInfo, Postgres};
+use crate::{PgConnectOptions, PgDatabaseError, PgTypeInfo, Postgres};
 
 pub(crate) use sqlx_core::connection::*;
 
+pub use self::cancel::PgCancelHandle;
+pub use self::pipeline::PgPipeline;
 pub use self::stream::PgStream;
 
+mod cancel;
 pub(crate) mod describe;
 mod establish;
 mod executor;
+#[cfg(feature = "gssapi")]
+mod gss;
+mod pipeline;
 mod sasl;
 mod stream;
 mod tls;
@@ -37,14 +44,16 @@ pub struct PgConnection {
 
     // process id of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     process_id: u32,
 
     // secret key of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     secret_key: u32,
 
+    // options the connection was made with
+    // used to connect again to send cancel requests
+    options: Arc<PgConnectOptions>,
+
     // sequence of statement IDs for use in preparing statements
     // in PostgreSQL, the statement is prepared to a user-supplied identifier
     next_statement_id: Oid,
@@ -56,10 +65,17 @@ pub struct PgConnection {
     cache_type_info: HashMap<Oid, PgTypeInfo>,
     cache_type_oid: HashMap<UStr, Oid>,
     cache_elem_type_to_array: HashMap<Oid, Oid>,
+    // user-defined types the server cannot send in binary
+    cache_text_only_types: HashSet<Oid>,
 
     // number of ReadyForQuery messages that we are currently expecting
     pub(crate) pending_ready_for_query_count: usize,
 
+    // portal of a query whose rows are being fetched in batches, with the statement it was
+    // bound from if that is not cached
+    // both must be closed and followed by a SYNC before the next query
+    pub(crate) open_portal: Option<(Oid, Option<Oid>)>,
+
     // current transaction status
     transaction_status: TransactionStatus,
     pub(crate) transaction_depth: usize,
@@ -73,8 +89,54 @@ impl PgConnection {
         self.stream.server_version_num
     }
 
+    /// Returns the number of transactions and savepoints open on this connection: `0` outside
+    /// of a transaction, `1` in a transaction, and one more for each nested
+    /// [`begin()`][sqlx_core::acquire::Acquire::begin] which created a savepoint.
+    pub fn transaction_depth(&self) -> usize {
+        self.transaction_depth
+    }
+
+    /// Returns the current value of a parameter reported by the server, such as
+    /// `server_version`, `TimeZone`, `client_encoding` or `application_name`.
+    ///
+    /// The server reports these parameters when the connection is established and again
+    /// whenever one of them changes, e.g. after `SET TIME ZONE`. Returns `None` for a
+    /// parameter the server does not report.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.stream.parameter_statuses.get(name).map(String::as_str)
+    }
+
+    /// Sets a handler called with each notice or warning sent by the server, such as the
+    /// output of `RAISE NOTICE` in PL/pgSQL, instead of logging it.
+    ///
+    /// Notices are logged under the `sqlx::postgres::notice` target by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::postgres::PgConnection) -> sqlx::Result<()> {
+    /// conn.set_notice_handler(|notice| {
+    ///     eprintln!("{:?} {}: {}", notice.severity(), notice.code(), notice.message());
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_notice_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(&PgDatabaseError) + Send + Sync + 'static,
+    {
+        self.stream.notice_handler = Some(Box::new(handler));
+    }
+
     // will return when the connection is ready for another query
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
+        if let Some((portal, one_off)) = self.open_portal.take() {
+            // the rows of the portal were not read to the end
+            self.stream.write(Close::Portal(Some(portal)));
+            self.write_sync();
+            self.write_close_one_off(one_off);
+        }
+
         if !self.stream.write_buffer_mut().is_empty() {
             self.stream.flush().await?;
         }
@@ -97,18 +159,32 @@ impl PgConnection {
             .await?;
 
         self.pending_ready_for_query_count -= 1;
-        self.transaction_status = r.transaction_status;
+        self.set_transaction_status(r.transaction_status);
 
         Ok(())
     }
 
     fn handle_ready_for_query(&mut self, message: Message) -> Result<(), Error> {
         self.pending_ready_for_query_count -= 1;
-        self.transaction_status = ReadyForQuery::decode(message.contents)?.transaction_status;
+        self.set_transaction_status(ReadyForQuery::decode(message.contents)?.transaction_status);
 
         Ok(())
     }
 
+    fn set_transaction_status(&mut self, status: TransactionStatus) {
+        // the transaction was ended without going through `Transaction`, e.g. by a `COMMIT`
+        // in a query; the savepoints are gone with it, so the next `begin()` starts a new
+        // transaction instead of a savepoint of a transaction that no longer exists
+        //
+        // only the last expected status is current, the earlier ones may answer a queued
+        // rollback preceding a `BEGIN`
+        if matches!(status, TransactionStatus::Idle) && self.pending_ready_for_query_count == 0 {
+            self.transaction_depth = 0;
+        }
+
+        self.transaction_status = status;
+    }
+
     /// Queue a simple query (not prepared) to execute the next time this connection is used.
     ///
     /// Used for rolling back transactions and releasing advisory locks.
@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use futures_core::future::BoxFuture;
+use sqlx_core::error::BoxDynError;
+
+use crate::error::Error;
+use crate::executor::Execute;
+use crate::logger::QueryLogger;
+use crate::message::{self, Bind, CommandComplete, DataRow, MessageFormat};
+use crate::statement::PgStatementMetadata;
+use crate::{PgArguments, PgConnection, PgQueryResult, PgRow, PgValueFormat, Postgres};
+
+/// A batch of queries sent to the server together, with a single round trip.
+///
+/// Created by [`PgConnection::pipeline()`]. The `Parse`, `Bind` and `Execute` messages of every
+/// query are written before a single `Sync`, and the responses are read back in order, which
+/// saves a round trip per query for bursts of small, independent queries:
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// let results = conn
+///     .pipeline()
+///     .push(sqlx::query("INSERT INTO logs (message) VALUES ($1)").bind("first"))
+///     .push(sqlx::query("INSERT INTO logs (message) VALUES ($1)").bind("second"))
+///     .push(sqlx::query("DELETE FROM logs WHERE created_at < now() - interval '1 day'"))
+///     .execute()
+///     .await?;
+///
+/// assert_eq!(results.len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Statements which are not yet cached on the connection are still prepared beforehand,
+/// one round trip each.
+///
+/// As the queries share a single `Sync`, they run in the same implicit transaction if no
+/// transaction is open: an error in one of them rolls back the ones before it, and the ones
+/// after it are not run. Each query must contain a single statement, as with any query
+/// with arguments.
+pub struct PgPipeline<'c, 'q> {
+    conn: &'c mut PgConnection,
+    queries: Vec<PipelineQuery<'q>>,
+}
+
+struct PipelineQuery<'q> {
+    sql: &'q str,
+    arguments: Result<Option<PgArguments>, BoxDynError>,
+    persistent: bool,
+    metadata: Option<Arc<PgStatementMetadata>>,
+}
+
+impl PgConnection {
+    /// Starts a [`PgPipeline`] of queries to send to the server with a single round trip.
+    pub fn pipeline<'q>(&mut self) -> PgPipeline<'_, 'q> {
+        PgPipeline {
+            conn: self,
+            queries: Vec::new(),
+        }
+    }
+}
+
+impl<'c, 'q> PgPipeline<'c, 'q> {
+    /// Adds a query to the end of the pipeline.
+    pub fn push<E>(mut self, mut query: E) -> Self
+    where
+        E: Execute<'q, Postgres>,
+    {
+        // False positive: https://github.com/rust-lang/rust-clippy/issues/12560
+        #[allow(clippy::map_clone)]
+        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+
+        self.queries.push(PipelineQuery {
+            sql: query.sql(),
+            arguments: query.take_arguments(),
+            persistent: query.persistent(),
+            metadata,
+        });
+
+        self
+    }
+
+    /// Runs the queries of the pipeline, returning the result of each one in order.
+    pub fn execute(self) -> BoxFuture<'c, Result<Vec<PgQueryResult>, Error>>
+    where
+        'q: 'c,
+    {
+        Box::pin(async move {
+            let results = self.run().await?;
+
+            Ok(results.into_iter().map(|(result, _)| result).collect())
+        })
+    }
+
+    /// Runs the queries of the pipeline, returning the rows of each one in order.
+    pub fn fetch_all(self) -> BoxFuture<'c, Result<Vec<Vec<PgRow>>, Error>>
+    where
+        'q: 'c,
+    {
+        Box::pin(async move {
+            let results = self.run().await?;
+
+            Ok(results.into_iter().map(|(_, rows)| rows).collect())
+        })
+    }
+
+    async fn run(self) -> Result<Vec<(PgQueryResult, Vec<PgRow>)>, Error> {
+        let PgPipeline { conn, queries } = self;
+
+        conn.wait_until_ready().await?;
+
+        // prepare every statement before writing the pipeline itself,
+        // as preparing a statement which is not cached needs its own round trip
+        let mut prepared = Vec::with_capacity(queries.len());
+        let mut one_off = Vec::new();
+
+        for query in queries {
+            let mut arguments = query.arguments.map_err(Error::Encode)?.unwrap_or_default();
+
+            let (statement, metadata) = conn
+                .get_or_prepare(
+                    query.sql,
+                    &arguments.types,
+                    query.persistent,
+                    query.metadata,
+                )
+                .await?;
+
+            arguments.apply_patches(conn, &metadata.parameters).await?;
+
+            one_off.extend(conn.one_off_statement(query.sql, statement));
+            prepared.push((query.sql, statement, metadata, arguments));
+        }
+
+        conn.wait_until_ready().await?;
+
+        for (_, statement, metadata, arguments) in &prepared {
+            // each Bind replaces the unnamed portal of the previous query
+            conn.stream.write(Bind {
+                portal: None,
+                statement: *statement,
+                formats: &[PgValueFormat::Binary],
+                num_params: arguments.types.len() as i16,
+                params: &arguments.buffer,
+                result_formats: metadata.bind_result_formats(),
+            });
+
+            conn.stream.write(message::Execute {
+                portal: None,
+                limit: 0,
+            });
+        }
+
+        conn.stream.write(message::Close::Portal(None));
+        conn.write_sync();
+
+        // closed after the [Sync], as the server skips every message up to it if a query fails
+        if !one_off.is_empty() {
+            for statement in one_off {
+                conn.stream.write(message::Close::Statement(statement));
+            }
+
+            conn.write_sync();
+        }
+
+        conn.stream.flush().await?;
+
+        let mut results = Vec::with_capacity(prepared.len());
+        let mut pending = prepared.iter();
+        let mut current = pending.next().map(|(sql, _, metadata, _)| {
+            (QueryLogger::new(sql, conn.log_settings.clone()), metadata)
+        });
+        let mut rows = Vec::new();
+
+        loop {
+            let message = conn.stream.recv().await?;
+
+            match message.format {
+                MessageFormat::BindComplete | MessageFormat::CloseComplete => {}
+
+                MessageFormat::DataRow => {
+                    let (logger, metadata) = current
+                        .as_mut()
+                        .ok_or_else(|| err_protocol!("pipeline: unexpected DataRow"))?;
+
+                    logger.increment_rows_returned();
+
+                    rows.push(PgRow {
+                        data: message.decode::<DataRow>()?,
+                        format: PgValueFormat::Binary,
+                        metadata: Arc::clone(metadata),
+                    });
+                }
+
+                // the execution of a query has finished, move on to the next one
+                MessageFormat::CommandComplete | MessageFormat::EmptyQueryResponse => {
+                    let (mut logger, _) = current.take().ok_or_else(|| {
+                        err_protocol!("pipeline: unexpected {:?}", message.format)
+                    })?;
+
+                    let rows_affected = if message.format == MessageFormat::CommandComplete {
+                        message.decode::<CommandComplete>()?.rows_affected()
+                    } else {
+                        0
+                    };
+
+                    logger.increase_rows_affected(rows_affected);
+
+                    results.push((PgQueryResult { rows_affected }, std::mem::take(&mut rows)));
+
+                    current = pending.next().map(|(sql, _, metadata, _)| {
+                        (QueryLogger::new(sql, conn.log_settings.clone()), metadata)
+                    });
+                }
+
+                MessageFormat::ReadyForQuery => {
+                    conn.handle_ready_for_query(message)?;
+                    break;
+                }
+
+                _ => {
+                    return Err(err_protocol!(
+                        "pipeline: unexpected message: {:?}",
+                        message.format
+                    ));
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -6,12 +6,15 @@ use crate::message::{
 use crate::PgConnectOptions;
 use hmac::{Hmac, Mac};
 use rand::Rng;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use stringprep::saslprep;
 
 use base64::prelude::{Engine as _, BASE64_STANDARD};
 
-const GS2_HEADER: &str = "n,,";
+// https://www.rfc-editor.org/rfc/rfc5802#section-7
+const GS2_HEADER_NO_BINDING: &str = "n,,";
+const GS2_HEADER_BINDING_UNSUPPORTED_BY_SERVER: &str = "y,,";
+const GS2_HEADER_TLS_SERVER_END_POINT: &str = "p=tls-server-end-point,,";
 const CHANNEL_ATTR: &str = "c";
 const USERNAME_ATTR: &str = "n";
 const CLIENT_PROOF_ATTR: &str = "p";
@@ -49,9 +52,29 @@ pub(crate) async fn authenticate(
         ));
     }
 
+    // bind the authentication to the TLS connection, if any, so that a man in the middle
+    // cannot relay it
+    let peer_certificate = stream.peer_certificate();
+
+    let (gs2_header, channel_binding_data) = match &peer_certificate {
+        Some(certificate) if has_sasl_plus => (
+            GS2_HEADER_TLS_SERVER_END_POINT,
+            tls_server_end_point(certificate),
+        ),
+
+        // the server would be able to tell a stripped `SCRAM-SHA-256-PLUS` from this header
+        Some(_) => (GS2_HEADER_BINDING_UNSUPPORTED_BY_SERVER, Vec::new()),
+
+        None => (GS2_HEADER_NO_BINDING, Vec::new()),
+    };
+
+    let plus = gs2_header == GS2_HEADER_TLS_SERVER_END_POINT;
+
     // channel-binding = "c=" base64
     let mut channel_binding = format!("{CHANNEL_ATTR}=");
-    BASE64_STANDARD.encode_string(GS2_HEADER, &mut channel_binding);
+    let mut cbind_input = gs2_header.as_bytes().to_vec();
+    cbind_input.extend(&channel_binding_data);
+    BASE64_STANDARD.encode_string(cbind_input, &mut channel_binding);
 
     // "n=" saslname ;; Usernames are prepared using SASLprep.
     let username = format!("{}={}", USERNAME_ATTR, options.username);
@@ -67,12 +90,12 @@ pub(crate) async fn authenticate(
     // client-first-message-bare = [reserved-mext ","] username "," nonce ["," extensions]
     let client_first_message_bare = format!("{username},{nonce}");
 
-    let client_first_message = format!("{GS2_HEADER}{client_first_message_bare}");
+    let client_first_message = format!("{gs2_header}{client_first_message_bare}");
 
     stream
         .send(SaslInitialResponse {
             response: &client_first_message,
-            plus: false,
+            plus,
         })
         .await?;
 
@@ -161,6 +184,69 @@ pub(crate) async fn authenticate(
     Ok(())
 }
 
+// the "tls-server-end-point" channel binding is the hash of the certificate of the server,
+// with the hash function of its signature algorithm, or SHA-256 in place of MD5 and SHA-1
+// https://www.rfc-editor.org/rfc/rfc5929#section-4.1
+fn tls_server_end_point(certificate: &[u8]) -> Vec<u8> {
+    // sha384WithRSAEncryption and ecdsa-with-SHA384
+    const SHA384: [&[u8]; 2] = [
+        &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c],
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03],
+    ];
+
+    // sha512WithRSAEncryption and ecdsa-with-SHA512
+    const SHA512: [&[u8]; 2] = [
+        &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d],
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04],
+    ];
+
+    match signature_algorithm(certificate) {
+        Some(oid) if SHA384.contains(&oid) => Sha384::digest(certificate).to_vec(),
+        Some(oid) if SHA512.contains(&oid) => Sha512::digest(certificate).to_vec(),
+        _ => Sha256::digest(certificate).to_vec(),
+    }
+}
+
+// the object identifier of the `signatureAlgorithm` of a DER-encoded certificate:
+// Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm, signatureValue }
+fn signature_algorithm(certificate: &[u8]) -> Option<&[u8]> {
+    let (certificate, _) = der_element(certificate, 0x30)?;
+    let (_tbs_certificate, rest) = der_element(certificate, 0x30)?;
+    let (algorithm, _) = der_element(rest, 0x30)?;
+    let (oid, _) = der_element(algorithm, 0x06)?;
+
+    Some(oid)
+}
+
+// splits the contents of a DER element with the given tag from the data after it
+fn der_element(data: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (&actual, data) = data.split_first()?;
+
+    if actual != tag {
+        return None;
+    }
+
+    let (&len, mut data) = data.split_first()?;
+
+    let len = if len < 0x80 {
+        usize::from(len)
+    } else {
+        // the long form, with the number of bytes of the length
+        let n = usize::from(len & 0x7f);
+
+        if n > 4 || data.len() < n {
+            return None;
+        }
+
+        let (len, rest) = data.split_at(n);
+        data = rest;
+
+        len.iter().fold(0, |len, &b| (len << 8) | usize::from(b))
+    };
+
+    (data.len() >= len).then(|| data.split_at(len))
+}
+
 // nonce is a sequence of random printable bytes
 fn gen_nonce() -> String {
     let mut rng = rand::thread_rng();
@@ -225,3 +311,28 @@ fn bench_sasl_hi(b: &mut test::Bencher) {
         );
     });
 }
+
+#[test]
+fn it_hashes_certificates_with_their_signature_algorithm() {
+    let sha384_with_rsa = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c];
+
+    // a certificate with an empty `tbsCertificate` and `signatureValue`
+    let mut certificate = vec![0x30, 0x12, 0x30, 0x00, 0x30, 0x0b, 0x06, 0x09];
+    certificate.extend(sha384_with_rsa);
+    certificate.extend([0x03, 0x01, 0x00]);
+
+    assert_eq!(
+        signature_algorithm(&certificate),
+        Some(&sha384_with_rsa[..])
+    );
+
+    assert_eq!(
+        tls_server_end_point(&certificate),
+        Sha384::digest(&certificate).to_vec()
+    );
+
+    assert_eq!(
+        tls_server_end_point(b"not a certificate"),
+        Sha256::digest(b"not a certificate").to_vec()
+    );
+}
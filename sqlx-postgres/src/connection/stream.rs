@@ -23,6 +23,8 @@ use crate::{PgConnectOptions, PgDatabaseError, PgSeverity};
 // in other words, `self` in any PgConnection method is a live connection to postgres that
 // is fully prepared to receive queries
 
+pub(crate) type NoticeHandler = Box<dyn FnMut(&PgDatabaseError) + Send + Sync>;
+
 pub struct PgStream {
     // A trait object is okay here as the buffering amortizes the overhead of both the dynamic
     // function call as well as the syscall.
@@ -36,6 +38,9 @@ pub struct PgStream {
     pub(crate) parameter_statuses: BTreeMap<String, String>,
 
     pub(crate) server_version_num: Option<u32>,
+
+    // called with every notice instead of logging it, see `PgConnection::set_notice_handler`
+    pub(crate) notice_handler: Option<NoticeHandler>,
 }
 
 impl PgStream {
@@ -52,6 +57,7 @@ impl PgStream {
             notifications: None,
             parameter_statuses: BTreeMap::default(),
             server_version_num: None,
+            notice_handler: None,
         })
     }
 
@@ -123,24 +129,24 @@ impl PgStream {
                     let ParameterStatus { name, value } = message.decode()?;
                     // TODO: handle `client_encoding`, `DateStyle` change
 
-                    match name.as_str() {
-                        "server_version" => {
-                            self.server_version_num = parse_server_version(&value);
-                        }
-                        _ => {
-                            self.parameter_statuses.insert(name, value);
-                        }
+                    if name == "server_version" {
+                        self.server_version_num = parse_server_version(&value);
                     }
 
+                    self.parameter_statuses.insert(name, value);
+
                     continue;
                 }
 
                 MessageFormat::NoticeResponse => {
-                    // do we need this to be more configurable?
-                    // if you are reading this comment and think so, open an issue
-
                     let notice: Notice = message.decode()?;
 
+                    if let Some(handler) = &mut self.notice_handler {
+                        handler(&PgDatabaseError(notice));
+
+                        continue;
+                    }
+
                     let (log_level, tracing_level) = match notice.severity() {
                         PgSeverity::Fatal | PgSeverity::Panic | PgSeverity::Error => {
                             (Level::Error, tracing::Level::ERROR)
@@ -162,6 +168,8 @@ impl PgStream {
                         sqlx_core::private_tracing_dynamic_event!(
                             target: "sqlx::postgres::notice",
                             tracing_level,
+                            severity = ?notice.severity(),
+                            code = notice.code(),
                             message = notice.message()
                         );
                     }
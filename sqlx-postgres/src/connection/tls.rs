@@ -21,6 +21,11 @@ async fn maybe_upgrade<S: Socket>(
     mut socket: S,
     options: &PgConnectOptions,
 ) -> Result<Box<dyn Socket>, Error> {
+    // like libpq, never use TLS over a unix domain socket; the server does not support it
+    if options.fetch_socket().is_some() {
+        return Ok(Box::new(socket));
+    }
+
     // https://www.postgresql.org/docs/12/libpq-ssl.html#LIBPQ-SSL-SSLMODE-STATEMENTS
     match options.ssl_mode {
         // FIXME: Implement ALLOW
@@ -47,10 +52,13 @@ async fn maybe_upgrade<S: Socket>(
         }
     }
 
-    let accept_invalid_certs = !matches!(
-        options.ssl_mode,
-        PgSslMode::VerifyCa | PgSslMode::VerifyFull
-    );
+    // like libpq, `require` verifies the certificate as `verify-ca` does if a root
+    // certificate was given
+    let accept_invalid_certs = match options.ssl_mode {
+        PgSslMode::VerifyCa | PgSslMode::VerifyFull => false,
+        PgSslMode::Require => options.ssl_root_cert.is_none(),
+        _ => true,
+    };
     let accept_invalid_hostnames = !matches!(options.ssl_mode, PgSslMode::VerifyFull);
 
     let config = TlsConfig {
@@ -1,8 +1,12 @@
 use std::borrow::Cow;
+use std::future::Future;
 use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
+use futures_util::Sink;
 
 use sqlx_core::bytes::{BufMut, Bytes};
 
@@ -130,7 +134,10 @@ impl PgPoolCopyExt for Pool<Postgres> {
 
 /// A connection in streaming `COPY FROM STDIN` mode.
 ///
-/// Created by [PgConnection::copy_in_raw] or [Pool::copy_out_raw].
+/// Created by [PgConnection::copy_in_raw] or [PgPoolCopyExt::copy_in_raw].
+///
+/// Chunks of data can also be sent through its [`Sink`] implementation, e.g. with
+/// `SinkExt::send_all`. Closing the sink only flushes the data sent so far.
 ///
 /// ### Note
 /// [PgCopyIn::finish] or [PgCopyIn::abort] *must* be called when finished or the connection
@@ -303,6 +310,65 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
     }
 }
 
+// once this much data is buffered, the sink waits for it to be written before accepting more
+const SINK_BUFFER_LIMIT: usize = 64 * 1024;
+
+impl<C, B> Sink<B> for PgCopyIn<C>
+where
+    C: DerefMut<Target = PgConnection> + Unpin,
+    B: Deref<Target = [u8]>,
+{
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let conn = self
+            .get_mut()
+            .conn
+            .as_deref_mut()
+            .expect("Sink: conn taken");
+
+        if conn.stream.write_buffer().get().len() < SINK_BUFFER_LIMIT {
+            return Poll::Ready(Ok(()));
+        }
+
+        poll_flush(conn, cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, data: B) -> Result<()> {
+        self.get_mut()
+            .conn
+            .as_deref_mut()
+            .expect("Sink: conn taken")
+            .stream
+            .write(CopyData(data));
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        poll_flush(
+            self.get_mut()
+                .conn
+                .as_deref_mut()
+                .expect("Sink: conn taken"),
+            cx,
+        )
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        <Self as Sink<B>>::poll_flush(self, cx)
+    }
+}
+
+fn poll_flush(conn: &mut PgConnection, cx: &mut Context<'_>) -> Poll<Result<()>> {
+    // the flush only consumes the buffer once it is written,
+    // so it is fine to start it over on every poll
+    let flush = conn.stream.flush();
+    futures_util::pin_mut!(flush);
+
+    flush.poll(cx).map_err(Error::from)
+}
+
 impl<C: DerefMut<Target = PgConnection>> Drop for PgCopyIn<C> {
     fn drop(&mut self) {
         if let Some(mut conn) = self.conn.take() {
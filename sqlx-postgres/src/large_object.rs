@@ -0,0 +1,396 @@
+use std::cmp;
+use std::io::{self, SeekFrom};
+use std::ops::DerefMut;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::future::BoxFuture;
+use futures_core::ready;
+use futures_io::{AsyncRead, AsyncWrite};
+
+use crate::error::{Error, Result};
+use crate::types::Oid;
+use crate::PgConnection;
+
+// `INV_READ` and `INV_WRITE` from `libpq/libpq-fs.h`
+const INV_READ: i32 = 0x40000;
+const INV_WRITE: i32 = 0x20000;
+
+// once this much data is buffered by `PgLargeObjectIo`, it is written before accepting more
+const WRITE_BUFFER_LIMIT: usize = 64 * 1024;
+
+/// The mode a large object is opened in with [`PgLargeObject::open()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgLargeObjectMode {
+    /// Open for reading only. Reads see the object as of the snapshot of the transaction.
+    Read,
+    /// Open for writing only.
+    Write,
+    /// Open for reading and writing. Reads see the committed and own uncommitted writes.
+    ReadWrite,
+}
+
+impl PgLargeObjectMode {
+    fn flags(self) -> i32 {
+        match self {
+            PgLargeObjectMode::Read => INV_READ,
+            PgLargeObjectMode::Write => INV_WRITE,
+            PgLargeObjectMode::ReadWrite => INV_READ | INV_WRITE,
+        }
+    }
+}
+
+/// An open [large object], for data stored in `pg_largeobject` and referenced by an `oid`
+/// rather than stored in a `bytea` column.
+///
+/// A large object is read and written in chunks with the server-side `loread()` and `lowrite()`
+/// functions, so it never has to be held in memory as a whole. See [`Self::into_io()`] to use it
+/// as an [`AsyncRead`] and [`AsyncWrite`].
+///
+/// A connection-like type is required. Allowed types include `PgConnection`,
+/// `PoolConnection<Postgres>` and `Transaction<Postgres>`, as well as mutable references to
+/// any of these.
+///
+/// ### Note: Transaction Required
+/// Large object descriptors are only valid until the end of the transaction they were opened
+/// in, so the connection should be in an explicit transaction for as long as the object is used.
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx_postgres::PgPool) -> sqlx_core::Result<()> {
+/// use sqlx_postgres::{PgLargeObject, PgLargeObjectMode};
+///
+/// let mut tx = pool.begin().await?;
+///
+/// let mut object = PgLargeObject::create(&mut *tx).await?;
+/// object.write(b"Hello, world!").await?;
+/// let oid = object.oid();
+/// object.close().await?;
+///
+/// let mut object = PgLargeObject::open(&mut *tx, oid, PgLargeObjectMode::Read).await?;
+/// assert_eq!(object.read(5).await?, b"Hello");
+/// object.close().await?;
+///
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [large object]: https://www.postgresql.org/docs/current/largeobjects.html
+#[derive(Debug)]
+pub struct PgLargeObject<C: DerefMut<Target = PgConnection>> {
+    conn: C,
+    oid: Oid,
+    fd: i32,
+}
+
+impl<C: DerefMut<Target = PgConnection>> PgLargeObject<C> {
+    /// Creates an empty large object with `lo_creat()` and opens it for reading and writing.
+    pub async fn create(mut conn: C) -> Result<Self> {
+        let oid: Oid = crate::query_scalar::query_scalar("SELECT lo_creat(-1)")
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Self::open(conn, oid, PgLargeObjectMode::ReadWrite).await
+    }
+
+    /// Opens an existing large object with `lo_open()`.
+    pub async fn open(mut conn: C, oid: Oid, mode: PgLargeObjectMode) -> Result<Self> {
+        let fd: i32 = crate::query_scalar::query_scalar("SELECT lo_open($1, $2)")
+            .bind(oid)
+            .bind(mode.flags())
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Ok(PgLargeObject { conn, oid, fd })
+    }
+
+    /// Deletes a large object with `lo_unlink()`.
+    pub async fn unlink(mut conn: C, oid: Oid) -> Result<()> {
+        crate::query::query("SELECT lo_unlink($1)")
+            .bind(oid)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the `oid` of the large object.
+    pub fn oid(&self) -> Oid {
+        self.oid
+    }
+
+    /// Reads up to `len` bytes from the current position with `loread()`.
+    ///
+    /// Fewer bytes are returned at the end of the object, and none past it.
+    pub async fn read(&mut self, len: usize) -> Result<Vec<u8>> {
+        let len = i32::try_from(len).unwrap_or(i32::MAX);
+
+        crate::query_scalar::query_scalar("SELECT loread($1, $2)")
+            .bind(self.fd)
+            .bind(len)
+            .fetch_one(&mut *self.conn)
+            .await
+    }
+
+    /// Writes `data` at the current position with `lowrite()`, returning the number of bytes
+    /// written.
+    pub async fn write(&mut self, data: &[u8]) -> Result<usize> {
+        let written: i32 = crate::query_scalar::query_scalar("SELECT lowrite($1, $2)")
+            .bind(self.fd)
+            .bind(data)
+            .fetch_one(&mut *self.conn)
+            .await?;
+
+        Ok(written as usize)
+    }
+
+    /// Moves the current position with `lo_lseek64()`, returning the new position.
+    pub async fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // `SEEK_SET`, `SEEK_CUR` and `SEEK_END`
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (
+                i64::try_from(offset).map_err(|e| Error::Encode(e.into()))?,
+                0,
+            ),
+            SeekFrom::Current(offset) => (offset, 1),
+            SeekFrom::End(offset) => (offset, 2),
+        };
+
+        let pos: i64 = crate::query_scalar::query_scalar("SELECT lo_lseek64($1, $2, $3)")
+            .bind(self.fd)
+            .bind(offset)
+            .bind(whence)
+            .fetch_one(&mut *self.conn)
+            .await?;
+
+        Ok(pos as u64)
+    }
+
+    /// Returns the current position with `lo_tell64()`.
+    pub async fn tell(&mut self) -> Result<u64> {
+        let pos: i64 = crate::query_scalar::query_scalar("SELECT lo_tell64($1)")
+            .bind(self.fd)
+            .fetch_one(&mut *self.conn)
+            .await?;
+
+        Ok(pos as u64)
+    }
+
+    /// Truncates or zero-extends the large object to `len` bytes with `lo_truncate64()`.
+    ///
+    /// The current position is not changed.
+    pub async fn truncate(&mut self, len: u64) -> Result<()> {
+        let len = i64::try_from(len).map_err(|e| Error::Encode(e.into()))?;
+
+        crate::query::query("SELECT lo_truncate64($1, $2)")
+            .bind(self.fd)
+            .bind(len)
+            .execute(&mut *self.conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Closes the descriptor with `lo_close()` and returns the connection.
+    ///
+    /// Descriptors are also closed at the end of the transaction.
+    pub async fn close(mut self) -> Result<C> {
+        crate::query::query("SELECT lo_close($1)")
+            .bind(self.fd)
+            .execute(&mut *self.conn)
+            .await?;
+
+        Ok(self.conn)
+    }
+
+    /// Returns an adapter implementing [`AsyncRead`] and [`AsyncWrite`] for the large object.
+    pub fn into_io<'c>(self) -> PgLargeObjectIo<'c, C>
+    where
+        C: Send + 'c,
+    {
+        PgLargeObjectIo {
+            state: State::Idle(self),
+            read_buf: Vec::new(),
+            write_buf: Vec::new(),
+        }
+    }
+}
+
+/// An adapter implementing [`AsyncRead`] and [`AsyncWrite`] for a [`PgLargeObject`], created
+/// with [`PgLargeObject::into_io()`].
+///
+/// Writes are buffered and written in chunks; they must be flushed, e.g. with
+/// `AsyncWriteExt::flush()` or `close()`, before calling [`Self::into_inner()`].
+/// Closing the adapter does not close the large object.
+pub struct PgLargeObjectIo<'c, C: DerefMut<Target = PgConnection>> {
+    state: State<'c, C>,
+    // data read but not yet returned by `poll_read()`
+    read_buf: Vec<u8>,
+    write_buf: Vec<u8>,
+}
+
+// an operation in progress; resolves to the large object and the data read, if any
+type OpFuture<'c, C> = BoxFuture<'c, (PgLargeObject<C>, Result<Option<Vec<u8>>>)>;
+
+enum State<'c, C: DerefMut<Target = PgConnection>> {
+    Idle(PgLargeObject<C>),
+    Busy(OpFuture<'c, C>),
+    // only while switching between the other states
+    Empty,
+}
+
+enum Op {
+    Read(usize),
+    Write(Vec<u8>),
+    Seek(i64),
+}
+
+// the fields are never pinned
+impl<C: DerefMut<Target = PgConnection>> Unpin for PgLargeObjectIo<'_, C> {}
+
+impl<'c, C: DerefMut<Target = PgConnection> + Send + 'c> PgLargeObjectIo<'c, C> {
+    /// Returns the large object.
+    ///
+    /// ### Panics
+    /// If an operation is in progress, i.e. a `poll_*()` call last returned `Poll::Pending`,
+    /// or if buffered writes were not flushed.
+    pub fn into_inner(self) -> PgLargeObject<C> {
+        assert!(
+            self.write_buf.is_empty(),
+            "PgLargeObjectIo::into_inner: writes not flushed"
+        );
+
+        match self.state {
+            State::Idle(object) => object,
+            _ => panic!("PgLargeObjectIo::into_inner: operation in progress"),
+        }
+    }
+
+    fn start(&mut self, op: Op) {
+        let State::Idle(mut object) = std::mem::replace(&mut self.state, State::Empty) else {
+            panic!("PgLargeObjectIo: operation in progress");
+        };
+
+        self.state = State::Busy(Box::pin(async move {
+            let result = match op {
+                Op::Read(len) => object.read(len).await.map(Some),
+                Op::Write(data) => object.write(&data).await.map(|_| None),
+                Op::Seek(offset) => object.seek(SeekFrom::Current(offset)).await.map(|_| None),
+            };
+
+            (object, result)
+        }));
+    }
+
+    // drives the operation in progress, if any, to completion;
+    // returns `true` if it was a read
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<bool>> {
+        let State::Busy(future) = &mut self.state else {
+            return Poll::Ready(Ok(false));
+        };
+
+        let (object, result) = ready!(future.as_mut().poll(cx));
+
+        self.state = State::Idle(object);
+
+        match result {
+            Ok(Some(data)) => {
+                self.read_buf = data;
+                Poll::Ready(Ok(true))
+            }
+            Ok(None) => Poll::Ready(Ok(false)),
+            Err(e) => Poll::Ready(Err(into_io_error(e))),
+        }
+    }
+
+    // writes the buffered data, first moving back over the data read but not returned
+    fn poll_flush_buffers(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            ready!(self.poll_idle(cx))?;
+
+            if !self.read_buf.is_empty() {
+                let offset = -(self.read_buf.len() as i64);
+                self.read_buf.clear();
+                self.start(Op::Seek(offset));
+            } else if !self.write_buf.is_empty() {
+                let data = std::mem::take(&mut self.write_buf);
+                self.start(Op::Write(data));
+            } else {
+                return Poll::Ready(Ok(()));
+            }
+        }
+    }
+}
+
+impl<'c, C: DerefMut<Target = PgConnection> + Send + 'c> AsyncRead for PgLargeObjectIo<'c, C> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        loop {
+            if !this.read_buf.is_empty() {
+                let len = cmp::min(buf.len(), this.read_buf.len());
+                buf[..len].copy_from_slice(&this.read_buf[..len]);
+                this.read_buf.drain(..len);
+
+                return Poll::Ready(Ok(len));
+            }
+
+            match this.state {
+                State::Busy(_) => {
+                    // `loread()` returns no data at the end of the object
+                    if ready!(this.poll_idle(cx))? && this.read_buf.is_empty() {
+                        return Poll::Ready(Ok(0));
+                    }
+                }
+
+                _ if !this.write_buf.is_empty() => {
+                    let data = std::mem::take(&mut this.write_buf);
+                    this.start(Op::Write(data));
+                }
+
+                _ => this.start(Op::Read(buf.len())),
+            }
+        }
+    }
+}
+
+impl<'c, C: DerefMut<Target = PgConnection> + Send + 'c> AsyncWrite for PgLargeObjectIo<'c, C> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if this.write_buf.len() >= WRITE_BUFFER_LIMIT || !this.read_buf.is_empty() {
+            ready!(this.poll_flush_buffers(cx))?;
+        }
+
+        this.write_buf.extend_from_slice(buf);
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_flush_buffers(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+fn into_io_error(e: Error) -> io::Error {
+    match e {
+        Error::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::Other, e),
+    }
+}
@@ -13,6 +13,7 @@ mod copy;
 mod database;
 mod error;
 mod io;
+mod large_object;
 mod listener;
 mod message;
 mod options;
@@ -34,6 +35,9 @@ mod value;
 #[doc(hidden)]
 pub mod any;
 
+#[cfg(feature = "replication")]
+pub mod replication;
+
 #[cfg(feature = "migrate")]
 mod migrate;
 
@@ -45,17 +49,18 @@ pub(crate) use sqlx_core::driver_prelude::*;
 pub use advisory_lock::{PgAdvisoryLock, PgAdvisoryLockGuard, PgAdvisoryLockKey};
 pub use arguments::{PgArgumentBuffer, PgArguments};
 pub use column::PgColumn;
-pub use connection::PgConnection;
+pub use connection::{PgCancelHandle, PgConnection, PgPipeline};
 pub use copy::{PgCopyIn, PgPoolCopyExt};
 pub use database::Postgres;
 pub use error::{PgDatabaseError, PgErrorPosition};
+pub use large_object::{PgLargeObject, PgLargeObjectIo, PgLargeObjectMode};
 pub use listener::{PgListener, PgNotification};
 pub use message::PgSeverity;
 pub use options::{PgConnectOptions, PgSslMode};
 pub use query_result::PgQueryResult;
 pub use row::PgRow;
 pub use statement::PgStatement;
-pub use transaction::PgTransactionManager;
+pub use transaction::{PgTransactionExt, PgTransactionManager};
 pub use type_info::{PgTypeInfo, PgTypeKind};
 pub use types::PgHasArrayType;
 pub use value::{PgValue, PgValueFormat, PgValueRef};
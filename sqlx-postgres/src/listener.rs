@@ -101,7 +101,11 @@ impl PgListener {
             .execute(&*format!(r#"LISTEN "{}""#, ident(channel)))
             .await?;
 
-        self.channels.push(channel.to_owned());
+        // `LISTEN` on a channel twice is a no-op, so it is recorded once to be undone
+        // by a single `UNLISTEN`
+        if !self.channels.iter().any(|c| c == channel) {
+            self.channels.push(channel.to_owned());
+        }
 
         Ok(())
     }
@@ -111,12 +115,25 @@ impl PgListener {
         &mut self,
         channels: impl IntoIterator<Item = &str>,
     ) -> Result<(), Error> {
-        let beg = self.channels.len();
-        self.channels.extend(channels.into_iter().map(|s| s.into()));
+        let mut new_channels: Vec<String> = Vec::new();
+
+        for channel in channels {
+            if !self
+                .channels
+                .iter()
+                .chain(&new_channels)
+                .any(|c| c == channel)
+            {
+                new_channels.push(channel.into());
+            }
+        }
 
-        let query = build_listen_all_query(&self.channels[beg..]);
+        let query = build_listen_all_query(&new_channels);
         self.connection().await?.execute(&*query).await?;
 
+        // only remember the channels once listening on them succeeded
+        self.channels.extend(new_channels);
+
         Ok(())
     }
 
@@ -157,9 +174,15 @@ impl PgListener {
             let mut connection = self.pool.acquire().await?;
             connection.stream.notifications = self.buffer_tx.take();
 
-            connection
+            if let Err(error) = connection
                 .execute(&*build_listen_all_query(&self.channels))
-                .await?;
+                .await
+            {
+                // keep the sender of the notification buffer for the next connection
+                self.buffer_tx = connection.stream.notifications.take();
+
+                return Err(error);
+            }
 
             self.connection = Some(connection);
         }
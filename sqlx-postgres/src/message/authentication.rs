@@ -36,6 +36,25 @@ pub enum Authentication {
     /// again using the 4-byte random salt.
     Md5Password(AuthenticationMd5Password),
 
+    /// The frontend must now initiate a GSSAPI negotiation, sending a [GssResponse]
+    /// with the first part of the GSSAPI data stream.
+    ///
+    /// If further messages are needed, the server will
+    /// respond with [Authentication::GssContinue].
+    Gss,
+
+    /// This message contains the response data from the previous step of GSSAPI
+    /// or SSPI negotiation.
+    ///
+    /// The frontend must respond with a [GssResponse] message if the negotiation
+    /// is not complete yet.
+    #[cfg(feature = "gssapi")]
+    GssContinue(Bytes),
+
+    /// The frontend must now initiate a SSPI negotiation, which is compatible with
+    /// GSSAPI when the server uses Kerberos.
+    Sspi,
+
     /// The frontend must now initiate a SASL negotiation,
     /// using one of the SASL mechanisms listed in the message.
     ///
@@ -74,6 +93,11 @@ impl Decode<'_> for Authentication {
                 Authentication::Md5Password(AuthenticationMd5Password { salt })
             }
 
+            7 => Authentication::Gss,
+            #[cfg(feature = "gssapi")]
+            8 => Authentication::GssContinue(buf),
+            9 => Authentication::Sspi,
+
             10 => Authentication::Sasl(AuthenticationSasl(buf)),
             11 => Authentication::SaslContinue(AuthenticationSaslContinue::decode(buf)?),
             12 => Authentication::SaslFinal(AuthenticationSaslFinal::decode(buf)?),
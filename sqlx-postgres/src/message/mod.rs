@@ -6,6 +6,7 @@ use crate::io::Decode;
 mod authentication;
 mod backend_key_data;
 mod bind;
+mod cancel_request;
 mod close;
 mod command_complete;
 mod copy;
@@ -13,6 +14,8 @@ mod data_row;
 mod describe;
 mod execute;
 mod flush;
+#[cfg(feature = "gssapi")]
+mod gss;
 mod notification;
 mod parameter_description;
 mod parameter_status;
@@ -31,6 +34,7 @@ mod terminate;
 pub use authentication::{Authentication, AuthenticationSasl};
 pub use backend_key_data::BackendKeyData;
 pub use bind::Bind;
+pub use cancel_request::CancelRequest;
 pub use close::Close;
 pub use command_complete::CommandComplete;
 pub use copy::{CopyData, CopyDone, CopyFail, CopyResponse};
@@ -39,6 +43,8 @@ pub use describe::Describe;
 pub use execute::Execute;
 #[allow(unused_imports)]
 pub use flush::Flush;
+#[cfg(feature = "gssapi")]
+pub use gss::GssResponse;
 pub use notification::Notification;
 pub use parameter_description::ParameterDescription;
 pub use parameter_status::ParameterStatus;
@@ -62,6 +68,7 @@ pub enum MessageFormat {
     BindComplete,
     CloseComplete,
     CommandComplete,
+    CopyBothResponse,
     CopyData,
     CopyDone,
     CopyInResponse,
@@ -109,6 +116,7 @@ impl MessageFormat {
             b'c' => MessageFormat::CopyDone,
             b'G' => MessageFormat::CopyInResponse,
             b'H' => MessageFormat::CopyOutResponse,
+            b'W' => MessageFormat::CopyBothResponse,
             b'D' => MessageFormat::DataRow,
             b'E' => MessageFormat::ErrorResponse,
             b'I' => MessageFormat::EmptyQueryResponse,
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::env::var;
-use std::fmt::{Display, Write};
+use std::fmt::Display;
 use std::path::{Path, PathBuf};
 
 pub use ssl_mode::PgSslMode;
@@ -33,6 +33,7 @@ mod ssl_mode;
 /// | `sslmode` | `prefer` | Determines whether or with what priority a secure SSL TCP/IP connection will be negotiated. See [`PgSslMode`]. |
 /// | `sslrootcert` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
+/// | `cursor-fetch-size` | `None` | Fetches the rows of queries with arguments from a portal in batches of this size; see [`PgConnectOptions::cursor_fetch_size`]. |
 /// | `host` | `None` | Path to the directory containing a PostgreSQL unix domain socket, which will be used instead of TCP if set. |
 /// | `hostaddr` | `None` | Same as `host`, but only accepts IP addresses. |
 /// | `application-name` | `None` | The name will be displayed in the pg_stat_activity view and included in CSV log entries. |
@@ -41,6 +42,7 @@ mod ssl_mode;
 /// | `port` | `5432` | Port number to connect to at the server host, or socket file name extension for Unix-domain connections. |
 /// | `dbname` | `None` | The database name. |
 /// | `options` | `None` | The runtime parameters to send to the server at connection start. |
+/// | `krbsrvname` | `postgres` | The Kerberos service name to use when authenticating with GSSAPI; requires the `gssapi` feature. |
 ///
 /// The URL scheme designator can be either `postgresql://` or `postgres://`.
 /// Each of the URL parts is optional.
@@ -98,10 +100,14 @@ pub struct PgConnectOptions {
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) cursor_fetch_size: Option<u32>,
     pub(crate) application_name: Option<String>,
     pub(crate) log_settings: LogSettings,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
     pub(crate) options: Option<String>,
+    pub(crate) krb_service_name: Option<String>,
+    // connects in logical replication mode, set by `PgLogicalReplication`
+    pub(crate) replication: bool,
 }
 
 impl Default for PgConnectOptions {
@@ -126,6 +132,7 @@ impl PgConnectOptions {
     ///  * `PGSSLKEY`
     ///  * `PGSSLMODE`
     ///  * `PGAPPNAME`
+    ///  * `PGKRBSRVNAME`
     ///
     /// # Example
     ///
@@ -164,21 +171,31 @@ impl PgConnectOptions {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
             statement_cache_capacity: 100,
+            cursor_fetch_size: None,
             application_name: var("PGAPPNAME").ok(),
             extra_float_digits: Some("2".into()),
             log_settings: Default::default(),
             options: var("PGOPTIONS").ok(),
+            krb_service_name: var("PGKRBSRVNAME").ok(),
+            replication: false,
         }
     }
 
     pub(crate) fn apply_pgpass(mut self) -> Self {
         if self.password.is_none() {
-            self.password = pgpass::load_password(
-                &self.host,
-                self.port,
-                &self.username,
-                self.database.as_deref(),
-            );
+            let socket = self.socket.as_ref().map(|path| path.to_string_lossy());
+            let host = socket.as_deref().unwrap_or(&self.host);
+
+            // like libpq, a connection through the default socket directory matches the
+            // `localhost` entries, any other socket directory is matched as the host name
+            let host = if DEFAULT_SOCKET_DIRS.contains(&host.trim_end_matches('/')) {
+                "localhost"
+            } else {
+                host
+            };
+
+            self.password =
+                pgpass::load_password(host, self.port, &self.username, self.database.as_deref());
         }
 
         self
@@ -224,6 +241,9 @@ impl PgConnectOptions {
     /// Sets a custom path to a directory containing a unix domain socket,
     /// switching the connection method from TCP to the corresponding socket.
     ///
+    /// The socket file is named `.s.PGSQL.<port>` inside that directory, as with libpq.
+    /// TLS is never used over a unix domain socket, whatever the [`PgSslMode`].
+    ///
     /// By default set to `None`.
     pub fn socket(mut self, path: impl AsRef<Path>) -> Self {
         self.socket = Some(path.as_ref().to_path_buf());
@@ -425,6 +445,28 @@ impl PgConnectOptions {
         self
     }
 
+    /// If set, the rows of a query with arguments are read from a named portal on the server,
+    /// fetching this many rows at a time with `Execute`, instead of the server sending all
+    /// of them at once.
+    ///
+    /// This bounds the memory used by the connection buffers when streaming a large result
+    /// set with [`fetch`][sqlx_core::executor::Executor::fetch], at the cost of a round trip
+    /// per batch. Queries without arguments use the simple query protocol and are unaffected.
+    ///
+    /// Not set by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .cursor_fetch_size(1000);
+    /// ```
+    pub fn cursor_fetch_size(mut self, rows: impl Into<Option<u32>>) -> Self {
+        self.cursor_fetch_size = rows.into().filter(|rows| *rows > 0);
+        self
+    }
+
     /// Sets the application name. Defaults to None
     ///
     /// # Example
@@ -439,6 +481,23 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets the Kerberos service name of the server, used to authenticate with GSSAPI when the
+    /// `gssapi` feature is enabled. Defaults to `postgres`.
+    ///
+    /// The server principal is `<service name>@<host>`, like with libpq.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .krb_service_name("pgsql");
+    /// ```
+    pub fn krb_service_name(mut self, service_name: &str) -> Self {
+        self.krb_service_name = Some(service_name.to_owned());
+        self
+    }
+
     /// Sets or removes the `extra_float_digits` connection option.
     ///
     /// This changes the default precision of floating-point values returned in text mode (when
@@ -514,7 +573,18 @@ impl PgConnectOptions {
                 options_str.push(' ');
             }
 
-            write!(options_str, "-c {k}={v}").expect("failed to write an option to the string");
+            // the server splits the options on spaces, which must be escaped in the values,
+            // e.g. `search_path=sqlx, public`
+            let option = format!("{k}={v}");
+            options_str.push_str("-c ");
+
+            for c in option.chars() {
+                if c == ' ' || c == '\\' {
+                    options_str.push('\\');
+                }
+
+                options_str.push(c);
+            }
         }
         self
     }
@@ -649,16 +719,17 @@ impl PgConnectOptions {
     }
 }
 
+const DEFAULT_SOCKET_DIRS: [&str; 3] = [
+    "/var/run/postgresql", // Debian
+    "/private/tmp",        // OSX (homebrew)
+    "/tmp",                // Default
+];
+
 fn default_host(port: u16) -> String {
     // try to check for the existence of a unix socket and uses that
     let socket = format!(".s.PGSQL.{port}");
-    let candidates = [
-        "/var/run/postgresql", // Debian
-        "/private/tmp",        // OSX (homebrew)
-        "/tmp",                // Default
-    ];
 
-    for candidate in &candidates {
+    for candidate in &DEFAULT_SOCKET_DIRS {
         if Path::new(candidate).join(&socket).exists() {
             return candidate.to_string();
         }
@@ -682,6 +753,11 @@ fn test_options_formatting() {
         options.options,
         Some("-c geqo=off -c statement_timeout=5min".to_string())
     );
+    let options = PgConnectOptions::new().options([("search_path", "sqlx, public")]);
+    assert_eq!(
+        options.options,
+        Some(r"-c search_path=sqlx,\ public".to_string())
+    );
     let options = PgConnectOptions::new();
     assert_eq!(options.options, None);
 }
@@ -62,6 +62,11 @@ impl PgConnectOptions {
                         options.statement_cache_capacity(value.parse().map_err(Error::config)?);
                 }
 
+                "cursor-fetch-size" => {
+                    options =
+                        options.cursor_fetch_size(value.parse::<u32>().map_err(Error::config)?);
+                }
+
                 "host" => {
                     if value.starts_with('/') {
                         options = options.socket(&*value);
@@ -85,6 +90,8 @@ impl PgConnectOptions {
 
                 "application_name" => options = options.application_name(&value),
 
+                "krbsrvname" => options = options.krb_service_name(&value),
+
                 "options" => {
                     if let Some(options) = options.options.as_mut() {
                         options.push(' ');
@@ -114,6 +121,10 @@ impl PgConnectOptions {
             Some(socket) => {
                 utf8_percent_encode(&socket.to_string_lossy(), NON_ALPHANUMERIC).to_string()
             }
+            // a socket directory given as the host, e.g. by `PGHOST` or the default host
+            None if self.host.starts_with('/') => {
+                utf8_percent_encode(&self.host, NON_ALPHANUMERIC).to_string()
+            }
             None => self.host.to_owned(),
         };
 
@@ -162,6 +173,16 @@ impl PgConnectOptions {
             &self.statement_cache_capacity.to_string(),
         );
 
+        if let Some(rows) = self.cursor_fetch_size {
+            url.query_pairs_mut()
+                .append_pair("cursor-fetch-size", &rows.to_string());
+        }
+
+        if let Some(service_name) = &self.krb_service_name {
+            url.query_pairs_mut()
+                .append_pair("krbsrvname", service_name);
+        }
+
         url
     }
 }
@@ -246,6 +267,27 @@ fn it_parses_application_name_correctly_from_parameter() {
     assert_eq!(Some("some_name"), opts.application_name.as_deref());
 }
 
+#[test]
+fn it_parses_krbsrvname_correctly_from_parameter() {
+    let url = "postgres:///?krbsrvname=pgsql";
+    let opts = PgConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(Some("pgsql"), opts.krb_service_name.as_deref());
+}
+
+#[test]
+fn it_parses_cursor_fetch_size_correctly_from_parameter() {
+    let url = "postgres:///?cursor-fetch-size=500";
+    let opts = PgConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(Some(500), opts.cursor_fetch_size);
+
+    let url = "postgres:///?cursor-fetch-size=0";
+    let opts = PgConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(None, opts.cursor_fetch_size);
+}
+
 #[test]
 fn it_parses_username_with_at_sign_correctly() {
     let url = "postgres://user@hostname:password@hostname:5432/database";
@@ -336,3 +378,18 @@ fn built_url_can_be_parsed() {
 
     assert!(parsed.is_ok());
 }
+
+#[test]
+fn built_url_can_be_parsed_when_host_is_a_socket_directory() {
+    let opts = PgConnectOptions::new_without_pgpass()
+        .host("/var/run/postgresql")
+        .port(5432)
+        .username("username");
+
+    let parsed = PgConnectOptions::from_str(&opts.build_url().to_string()).unwrap();
+
+    assert_eq!(
+        parsed.fetch_socket().as_deref(),
+        Some("/var/run/postgresql/.s.PGSQL.5432")
+    );
+}
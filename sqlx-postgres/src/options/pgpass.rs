@@ -132,7 +132,7 @@ fn load_password_from_line(
             matches_next_field(whole_line, &mut line, &port.to_string())?;
             matches_next_field(whole_line, &mut line, database.unwrap_or_default())?;
             matches_next_field(whole_line, &mut line, username)?;
-            Some(line.to_owned())
+            Some(unescape_password(line))
         }
     }
 }
@@ -155,6 +155,21 @@ fn matches_next_field(whole_line: &str, line: &mut &str, value: &str) -> Option<
     }
 }
 
+/// remove the backslash escapes from the password, the last field of a line
+fn unescape_password(line: &str) -> String {
+    let mut password = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => password.extend(chars.next()),
+            c => password.push(c),
+        }
+    }
+
+    password
+}
+
 /// extract the next value from a line in a pgpass file
 ///
 /// `line` will get updated to point behind the field and delimiter
@@ -273,6 +288,18 @@ mod tests {
             Some("baz".to_owned())
         );
 
+        // escaped password
+        assert_eq!(
+            load_password_from_line(
+                r"localhost:5432:bar:foo:b\:a\\z",
+                "localhost",
+                5432,
+                "foo",
+                Some("bar")
+            ),
+            Some(r"b:a\z".to_owned())
+        );
+
         // doesn't match
         assert_eq!(
             load_password_from_line(
@@ -0,0 +1,531 @@
+use std::sync::Arc;
+
+use sqlx_core::bytes::{Buf, Bytes};
+
+use crate::decode::Decode;
+use crate::error::{mismatched_types, Error};
+use crate::io::BufExt;
+use crate::type_info::PgTypeInfo;
+use crate::types::{Oid, Type};
+use crate::value::{PgValueFormat, PgValueRef, ValueRef};
+use crate::{HashMap, Postgres};
+
+use super::PgLsn;
+
+/// A message of the `pgoutput` logical decoding plugin, protocol version 1.
+///
+/// The changes of a transaction are streamed between its `Begin` and `Commit`, and are
+/// preceded by the definition of their relation the first time it is used in the stream.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum PgLogicalMessage {
+    Begin {
+        /// The position of the end of the transaction.
+        final_lsn: PgLsn,
+        /// The commit time, in microseconds since 2000-01-01 00:00:00 UTC.
+        commit_time: i64,
+        xid: u32,
+    },
+
+    Commit {
+        /// The position of the commit.
+        commit_lsn: PgLsn,
+        /// The position of the end of the transaction, to [acknowledge] once processed.
+        ///
+        /// [acknowledge]: super::PgReplicationStream::acknowledge
+        end_lsn: PgLsn,
+        /// The commit time, in microseconds since 2000-01-01 00:00:00 UTC.
+        commit_time: i64,
+    },
+
+    /// The origin of a transaction replicated from another server.
+    Origin { commit_lsn: PgLsn, name: String },
+
+    /// The definition of a relation, which is also kept to decode its changes.
+    Relation(Arc<PgRelation>),
+
+    /// The definition of a custom type used by a relation.
+    Type {
+        oid: Oid,
+        namespace: String,
+        name: String,
+    },
+
+    Insert {
+        relation: Arc<PgRelation>,
+        new: PgTuple,
+    },
+
+    Update {
+        relation: Arc<PgRelation>,
+        /// The previous values of the replica identity columns if they changed, or the whole
+        /// previous row if the replica identity is `FULL`.
+        old: Option<PgTuple>,
+        new: PgTuple,
+    },
+
+    Delete {
+        relation: Arc<PgRelation>,
+        /// The values of the replica identity columns, or the whole row if the replica
+        /// identity is `FULL`.
+        old: PgTuple,
+    },
+
+    Truncate {
+        relations: Vec<Arc<PgRelation>>,
+        cascade: bool,
+        restart_identity: bool,
+    },
+
+    /// A message this version does not decode.
+    Other { tag: u8, data: Bytes },
+}
+
+/// The definition of a relation in the replication stream.
+#[derive(Debug)]
+pub struct PgRelation {
+    oid: Oid,
+    namespace: String,
+    name: String,
+    replica_identity: u8,
+    columns: Vec<PgRelationColumn>,
+}
+
+impl PgRelation {
+    pub fn oid(&self) -> Oid {
+        self.oid
+    }
+
+    /// Returns the schema of the relation; `pg_catalog` is sent as an empty string.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the `relreplident` setting of the relation: `d` (default), `n` (nothing),
+    /// `f` (all columns) or `i` (index).
+    pub fn replica_identity(&self) -> u8 {
+        self.replica_identity
+    }
+
+    pub fn columns(&self) -> &[PgRelationColumn] {
+        &self.columns
+    }
+}
+
+/// A column of a [`PgRelation`].
+#[derive(Debug)]
+pub struct PgRelationColumn {
+    name: String,
+    type_info: PgTypeInfo,
+    type_modifier: i32,
+    key: bool,
+}
+
+impl PgRelationColumn {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_info(&self) -> &PgTypeInfo {
+        &self.type_info
+    }
+
+    pub fn type_modifier(&self) -> i32 {
+        self.type_modifier
+    }
+
+    /// Returns `true` if the column is part of the replica identity of the relation.
+    pub fn is_key(&self) -> bool {
+        self.key
+    }
+}
+
+/// A value of a [`PgTuple`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgTupleValue {
+    Null,
+    /// A TOASTed value that did not change, and is not sent again.
+    UnchangedToast,
+    Text(Bytes),
+    Binary(Bytes),
+}
+
+/// The values of a row of a [`PgRelation`].
+#[derive(Debug, Clone)]
+pub struct PgTuple {
+    relation: Arc<PgRelation>,
+    values: Vec<PgTupleValue>,
+}
+
+impl PgTuple {
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn values(&self) -> &[PgTupleValue] {
+        &self.values
+    }
+
+    /// Decodes the value at the given index, as it would be decoded from a text-format
+    /// result.
+    ///
+    /// Returns an error if the value is an unchanged TOASTed value.
+    pub fn try_get<'r, T>(&'r self, index: usize) -> Result<T, Error>
+    where
+        T: Decode<'r, Postgres> + Type<Postgres>,
+    {
+        let (value, column) = self
+            .values
+            .get(index)
+            .zip(self.relation.columns.get(index))
+            .ok_or(Error::ColumnIndexOutOfBounds {
+                index,
+                len: self.values.len(),
+            })?;
+
+        let (value, format) = match value {
+            PgTupleValue::Null => (None, PgValueFormat::Text),
+            PgTupleValue::Text(value) => (Some(&value[..]), PgValueFormat::Text),
+            PgTupleValue::Binary(value) => (Some(&value[..]), PgValueFormat::Binary),
+
+            PgTupleValue::UnchangedToast => {
+                return Err(Error::ColumnDecode {
+                    index: format!("{index:?}"),
+                    source: "unchanged TOASTed values are not sent in the replication stream"
+                        .into(),
+                });
+            }
+        };
+
+        let value = PgValueRef {
+            value,
+            row: None,
+            type_info: column.type_info.clone(),
+            format,
+        };
+
+        if !value.is_null() && !T::compatible(&column.type_info) {
+            return Err(Error::ColumnDecode {
+                index: format!("{index:?}"),
+                source: mismatched_types::<Postgres, T>(&column.type_info),
+            });
+        }
+
+        T::decode(value).map_err(|source| Error::ColumnDecode {
+            index: format!("{index:?}"),
+            source,
+        })
+    }
+}
+
+pub(super) fn decode_message(
+    mut buf: Bytes,
+    relations: &mut HashMap<Oid, Arc<PgRelation>>,
+) -> Result<PgLogicalMessage, Error> {
+    if buf.is_empty() {
+        return Err(err_protocol!("empty pgoutput message"));
+    }
+
+    let tag = buf.get_u8();
+
+    let message = match tag {
+        b'B' => {
+            ensure_len(&buf, 20)?;
+
+            PgLogicalMessage::Begin {
+                final_lsn: PgLsn(buf.get_u64()),
+                commit_time: buf.get_i64(),
+                xid: buf.get_u32(),
+            }
+        }
+
+        b'C' => {
+            ensure_len(&buf, 25)?;
+
+            // flags, currently unused
+            buf.advance(1);
+
+            PgLogicalMessage::Commit {
+                commit_lsn: PgLsn(buf.get_u64()),
+                end_lsn: PgLsn(buf.get_u64()),
+                commit_time: buf.get_i64(),
+            }
+        }
+
+        b'O' => {
+            ensure_len(&buf, 8)?;
+
+            PgLogicalMessage::Origin {
+                commit_lsn: PgLsn(buf.get_u64()),
+                name: buf.get_str_nul()?,
+            }
+        }
+
+        b'R' => {
+            ensure_len(&buf, 4)?;
+
+            let oid = Oid(buf.get_u32());
+            let namespace = buf.get_str_nul()?;
+            let name = buf.get_str_nul()?;
+
+            ensure_len(&buf, 3)?;
+
+            let replica_identity = buf.get_u8();
+            let num_columns = buf.get_u16();
+
+            let mut columns = Vec::with_capacity(num_columns as usize);
+
+            for _ in 0..num_columns {
+                ensure_len(&buf, 1)?;
+
+                let flags = buf.get_u8();
+                let name = buf.get_str_nul()?;
+
+                ensure_len(&buf, 8)?;
+
+                let type_oid = Oid(buf.get_u32());
+                let type_modifier = buf.get_i32();
+
+                columns.push(PgRelationColumn {
+                    name,
+                    type_info: PgTypeInfo::try_from_oid(type_oid)
+                        .unwrap_or(PgTypeInfo::with_oid(type_oid)),
+                    type_modifier,
+                    key: flags & 1 != 0,
+                });
+            }
+
+            let relation = Arc::new(PgRelation {
+                oid,
+                namespace,
+                name,
+                replica_identity,
+                columns,
+            });
+
+            relations.insert(oid, Arc::clone(&relation));
+
+            PgLogicalMessage::Relation(relation)
+        }
+
+        b'Y' => {
+            ensure_len(&buf, 4)?;
+
+            PgLogicalMessage::Type {
+                oid: Oid(buf.get_u32()),
+                namespace: buf.get_str_nul()?,
+                name: buf.get_str_nul()?,
+            }
+        }
+
+        b'I' => {
+            let relation = get_relation(&mut buf, relations)?;
+
+            expect_tuple_tag(&mut buf, b'N')?;
+            let new = decode_tuple(&mut buf, &relation)?;
+
+            PgLogicalMessage::Insert { relation, new }
+        }
+
+        b'U' => {
+            let relation = get_relation(&mut buf, relations)?;
+
+            ensure_len(&buf, 1)?;
+
+            let old = match buf[0] {
+                b'K' | b'O' => {
+                    buf.advance(1);
+                    Some(decode_tuple(&mut buf, &relation)?)
+                }
+
+                _ => None,
+            };
+
+            expect_tuple_tag(&mut buf, b'N')?;
+            let new = decode_tuple(&mut buf, &relation)?;
+
+            PgLogicalMessage::Update { relation, old, new }
+        }
+
+        b'D' => {
+            let relation = get_relation(&mut buf, relations)?;
+
+            ensure_len(&buf, 1)?;
+
+            match buf.get_u8() {
+                b'K' | b'O' => {}
+                tag => {
+                    return Err(err_protocol!(
+                        "expected old tuple in pgoutput Delete but found {:?}",
+                        tag as char
+                    ));
+                }
+            }
+
+            let old = decode_tuple(&mut buf, &relation)?;
+
+            PgLogicalMessage::Delete { relation, old }
+        }
+
+        b'T' => {
+            ensure_len(&buf, 5)?;
+
+            let num_relations = buf.get_u32();
+            let options = buf.get_u8();
+
+            let mut truncated = Vec::with_capacity(num_relations as usize);
+
+            for _ in 0..num_relations {
+                truncated.push(get_relation(&mut buf, relations)?);
+            }
+
+            PgLogicalMessage::Truncate {
+                relations: truncated,
+                cascade: options & 1 != 0,
+                restart_identity: options & 2 != 0,
+            }
+        }
+
+        tag => PgLogicalMessage::Other { tag, data: buf },
+    };
+
+    Ok(message)
+}
+
+fn ensure_len(buf: &Bytes, len: usize) -> Result<(), Error> {
+    if buf.remaining() < len {
+        return Err(err_protocol!(
+            "pgoutput message truncated, expected {} more bytes but found {}",
+            len,
+            buf.remaining()
+        ));
+    }
+
+    Ok(())
+}
+
+fn get_relation(
+    buf: &mut Bytes,
+    relations: &HashMap<Oid, Arc<PgRelation>>,
+) -> Result<Arc<PgRelation>, Error> {
+    ensure_len(buf, 4)?;
+
+    let oid = Oid(buf.get_u32());
+
+    relations
+        .get(&oid)
+        .cloned()
+        .ok_or_else(|| err_protocol!("pgoutput change for unknown relation {}", oid.0))
+}
+
+fn expect_tuple_tag(buf: &mut Bytes, expected: u8) -> Result<(), Error> {
+    ensure_len(buf, 1)?;
+
+    let tag = buf.get_u8();
+
+    if tag != expected {
+        return Err(err_protocol!(
+            "expected tuple {:?} in pgoutput message but found {:?}",
+            expected as char,
+            tag as char
+        ));
+    }
+
+    Ok(())
+}
+
+fn decode_tuple(buf: &mut Bytes, relation: &Arc<PgRelation>) -> Result<PgTuple, Error> {
+    ensure_len(buf, 2)?;
+
+    let num_values = buf.get_u16();
+    let mut values = Vec::with_capacity(num_values as usize);
+
+    for _ in 0..num_values {
+        ensure_len(buf, 1)?;
+
+        let value = match buf.get_u8() {
+            b'n' => PgTupleValue::Null,
+            b'u' => PgTupleValue::UnchangedToast,
+
+            kind @ (b't' | b'b') => {
+                ensure_len(buf, 4)?;
+                let len = buf.get_u32() as usize;
+
+                ensure_len(buf, len)?;
+                let data = buf.split_to(len);
+
+                if kind == b't' {
+                    PgTupleValue::Text(data)
+                } else {
+                    PgTupleValue::Binary(data)
+                }
+            }
+
+            kind => {
+                return Err(err_protocol!(
+                    "unknown pgoutput tuple value kind {:?}",
+                    kind as char
+                ));
+            }
+        };
+
+        values.push(value);
+    }
+
+    Ok(PgTuple {
+        relation: Arc::clone(relation),
+        values,
+    })
+}
+
+#[test]
+fn test_decode_insert() -> Result<(), Error> {
+    let mut relations = HashMap::new();
+
+    let relation = b"R\0\0\x40\x00public\0users\0d\0\x02\
+        \x01id\0\0\0\0\x17\xff\xff\xff\xff\
+        \x00name\0\0\0\0\x19\xff\xff\xff\xff";
+
+    let message = decode_message(Bytes::from_static(relation), &mut relations)?;
+
+    let PgLogicalMessage::Relation(relation) = message else {
+        panic!("expected Relation, got {message:?}");
+    };
+
+    assert_eq!(relation.oid(), Oid(0x4000));
+    assert_eq!(relation.namespace(), "public");
+    assert_eq!(relation.name(), "users");
+    assert_eq!(relation.columns().len(), 2);
+    assert!(relation.columns()[0].is_key());
+    assert_eq!(relation.columns()[1].name(), "name");
+
+    let insert = b"I\0\0\x40\x00N\0\x02t\0\0\0\x0242n";
+
+    let message = decode_message(Bytes::from_static(insert), &mut relations)?;
+
+    let PgLogicalMessage::Insert { relation, new } = message else {
+        panic!("expected Insert, got {message:?}");
+    };
+
+    assert_eq!(relation.name(), "users");
+    assert_eq!(new.try_get::<i32>(0)?, 42);
+    assert_eq!(new.try_get::<Option<String>>(1)?, None);
+    assert!(new.try_get::<String>(0).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_decode_change_for_unknown_relation() {
+    let delete = b"D\0\0\x40\x00K\0\x01n";
+
+    assert!(decode_message(Bytes::from_static(delete), &mut HashMap::new()).is_err());
+}
@@ -33,7 +33,12 @@ impl Row for PgRow {
         let value = self.data.get(index);
 
         Ok(PgValueRef {
-            format: self.format,
+            format: self
+                .metadata
+                .result_formats
+                .get(index)
+                .copied()
+                .unwrap_or(self.format),
             row: Some(&self.data.storage),
             type_info: column.type_info.clone(),
             value,
@@ -2,7 +2,7 @@ use super::{PgColumn, PgTypeInfo};
 use crate::column::ColumnIndex;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
-use crate::{PgArguments, Postgres};
+use crate::{PgArguments, PgValueFormat, Postgres};
 use std::borrow::Cow;
 use std::sync::Arc;
 
@@ -22,6 +22,19 @@ pub(crate) struct PgStatementMetadata {
     // See `sqlx-postgres/src/any.rs`
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
     pub(crate) parameters: Vec<PgTypeInfo>,
+    // the format of each column when the statement is executed, if they are not all binary
+    pub(crate) result_formats: Vec<PgValueFormat>,
+}
+
+impl PgStatementMetadata {
+    // the result formats to request in [Bind]; a single format applies to every column
+    pub(crate) fn bind_result_formats(&self) -> &[PgValueFormat] {
+        if self.result_formats.is_empty() {
+            &[PgValueFormat::Binary]
+        } else {
+            &self.result_formats
+        }
+    }
 }
 
 impl<'q> Statement<'q> for PgStatement<'q> {
@@ -15,14 +15,25 @@ impl TransactionManager for PgTransactionManager {
 
     fn begin(conn: &mut PgConnection) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
-            let rollback = Rollback::new(conn);
-            let query = begin_ansi_transaction_sql(rollback.conn.transaction_depth);
-            rollback.conn.queue_simple_query(&query);
-            rollback.conn.transaction_depth += 1;
-            rollback.conn.wait_until_ready().await?;
-            rollback.defuse();
+            let query = begin_ansi_transaction_sql(conn.transaction_depth);
 
-            Ok(())
+            begin(conn, &query).await
+        })
+    }
+
+    fn begin_with(conn: &mut PgConnection, options: TxOptions) -> BoxFuture<'_, Result<(), Error>> {
+        if options == TxOptions::default() {
+            return Self::begin(conn);
+        }
+
+        Box::pin(async move {
+            if conn.transaction_depth > 0 {
+                return Err(Error::Configuration(
+                    "transaction options cannot be set on a savepoint".into(),
+                ));
+            }
+
+            begin(conn, &begin_transaction_sql(options)).await
         })
     }
 
@@ -32,7 +43,9 @@ impl TransactionManager for PgTransactionManager {
                 conn.execute(&*commit_ansi_transaction_sql(conn.transaction_depth))
                     .await?;
 
-                conn.transaction_depth -= 1;
+                // ending the transaction itself already set the depth to 0
+                // when the connection became idle
+                conn.transaction_depth = conn.transaction_depth.saturating_sub(1);
             }
 
             Ok(())
@@ -45,7 +58,9 @@ impl TransactionManager for PgTransactionManager {
                 conn.execute(&*rollback_ansi_transaction_sql(conn.transaction_depth))
                     .await?;
 
-                conn.transaction_depth -= 1;
+                // ending the transaction itself already set the depth to 0
+                // when the connection became idle
+                conn.transaction_depth = conn.transaction_depth.saturating_sub(1);
             }
 
             Ok(())
@@ -61,6 +76,123 @@ impl TransactionManager for PgTransactionManager {
     }
 }
 
+async fn begin(conn: &mut PgConnection, query: &str) -> Result<(), Error> {
+    let rollback = Rollback::new(conn);
+    rollback.conn.queue_simple_query(query);
+    rollback.conn.transaction_depth += 1;
+    rollback.conn.wait_until_ready().await?;
+    rollback.defuse();
+
+    Ok(())
+}
+
+fn begin_transaction_sql(options: TxOptions) -> String {
+    let mut modes = Vec::new();
+
+    if let Some(level) = options.isolation_level() {
+        modes.push(format!("ISOLATION LEVEL {level}"));
+    }
+
+    if options.is_read_only() {
+        modes.push("READ ONLY".to_owned());
+    }
+
+    if options.is_deferrable() {
+        modes.push("DEFERRABLE".to_owned());
+    }
+
+    format!("BEGIN {}", modes.join(", "))
+}
+
+/// Implements two-phase commit for a [`Transaction`] on Postgres.
+///
+/// Requires `max_prepared_transactions` to be set to a non-zero value on the server.
+pub trait PgTransactionExt {
+    /// Prepares this transaction for two-phase commit with `PREPARE TRANSACTION`, under the
+    /// given global transaction identifier.
+    ///
+    /// The transaction is dissociated from the connection and kept by the server, even across
+    /// a restart, until [`PgConnection::commit_prepared()`] or
+    /// [`PgConnection::rollback_prepared()`] is called with the same identifier, from
+    /// any connection.
+    ///
+    /// Returns an error for a savepoint, as only a whole transaction can be prepared.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::postgres::PgTransactionExt;
+    /// use sqlx::{Acquire, Executor};
+    ///
+    /// let mut tx = conn.begin().await?;
+    /// tx.execute("UPDATE accounts SET balance = balance - 100 WHERE id = 1").await?;
+    /// tx.prepare_two_phase("transfer-42").await?;
+    ///
+    /// // once every participant has prepared its part
+    /// conn.commit_prepared("transfer-42").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn prepare_two_phase<'a>(self, gid: &'a str) -> BoxFuture<'a, Result<(), Error>>
+    where
+        Self: 'a;
+}
+
+impl<'c> PgTransactionExt for Transaction<'c, Postgres> {
+    fn prepare_two_phase<'a>(mut self, gid: &'a str) -> BoxFuture<'a, Result<(), Error>>
+    where
+        Self: 'a,
+    {
+        Box::pin(async move {
+            let conn: &mut PgConnection = &mut self;
+
+            if conn.transaction_depth != 1 {
+                return Err(Error::Configuration(
+                    "cannot prepare a savepoint for two-phase commit".into(),
+                ));
+            }
+
+            // the transaction is no longer associated with this connection, even if preparing
+            // it fails, as it is then rolled back
+            conn.transaction_depth = 0;
+
+            conn.execute(&*format!("PREPARE TRANSACTION {}", literal(gid)))
+                .await?;
+
+            // nothing is left to commit at depth 0, this only marks the transaction as closed
+            self.commit().await
+        })
+    }
+}
+
+impl PgConnection {
+    /// Commits a transaction prepared with [`PgTransactionExt::prepare_two_phase()`].
+    ///
+    /// This cannot be called from within a transaction.
+    pub async fn commit_prepared(&mut self, gid: &str) -> Result<(), Error> {
+        self.execute(&*format!("COMMIT PREPARED {}", literal(gid)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Rolls back a transaction prepared with [`PgTransactionExt::prepare_two_phase()`].
+    ///
+    /// This cannot be called from within a transaction.
+    pub async fn rollback_prepared(&mut self, gid: &str) -> Result<(), Error> {
+        self.execute(&*format!("ROLLBACK PREPARED {}", literal(gid)))
+            .await?;
+
+        Ok(())
+    }
+}
+
+// quotes a global transaction identifier as a string literal
+fn literal(gid: &str) -> String {
+    format!("'{}'", gid.replace('\'', "''"))
+}
+
 struct Rollback<'c> {
     conn: &'c mut PgConnection,
     defuse: bool,
@@ -85,3 +217,26 @@ impl<'c> Rollback<'c> {
         self.defuse = true;
     }
 }
+
+#[test]
+fn test_begin_transaction_sql() {
+    assert_eq!(
+        begin_transaction_sql(TxOptions::new().isolation(IsolationLevel::RepeatableRead)),
+        "BEGIN ISOLATION LEVEL REPEATABLE READ"
+    );
+    assert_eq!(
+        begin_transaction_sql(
+            TxOptions::new()
+                .isolation(IsolationLevel::Serializable)
+                .read_only()
+                .deferrable()
+        ),
+        "BEGIN ISOLATION LEVEL SERIALIZABLE, READ ONLY, DEFERRABLE"
+    );
+}
+
+#[test]
+fn test_literal() {
+    assert_eq!(literal("transfer-42"), "'transfer-42'");
+    assert_eq!(literal("it's"), "'it''s'");
+}
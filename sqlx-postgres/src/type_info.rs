@@ -147,6 +147,30 @@ pub enum PgType {
     Money,
     MoneyArray,
 
+    // https://www.postgresql.org/docs/current/datatype-oid.html
+    Regproc,
+    RegprocArray,
+    Regprocedure,
+    RegprocedureArray,
+    Regoper,
+    RegoperArray,
+    Regoperator,
+    RegoperatorArray,
+    Regclass,
+    RegclassArray,
+    Regtype,
+    RegtypeArray,
+    Regconfig,
+    RegconfigArray,
+    Regdictionary,
+    RegdictionaryArray,
+    Regnamespace,
+    RegnamespaceArray,
+    Regrole,
+    RegroleArray,
+    Regcollation,
+    RegcollationArray,
+
     // https://www.postgresql.org/docs/9.3/datatype-pseudo.html
     Void,
 
@@ -257,7 +281,7 @@ impl PgTypeInfo {
         .contains(self)
         {
             Some("ipnetwork")
-        } else if [PgTypeInfo::MACADDR].contains(self) {
+        } else if [PgTypeInfo::MACADDR, PgTypeInfo::MACADDR8].contains(self) {
             Some("mac_address")
         } else if [PgTypeInfo::NUMERIC, PgTypeInfo::NUMERIC_ARRAY].contains(self) {
             Some("bigdecimal")
@@ -384,6 +408,28 @@ impl PgType {
             775 => PgType::Macaddr8Array,
             790 => PgType::Money,
             791 => PgType::MoneyArray,
+            24 => PgType::Regproc,
+            1008 => PgType::RegprocArray,
+            2202 => PgType::Regprocedure,
+            2207 => PgType::RegprocedureArray,
+            2203 => PgType::Regoper,
+            2208 => PgType::RegoperArray,
+            2204 => PgType::Regoperator,
+            2209 => PgType::RegoperatorArray,
+            2205 => PgType::Regclass,
+            2210 => PgType::RegclassArray,
+            2206 => PgType::Regtype,
+            2211 => PgType::RegtypeArray,
+            3734 => PgType::Regconfig,
+            3735 => PgType::RegconfigArray,
+            3769 => PgType::Regdictionary,
+            3770 => PgType::RegdictionaryArray,
+            4089 => PgType::Regnamespace,
+            4090 => PgType::RegnamespaceArray,
+            4096 => PgType::Regrole,
+            4097 => PgType::RegroleArray,
+            4191 => PgType::Regcollation,
+            4192 => PgType::RegcollationArray,
             829 => PgType::Macaddr,
             869 => PgType::Inet,
             1000 => PgType::BoolArray,
@@ -492,6 +538,28 @@ impl PgType {
             PgType::Macaddr8Array => Oid(775),
             PgType::Money => Oid(790),
             PgType::MoneyArray => Oid(791),
+            PgType::Regproc => Oid(24),
+            PgType::RegprocArray => Oid(1008),
+            PgType::Regprocedure => Oid(2202),
+            PgType::RegprocedureArray => Oid(2207),
+            PgType::Regoper => Oid(2203),
+            PgType::RegoperArray => Oid(2208),
+            PgType::Regoperator => Oid(2204),
+            PgType::RegoperatorArray => Oid(2209),
+            PgType::Regclass => Oid(2205),
+            PgType::RegclassArray => Oid(2210),
+            PgType::Regtype => Oid(2206),
+            PgType::RegtypeArray => Oid(2211),
+            PgType::Regconfig => Oid(3734),
+            PgType::RegconfigArray => Oid(3735),
+            PgType::Regdictionary => Oid(3769),
+            PgType::RegdictionaryArray => Oid(3770),
+            PgType::Regnamespace => Oid(4089),
+            PgType::RegnamespaceArray => Oid(4090),
+            PgType::Regrole => Oid(4096),
+            PgType::RegroleArray => Oid(4097),
+            PgType::Regcollation => Oid(4191),
+            PgType::RegcollationArray => Oid(4192),
             PgType::Macaddr => Oid(829),
             PgType::Inet => Oid(869),
             PgType::BoolArray => Oid(1000),
@@ -661,6 +729,28 @@ impl PgType {
             PgType::JsonpathArray => "JSONPATH[]",
             PgType::Money => "MONEY",
             PgType::MoneyArray => "MONEY[]",
+            PgType::Regproc => "REGPROC",
+            PgType::RegprocArray => "REGPROC[]",
+            PgType::Regprocedure => "REGPROCEDURE",
+            PgType::RegprocedureArray => "REGPROCEDURE[]",
+            PgType::Regoper => "REGOPER",
+            PgType::RegoperArray => "REGOPER[]",
+            PgType::Regoperator => "REGOPERATOR",
+            PgType::RegoperatorArray => "REGOPERATOR[]",
+            PgType::Regclass => "REGCLASS",
+            PgType::RegclassArray => "REGCLASS[]",
+            PgType::Regtype => "REGTYPE",
+            PgType::RegtypeArray => "REGTYPE[]",
+            PgType::Regconfig => "REGCONFIG",
+            PgType::RegconfigArray => "REGCONFIG[]",
+            PgType::Regdictionary => "REGDICTIONARY",
+            PgType::RegdictionaryArray => "REGDICTIONARY[]",
+            PgType::Regnamespace => "REGNAMESPACE",
+            PgType::RegnamespaceArray => "REGNAMESPACE[]",
+            PgType::Regrole => "REGROLE",
+            PgType::RegroleArray => "REGROLE[]",
+            PgType::Regcollation => "REGCOLLATION",
+            PgType::RegcollationArray => "REGCOLLATION[]",
             PgType::Void => "VOID",
             PgType::Custom(ty) => &ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -762,6 +852,28 @@ impl PgType {
             PgType::JsonpathArray => "_jsonpath",
             PgType::Money => "money",
             PgType::MoneyArray => "_money",
+            PgType::Regproc => "regproc",
+            PgType::RegprocArray => "_regproc",
+            PgType::Regprocedure => "regprocedure",
+            PgType::RegprocedureArray => "_regprocedure",
+            PgType::Regoper => "regoper",
+            PgType::RegoperArray => "_regoper",
+            PgType::Regoperator => "regoperator",
+            PgType::RegoperatorArray => "_regoperator",
+            PgType::Regclass => "regclass",
+            PgType::RegclassArray => "_regclass",
+            PgType::Regtype => "regtype",
+            PgType::RegtypeArray => "_regtype",
+            PgType::Regconfig => "regconfig",
+            PgType::RegconfigArray => "_regconfig",
+            PgType::Regdictionary => "regdictionary",
+            PgType::RegdictionaryArray => "_regdictionary",
+            PgType::Regnamespace => "regnamespace",
+            PgType::RegnamespaceArray => "_regnamespace",
+            PgType::Regrole => "regrole",
+            PgType::RegroleArray => "_regrole",
+            PgType::Regcollation => "regcollation",
+            PgType::RegcollationArray => "_regcollation",
             PgType::Void => "void",
             PgType::Custom(ty) => &ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -863,6 +975,28 @@ impl PgType {
             PgType::JsonpathArray => &PgTypeKind::Array(PgTypeInfo(PgType::Jsonpath)),
             PgType::Money => &PgTypeKind::Simple,
             PgType::MoneyArray => &PgTypeKind::Array(PgTypeInfo(PgType::Money)),
+            PgType::Regproc => &PgTypeKind::Simple,
+            PgType::RegprocArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regproc)),
+            PgType::Regprocedure => &PgTypeKind::Simple,
+            PgType::RegprocedureArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regprocedure)),
+            PgType::Regoper => &PgTypeKind::Simple,
+            PgType::RegoperArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regoper)),
+            PgType::Regoperator => &PgTypeKind::Simple,
+            PgType::RegoperatorArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regoperator)),
+            PgType::Regclass => &PgTypeKind::Simple,
+            PgType::RegclassArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regclass)),
+            PgType::Regtype => &PgTypeKind::Simple,
+            PgType::RegtypeArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regtype)),
+            PgType::Regconfig => &PgTypeKind::Simple,
+            PgType::RegconfigArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regconfig)),
+            PgType::Regdictionary => &PgTypeKind::Simple,
+            PgType::RegdictionaryArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regdictionary)),
+            PgType::Regnamespace => &PgTypeKind::Simple,
+            PgType::RegnamespaceArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regnamespace)),
+            PgType::Regrole => &PgTypeKind::Simple,
+            PgType::RegroleArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regrole)),
+            PgType::Regcollation => &PgTypeKind::Simple,
+            PgType::RegcollationArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regcollation)),
 
             PgType::Void => &PgTypeKind::Pseudo,
 
@@ -931,6 +1065,28 @@ impl PgType {
             PgType::Macaddr8Array => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr8))),
             PgType::Money => None,
             PgType::MoneyArray => Some(Cow::Owned(PgTypeInfo(PgType::Money))),
+            PgType::Regproc => None,
+            PgType::RegprocArray => Some(Cow::Owned(PgTypeInfo(PgType::Regproc))),
+            PgType::Regprocedure => None,
+            PgType::RegprocedureArray => Some(Cow::Owned(PgTypeInfo(PgType::Regprocedure))),
+            PgType::Regoper => None,
+            PgType::RegoperArray => Some(Cow::Owned(PgTypeInfo(PgType::Regoper))),
+            PgType::Regoperator => None,
+            PgType::RegoperatorArray => Some(Cow::Owned(PgTypeInfo(PgType::Regoperator))),
+            PgType::Regclass => None,
+            PgType::RegclassArray => Some(Cow::Owned(PgTypeInfo(PgType::Regclass))),
+            PgType::Regtype => None,
+            PgType::RegtypeArray => Some(Cow::Owned(PgTypeInfo(PgType::Regtype))),
+            PgType::Regconfig => None,
+            PgType::RegconfigArray => Some(Cow::Owned(PgTypeInfo(PgType::Regconfig))),
+            PgType::Regdictionary => None,
+            PgType::RegdictionaryArray => Some(Cow::Owned(PgTypeInfo(PgType::Regdictionary))),
+            PgType::Regnamespace => None,
+            PgType::RegnamespaceArray => Some(Cow::Owned(PgTypeInfo(PgType::Regnamespace))),
+            PgType::Regrole => None,
+            PgType::RegroleArray => Some(Cow::Owned(PgTypeInfo(PgType::Regrole))),
+            PgType::Regcollation => None,
+            PgType::RegcollationArray => Some(Cow::Owned(PgTypeInfo(PgType::Regcollation))),
             PgType::Macaddr => None,
             PgType::MacaddrArray => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr))),
             PgType::Inet => None,
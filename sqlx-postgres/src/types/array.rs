@@ -270,6 +270,11 @@ where
 
                 // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L718
 
+                // the bounds are only printed if the lower bound is not "1", e.g. `[0:1]={1,2}`
+                if s.starts_with('[') {
+                    return Err(format!("encountered an array with dimension decoration {s:?}; only arrays starting at one are supported").into());
+                }
+
                 // trim the wrapping braces
                 let s = &s[1..(s.len() - 1)];
 
@@ -278,6 +283,11 @@ where
                     return Ok(Vec::new());
                 }
 
+                // the elements of a one-dimensional array are never braces unless quoted
+                if s.starts_with('{') {
+                    return Err("encountered a multi-dimensional array; only one-dimensional arrays are supported".into());
+                }
+
                 // NOTE: Nearly *all* types use ',' as the sequence delimiter. Yes, there is one
                 //       that does not. The BOX (not PostGIS) type uses ';' as a delimiter.
 
@@ -288,6 +298,7 @@ where
                 let mut done = false;
                 let mut in_quotes = false;
                 let mut in_escape = false;
+                let mut quoted = false;
                 let mut value = String::with_capacity(10);
                 let mut chars = s.chars();
                 let mut elements = Vec::with_capacity(4);
@@ -303,6 +314,7 @@ where
 
                                 '"' => {
                                     in_quotes = !in_quotes;
+                                    quoted = true;
                                 }
 
                                 '\\' => {
@@ -325,7 +337,8 @@ where
                         }
                     }
 
-                    let value_opt = if value == "NULL" {
+                    // a string with the text "NULL" is quoted
+                    let value_opt = if value == "NULL" && !quoted {
                         None
                     } else {
                         Some(value.as_bytes())
@@ -339,6 +352,7 @@ where
                     })?);
 
                     value.clear();
+                    quoted = false;
                 }
 
                 Ok(elements)
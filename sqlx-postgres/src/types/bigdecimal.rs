@@ -37,6 +37,10 @@ impl TryFrom<PgNumeric> for BigDecimal {
             PgNumeric::NotANumber => {
                 return Err("BigDecimal does not support NaN values".into());
             }
+
+            PgNumeric::Infinity | PgNumeric::NegativeInfinity => {
+                return Err("BigDecimal does not support infinite values".into());
+            }
         };
 
         if digits.is_empty() {
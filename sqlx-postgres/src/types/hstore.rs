@@ -1,5 +1,6 @@
 use std::{
-    collections::{btree_map, BTreeMap},
+    collections::{btree_map, BTreeMap, HashMap},
+    hash::BuildHasher,
     mem::size_of,
     ops::{Deref, DerefMut},
     str::from_utf8,
@@ -12,13 +13,14 @@ use crate::{
     encode::{Encode, IsNull},
     error::BoxDynError,
     types::Type,
-    PgArgumentBuffer, PgTypeInfo, PgValueRef, Postgres,
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
 };
 
 /// Key-value support (`hstore`) for Postgres.
 ///
-/// SQLx currently maps `hstore` to a `BTreeMap<String, Option<String>>` but this may be expanded in
-/// future to allow for user defined types.
+/// SQLx maps `hstore` to a `BTreeMap<String, Option<String>>` wrapped in this type, and also
+/// supports `HashMap<String, Option<String>>` directly. As an extension type, `hstore` has no
+/// fixed OID; it is resolved by name for each database.
 ///
 /// See [the Postgres manual, Appendix F, Section 18][PG.F.18]
 ///
@@ -138,59 +140,118 @@ impl Type<Postgres> for PgHstore {
     }
 }
 
+impl PgHasArrayType for PgHstore {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_hstore")
+    }
+}
+
 impl<'r> Decode<'r, Postgres> for PgHstore {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        let mut buf = <&[u8] as Decode<Postgres>>::decode(value)?;
-        let len = read_length(&mut buf)?;
+        decode_entries(value).map(PgHstore)
+    }
+}
 
-        if len < 0 {
-            Err(format!("hstore, invalid entry count: {len}"))?;
-        }
+impl Encode<'_, Postgres> for PgHstore {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        encode_entries(buf, self.0.len(), self.0.iter())
+    }
+}
 
-        let mut result = Self::default();
+// `hstore` is an extension type, so it is looked up by name and its OID is resolved by each
+// connection, as for `PgHstore`
+impl<S> Type<Postgres> for HashMap<String, Option<String>, S> {
+    fn type_info() -> PgTypeInfo {
+        PgHstore::type_info()
+    }
+}
 
-        while !buf.is_empty() {
-            let key_len = read_length(&mut buf)?;
-            let key = read_value(&mut buf, key_len)?.ok_or("hstore, key not found")?;
+impl<S> PgHasArrayType for HashMap<String, Option<String>, S> {
+    fn array_type_info() -> PgTypeInfo {
+        PgHstore::array_type_info()
+    }
+}
 
-            let value_len = read_length(&mut buf)?;
-            let value = read_value(&mut buf, value_len)?;
+impl<'r, S> Decode<'r, Postgres> for HashMap<String, Option<String>, S>
+where
+    S: BuildHasher + Default,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        decode_entries(value)
+    }
+}
 
-            result.insert(key, value);
-        }
+impl<S> Encode<'_, Postgres> for HashMap<String, Option<String>, S> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        encode_entries(buf, self.len(), self.iter())
+    }
+}
+
+fn decode_entries<C>(value: PgValueRef<'_>) -> Result<C, BoxDynError>
+where
+    C: Default + Extend<(String, Option<String>)>,
+{
+    let mut result = C::default();
+
+    if value.format() == PgValueFormat::Text {
+        parse_text(value.as_str()?, &mut result)?;
+        return Ok(result);
+    }
+
+    let mut buf = <&[u8] as Decode<Postgres>>::decode(value)?;
+    let len = read_length(&mut buf)?;
+
+    if len < 0 {
+        Err(format!("hstore, invalid entry count: {len}"))?;
+    }
+
+    while !buf.is_empty() {
+        let key_len = read_length(&mut buf)?;
+        let key = read_value(&mut buf, key_len)?.ok_or("hstore, key not found")?;
+
+        let value_len = read_length(&mut buf)?;
+        let value = read_value(&mut buf, value_len)?;
 
-        Ok(result)
+        result.extend([(key, value)]);
     }
+
+    Ok(result)
 }
 
-impl Encode<'_, Postgres> for PgHstore {
-    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
-        buf.extend_from_slice(&i32::to_be_bytes(self.0.len() as i32));
+fn encode_entries<'a>(
+    buf: &mut PgArgumentBuffer,
+    len: usize,
+    entries: impl Iterator<Item = (&'a String, &'a Option<String>)>,
+) -> Result<IsNull, BoxDynError> {
+    buf.extend_from_slice(&i32::to_be_bytes(len as i32));
 
-        for (key, val) in &self.0 {
-            let key_bytes = key.as_bytes();
+    for (key, val) in entries {
+        let key_bytes = key.as_bytes();
 
-            buf.extend_from_slice(&i32::to_be_bytes(key_bytes.len() as i32));
-            buf.extend_from_slice(key_bytes);
+        buf.extend_from_slice(&i32::to_be_bytes(key_bytes.len() as i32));
+        buf.extend_from_slice(key_bytes);
 
-            match val {
-                Some(val) => {
-                    let val_bytes = val.as_bytes();
+        match val {
+            Some(val) => {
+                let val_bytes = val.as_bytes();
 
-                    buf.extend_from_slice(&i32::to_be_bytes(val_bytes.len() as i32));
-                    buf.extend_from_slice(val_bytes);
-                }
-                None => {
-                    buf.extend_from_slice(&i32::to_be_bytes(-1));
-                }
+                buf.extend_from_slice(&i32::to_be_bytes(val_bytes.len() as i32));
+                buf.extend_from_slice(val_bytes);
+            }
+            None => {
+                buf.extend_from_slice(&i32::to_be_bytes(-1));
             }
         }
-
-        Ok(IsNull::No)
     }
+
+    Ok(IsNull::No)
 }
 
 fn read_length(buf: &mut &[u8]) -> Result<i32, BoxDynError> {
+    if buf.len() < size_of::<i32>() {
+        Err("hstore, reading length: unexpected end of input")?;
+    }
+
     let (bytes, rest) = buf.split_at(size_of::<i32>());
 
     *buf = rest;
@@ -204,8 +265,12 @@ fn read_length(buf: &mut &[u8]) -> Result<i32, BoxDynError> {
 
 fn read_value(buf: &mut &[u8], len: i32) -> Result<Option<String>, BoxDynError> {
     match len {
-        len if len <= 0 => Ok(None),
+        len if len < 0 => Ok(None),
         len => {
+            if buf.len() < len as usize {
+                Err("hstore, reading value: unexpected end of input")?;
+            }
+
             let (val, rest) = buf.split_at(len as usize);
 
             *buf = rest;
@@ -219,10 +284,74 @@ fn read_value(buf: &mut &[u8], len: i32) -> Result<Option<String>, BoxDynError>
     }
 }
 
+// parses the text output of `hstore`, e.g. `"a"=>"1", "b"=>NULL`
+fn parse_text<C>(mut s: &str, result: &mut C) -> Result<(), BoxDynError>
+where
+    C: Extend<(String, Option<String>)>,
+{
+    loop {
+        s = s.trim_start();
+
+        if s.is_empty() {
+            return Ok(());
+        }
+
+        let key = parse_quoted(&mut s)?;
+
+        s = s
+            .trim_start()
+            .strip_prefix("=>")
+            .ok_or_else(|| format!("hstore, expected `=>` after key {key:?}"))?
+            .trim_start();
+
+        let value = match s.strip_prefix("NULL") {
+            Some(rest) => {
+                s = rest;
+                None
+            }
+            None => Some(parse_quoted(&mut s)?),
+        };
+
+        result.extend([(key, value)]);
+
+        s = s.trim_start();
+
+        if let Some(rest) = s.strip_prefix(',') {
+            s = rest;
+        } else if !s.is_empty() {
+            Err(format!("hstore, expected `,` between entries: {s:?}"))?;
+        }
+    }
+}
+
+// parses a double-quoted string in which `\` escapes the next character
+fn parse_quoted(s: &mut &str) -> Result<String, BoxDynError> {
+    let input: &str = s;
+
+    let mut chars = input
+        .strip_prefix('"')
+        .ok_or_else(|| format!("hstore, expected a quoted string: {input:?}"))?
+        .chars();
+
+    let mut result = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                *s = chars.as_str();
+                return Ok(result);
+            }
+            '\\' => result.push(chars.next().ok_or("hstore, unterminated escape sequence")?),
+            c => result.push(c),
+        }
+    }
+
+    Err(format!("hstore, unterminated quoted string: {input:?}").into())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::PgValueFormat;
 
     const EMPTY: &str = "00000000";
 
@@ -257,6 +386,44 @@ mod test {
         assert_eq!(res_name_surname["age"], None);
     }
 
+    #[test]
+    fn hstore_deserialize_text_ok() {
+        let text = r#""a"=>"1", "b"=>NULL, "c \"d\""=>"e\\f", ""=>"""#;
+
+        let value = PgValueRef {
+            value: Some(text.as_bytes()),
+            row: None,
+            type_info: PgTypeInfo::with_name("hstore"),
+            format: PgValueFormat::Text,
+        };
+
+        let res = HashMap::<String, Option<String>>::decode(value).unwrap();
+
+        assert_eq!(res.len(), 4);
+        assert_eq!(res["a"], Some("1".to_string()));
+        assert_eq!(res["b"], None);
+        assert_eq!(res["c \"d\""], Some("e\\f".to_string()));
+        assert_eq!(res[""], Some("".to_string()));
+    }
+
+    #[test]
+    fn hstore_deserialize_empty_value() {
+        // "key" => ""
+        let buf = hex::decode("00000001000000036b657900000000").unwrap();
+
+        let value = PgValueRef {
+            value: Some(buf.as_slice()),
+            row: None,
+            type_info: PgTypeInfo::with_name("hstore"),
+            format: PgValueFormat::Binary,
+        };
+
+        assert_eq!(
+            PgHstore::decode(value).unwrap()["key"],
+            Some("".to_string())
+        );
+    }
+
     #[test]
     #[should_panic(expected = "hstore, invalid entry count: -5")]
     fn hstore_deserialize_buffer_length_error() {
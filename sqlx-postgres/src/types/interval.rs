@@ -17,6 +17,21 @@ pub struct PgInterval {
     pub microseconds: i64,
 }
 
+impl PgInterval {
+    // a month or a day is not always as long, e.g. when daylight saving time changes
+    fn exact_microseconds(&self) -> Result<i64, BoxDynError> {
+        if self.months != 0 || self.days != 0 {
+            return Err(
+                "a PostgreSQL `INTERVAL` with months or days has no exact duration; \
+                 use `justify_hours()` or extract the fields of the interval instead"
+                    .into(),
+            );
+        }
+
+        Ok(self.microseconds)
+    }
+}
+
 impl Type<Postgres> for PgInterval {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INTERVAL
@@ -112,6 +127,22 @@ impl TryFrom<std::time::Duration> for PgInterval {
     }
 }
 
+impl TryFrom<PgInterval> for std::time::Duration {
+    type Error = BoxDynError;
+
+    /// Convert a `PgInterval` to a `std::time::Duration`.
+    ///
+    /// This returns an error if the interval has months or days, which do not have a fixed length,
+    /// or if it is negative.
+    fn try_from(value: PgInterval) -> Result<Self, BoxDynError> {
+        let microseconds = value.exact_microseconds()?;
+
+        Ok(Self::from_micros(microseconds.try_into().map_err(
+            |_| "a negative PostgreSQL `INTERVAL` cannot be a `std::time::Duration`",
+        )?))
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl Type<Postgres> for chrono::Duration {
     fn type_info() -> PgTypeInfo {
@@ -174,6 +205,18 @@ impl TryFrom<chrono::Duration> for PgInterval {
     }
 }
 
+#[cfg(feature = "chrono")]
+impl TryFrom<PgInterval> for chrono::Duration {
+    type Error = BoxDynError;
+
+    /// Convert a `PgInterval` to a `chrono::Duration`.
+    ///
+    /// This returns an error if the interval has months or days, which do not have a fixed length.
+    fn try_from(value: PgInterval) -> Result<Self, BoxDynError> {
+        Ok(Self::microseconds(value.exact_microseconds()?))
+    }
+}
+
 #[cfg(feature = "time")]
 impl Type<Postgres> for time::Duration {
     fn type_info() -> PgTypeInfo {
@@ -221,6 +264,18 @@ impl TryFrom<time::Duration> for PgInterval {
     }
 }
 
+#[cfg(feature = "time")]
+impl TryFrom<PgInterval> for time::Duration {
+    type Error = BoxDynError;
+
+    /// Convert a `PgInterval` to a `time::Duration`.
+    ///
+    /// This returns an error if the interval has months or days, which do not have a fixed length.
+    fn try_from(value: PgInterval) -> Result<Self, BoxDynError> {
+        Ok(Self::microseconds(value.exact_microseconds()?))
+    }
+}
+
 #[test]
 fn test_encode_interval() {
     let mut buf = PgArgumentBuffer::default();
@@ -323,6 +378,24 @@ fn test_pginterval_std() {
         &interval
     );
 
+    // Case for the conversion back
+    assert_eq!(
+        std::time::Duration::try_from(interval).unwrap(),
+        std::time::Duration::from_micros(27_000)
+    );
+    assert!(std::time::Duration::try_from(PgInterval {
+        days: 1,
+        months: 0,
+        microseconds: 0,
+    })
+    .is_err());
+    assert!(std::time::Duration::try_from(PgInterval {
+        days: 0,
+        months: 0,
+        microseconds: -1,
+    })
+    .is_err());
+
     // Case when precision loss occurs
     assert!(PgInterval::try_from(std::time::Duration::from_nanos(27_000_001)).is_err());
 
@@ -355,6 +428,18 @@ fn test_pginterval_chrono() {
         &interval
     );
 
+    // Case for the conversion back
+    assert_eq!(
+        chrono::Duration::try_from(interval).unwrap(),
+        chrono::Duration::microseconds(-27_000)
+    );
+    assert!(chrono::Duration::try_from(PgInterval {
+        days: 0,
+        months: 1,
+        microseconds: 0,
+    })
+    .is_err());
+
     // Case when precision loss occurs
     assert!(PgInterval::try_from(chrono::Duration::nanoseconds(27_000_001)).is_err());
     assert!(PgInterval::try_from(chrono::Duration::nanoseconds(-27_000_001)).is_err());
@@ -389,6 +474,12 @@ fn test_pginterval_time() {
         &interval
     );
 
+    // Case for the conversion back
+    assert_eq!(
+        time::Duration::try_from(interval).unwrap(),
+        time::Duration::microseconds(-27_000)
+    );
+
     // Case when precision loss occurs
     assert!(PgInterval::try_from(time::Duration::nanoseconds(27_000_001)).is_err());
     assert!(PgInterval::try_from(time::Duration::nanoseconds(-27_000_001)).is_err());
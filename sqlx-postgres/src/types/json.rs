@@ -85,15 +85,36 @@ where
         let mut buf = value.as_bytes()?;
 
         if value.format() == PgValueFormat::Binary && value.type_info == PgTypeInfo::JSONB {
-            assert_eq!(
-                buf[0], 1,
-                "unsupported JSONB format version {}; please open an issue",
-                buf[0]
-            );
-
-            buf = &buf[1..];
+            buf = match buf.split_first() {
+                Some((&1, json)) => json,
+
+                Some((version, _)) => {
+                    return Err(format!(
+                        "unsupported JSONB format version {version}; please open an issue"
+                    )
+                    .into())
+                }
+
+                None => return Err("empty JSONB value, expected a format version".into()),
+            };
         }
 
         serde_json::from_slice(buf).map(Json).map_err(Into::into)
     }
 }
+
+#[test]
+fn test_decode_jsonb_version() {
+    fn decode(bytes: &[u8]) -> Result<Json<JsonValue>, BoxDynError> {
+        Decode::<Postgres>::decode(PgValueRef {
+            value: Some(bytes),
+            row: None,
+            type_info: PgTypeInfo::JSONB,
+            format: PgValueFormat::Binary,
+        })
+    }
+
+    assert_eq!(decode(b"\x01[1]").unwrap().0, serde_json::json!([1]));
+    assert!(decode(b"\x02[1]").is_err());
+    assert!(decode(b"").is_err());
+}
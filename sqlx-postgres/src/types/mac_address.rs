@@ -11,8 +11,9 @@ impl Type<Postgres> for MacAddress {
         PgTypeInfo::MACADDR
     }
 
+    // a `macaddr8` is only decoded if it holds an EUI-48 address
     fn compatible(ty: &PgTypeInfo) -> bool {
-        *ty == PgTypeInfo::MACADDR
+        *ty == PgTypeInfo::MACADDR || *ty == PgTypeInfo::MACADDR8
     }
 }
 
@@ -36,16 +37,44 @@ impl Encode<'_, Postgres> for MacAddress {
 impl Decode<'_, Postgres> for MacAddress {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         let bytes = match value.format() {
-            PgValueFormat::Binary => value.as_bytes()?,
+            PgValueFormat::Binary => value.as_bytes()?.to_vec(),
+            PgValueFormat::Text if value.type_info == PgTypeInfo::MACADDR8 => {
+                parse_macaddr8(value.as_str()?)?
+            }
             PgValueFormat::Text => {
                 return Ok(value.as_str()?.parse()?);
             }
         };
 
-        if bytes.len() == 6 {
-            return Ok(MacAddress::new(bytes.try_into().unwrap()));
-        }
+        match bytes[..] {
+            [a, b, c, d, e, f] => Ok(MacAddress::new([a, b, c, d, e, f])),
+
+            // `macaddr8` stores an EUI-48 address with `FF:FE` in the middle
+            [a, b, c, 0xff, 0xfe, f, g, h] => Ok(MacAddress::new([a, b, c, f, g, h])),
 
-        Err("invalid data received when expecting an MACADDR".into())
+            [_, _, _, _, _, _, _, _] => {
+                Err("the MACADDR8 is not an EUI-48 address and does not fit in a MacAddress".into())
+            }
+
+            _ => Err("invalid data received when expecting an MACADDR".into()),
+        }
     }
 }
+
+// the text output of `macaddr8` is always of the form `08:00:2b:01:02:03:04:05`
+fn parse_macaddr8(s: &str) -> Result<Vec<u8>, BoxDynError> {
+    s.split(':')
+        .map(|byte| u8::from_str_radix(byte, 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| format!("invalid MACADDR8 {s:?}").into())
+}
+
+#[test]
+fn test_parse_macaddr8() {
+    assert_eq!(
+        parse_macaddr8("08:00:2b:ff:fe:01:02:03").unwrap(),
+        [0x08, 0x00, 0x2b, 0xff, 0xfe, 0x01, 0x02, 0x03]
+    );
+
+    assert!(parse_macaddr8("08:00:2b:ff:fe:01:02:zz").is_err());
+}
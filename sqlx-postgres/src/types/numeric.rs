@@ -9,6 +9,12 @@ pub(crate) enum PgNumeric {
     /// Equivalent to the `'NaN'` value in Postgres. The result of, e.g. `1 / 0`.
     NotANumber,
 
+    /// Equivalent to the `'Infinity'` value in Postgres 14 and later.
+    Infinity,
+
+    /// Equivalent to the `'-Infinity'` value in Postgres 14 and later.
+    NegativeInfinity,
+
     /// A populated `NUMERIC` value.
     ///
     /// A description of these fields can be found here (although the type being described is the
@@ -52,6 +58,8 @@ pub(crate) enum PgNumeric {
 const SIGN_POS: u16 = 0x0000;
 const SIGN_NEG: u16 = 0x4000;
 const SIGN_NAN: u16 = 0xC000; // overflows i16 (C equivalent truncates from integer literal)
+const SIGN_PINF: u16 = 0xD000;
+const SIGN_NINF: u16 = 0xF000;
 
 /// Possible sign values for [PgNumeric].
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -67,7 +75,9 @@ impl PgNumericSign {
             SIGN_POS => Ok(PgNumericSign::Positive),
             SIGN_NEG => Ok(PgNumericSign::Negative),
 
-            SIGN_NAN => unreachable!("sign value for NaN passed to PgNumericSign"),
+            SIGN_NAN | SIGN_PINF | SIGN_NINF => {
+                unreachable!("sign value for a special value passed to PgNumericSign")
+            }
 
             _ => Err(format!("invalid value for PgNumericSign: {val:#04X}").into()),
         }
@@ -84,6 +94,10 @@ impl PgNumeric {
 
         if sign == SIGN_NAN {
             Ok(PgNumeric::NotANumber)
+        } else if sign == SIGN_PINF {
+            Ok(PgNumeric::Infinity)
+        } else if sign == SIGN_NINF {
+            Ok(PgNumeric::NegativeInfinity)
         } else {
             let digits: Vec<_> = (0..num_digits).map(|_| buf.get_i16()).collect::<_>();
 
@@ -125,12 +139,33 @@ impl PgNumeric {
                 }
             }
 
-            PgNumeric::NotANumber => {
+            PgNumeric::NotANumber | PgNumeric::Infinity | PgNumeric::NegativeInfinity => {
+                let sign = match self {
+                    PgNumeric::Infinity => SIGN_PINF,
+                    PgNumeric::NegativeInfinity => SIGN_NINF,
+                    _ => SIGN_NAN,
+                };
+
                 buf.extend(&0_i16.to_be_bytes());
                 buf.extend(&0_i16.to_be_bytes());
-                buf.extend(&SIGN_NAN.to_be_bytes());
+                buf.extend(&sign.to_be_bytes());
                 buf.extend(&0_i16.to_be_bytes());
             }
         }
     }
 }
+
+#[test]
+fn test_decode_special_values() {
+    let decode = |sign: u16| {
+        let mut buf = vec![0, 0, 0, 0];
+        buf.extend(sign.to_be_bytes());
+        buf.extend([0, 0]);
+
+        PgNumeric::decode(&buf).unwrap()
+    };
+
+    assert_eq!(decode(SIGN_NAN), PgNumeric::NotANumber);
+    assert_eq!(decode(SIGN_PINF), PgNumeric::Infinity);
+    assert_eq!(decode(SIGN_NINF), PgNumeric::NegativeInfinity);
+}
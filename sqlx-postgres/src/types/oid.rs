@@ -4,6 +4,7 @@ use serde::{de::Deserializer, ser::Serializer, Deserialize, Serialize};
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+use crate::type_info::PgType;
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 
@@ -27,12 +28,44 @@ impl Type<Postgres> for Oid {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::OID
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::OID || is_oid_type(ty)
+    }
 }
 
 impl PgHasArrayType for Oid {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::OID_ARRAY
     }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::OID_ARRAY
+            || ty
+                .0
+                .try_array_element()
+                .map_or(false, |element| is_oid_type(&element))
+    }
+}
+
+// the object identifier types, e.g. `regclass`, are aliases of `oid` which are sent as an `oid`
+// in binary, but as the name of the object in text
+fn is_oid_type(ty: &PgTypeInfo) -> bool {
+    matches!(
+        ty.0,
+        PgType::Oid
+            | PgType::Regproc
+            | PgType::Regprocedure
+            | PgType::Regoper
+            | PgType::Regoperator
+            | PgType::Regclass
+            | PgType::Regtype
+            | PgType::Regconfig
+            | PgType::Regdictionary
+            | PgType::Regnamespace
+            | PgType::Regrole
+            | PgType::Regcollation
+    )
 }
 
 impl Encode<'_, Postgres> for Oid {
@@ -47,7 +80,17 @@ impl Decode<'_, Postgres> for Oid {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         Ok(Self(match value.format() {
             PgValueFormat::Binary => BigEndian::read_u32(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
+            PgValueFormat::Text => {
+                let text = value.as_str()?;
+
+                text.parse().map_err(|_| {
+                    format!(
+                        "expected an object identifier but got {text:?}; the text format of {} \
+                         is a name, cast it to `oid` or use a prepared query",
+                        value.type_info
+                    )
+                })?
+            }
         }))
     }
 }
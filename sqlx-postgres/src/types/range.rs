@@ -381,6 +381,14 @@ where
 
                 let s = value.as_str()?;
 
+                // an empty range is decoded as in the binary format
+                if s == "empty" {
+                    return Ok(PgRange {
+                        start: Bound::Unbounded,
+                        end: Bound::Unbounded,
+                    });
+                }
+
                 // remember the bounds
                 let sb = s.as_bytes();
                 let lower = sb[0] as char;
@@ -393,7 +401,7 @@ where
 
                 let mut element = String::new();
                 let mut done = false;
-                let mut quoted = false;
+                let mut quoted;
                 let mut in_quotes = false;
                 let mut in_escape = false;
                 let mut prev_ch = '\0';
@@ -401,6 +409,7 @@ where
 
                 while !done {
                     element.clear();
+                    quoted = false;
 
                     loop {
                         match chars.next() {
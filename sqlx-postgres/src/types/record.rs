@@ -41,13 +41,14 @@ impl<'a> PgRecordEncoder<'a> {
     {
         let ty = value.produces().unwrap_or_else(T::type_info);
 
-        if let PgType::DeclareWithName(name) = ty.0 {
+        match ty.0 {
             // push a hole for this type ID
             // to be filled in on query execution
-            self.buf.patch_type_by_name(&name);
-        } else {
+            PgType::DeclareWithName(name) => self.buf.patch_type_by_name(&name),
+            PgType::DeclareArrayOf(array) => self.buf.patch_array_type(array),
+
             // write type id
-            self.buf.extend(&ty.0.oid().0.to_be_bytes());
+            ty => self.buf.extend(&ty.oid().0.to_be_bytes()),
         }
 
         self.buf.encode(value)?;
@@ -141,38 +142,40 @@ impl<'r> PgRecordDecoder<'r> {
             }
 
             PgValueFormat::Text => {
-                let mut element = String::new();
+                // the element is collected byte by byte, as the delimiters are all ASCII
+                // and never part of a multi-byte UTF-8 character
+                let mut element = Vec::new();
                 let mut quoted = false;
                 let mut in_quotes = false;
                 let mut in_escape = false;
-                let mut prev_ch = '\0';
+                let mut prev_ch = b'\0';
 
                 while !self.buf.is_empty() {
-                    let ch = self.buf.get_u8() as char;
+                    let ch = self.buf.get_u8();
                     match ch {
                         _ if in_escape => {
                             element.push(ch);
                             in_escape = false;
                         }
 
-                        '"' if in_quotes => {
+                        b'"' if in_quotes => {
                             in_quotes = false;
                         }
 
-                        '"' => {
+                        b'"' => {
                             in_quotes = true;
                             quoted = true;
 
-                            if prev_ch == '"' {
-                                element.push('"')
+                            if prev_ch == b'"' {
+                                element.push(b'"')
                             }
                         }
 
-                        '\\' if !in_escape => {
+                        b'\\' if !in_escape => {
                             in_escape = true;
                         }
 
-                        ',' if !in_quotes => break,
+                        b',' if !in_quotes => break,
 
                         _ => {
                             element.push(ch);
@@ -185,7 +188,7 @@ impl<'r> PgRecordDecoder<'r> {
                     // completely empty input means NULL
                     None
                 } else {
-                    Some(element.as_bytes())
+                    Some(&*element)
                 };
 
                 // NOTE: we do not call [`accepts`] or give a chance to from a user as
@@ -36,6 +36,10 @@ impl TryFrom<PgNumeric> for Decimal {
             PgNumeric::NotANumber => {
                 return Err("Decimal does not support NaN values".into());
             }
+
+            PgNumeric::Infinity | PgNumeric::NegativeInfinity => {
+                return Err("Decimal does not support infinite values".into());
+            }
         };
 
         if digits.is_empty() {
@@ -1,6 +1,8 @@
 use futures::TryStreamExt;
-use sqlx::mysql::{MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow};
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
+use sqlx::mysql::{
+    MySql, MySqlConnectOptions, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow,
+};
+use sqlx::{Column, Connection, Executor, IsolationLevel, Row, Statement, TxOptions, TypeInfo};
 use sqlx_test::{new, setup_if_needed};
 use std::env;
 
@@ -98,6 +100,175 @@ async fn it_executes_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_returns_the_ok_packet_of_a_statement() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER AUTO_INCREMENT PRIMARY KEY)")
+        .await?;
+
+    let done = conn
+        .execute("INSERT INTO users () VALUES (), (), ()")
+        .await?;
+
+    assert_eq!(done.rows_affected(), 3);
+    // the id of the first row inserted by the statement
+    assert_eq!(done.last_insert_id(), 1);
+    assert_eq!(done.warnings(), 0);
+
+    let done = sqlx::query("INSERT INTO users () VALUES ()")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.last_insert_id(), 4);
+
+    // a note is raised for a table that does not exist
+    let done = conn
+        .execute("DROP TEMPORARY TABLE IF EXISTS no_such_table")
+        .await?;
+
+    assert_eq!(done.warnings(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_shows_warnings() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("DROP TEMPORARY TABLE IF EXISTS no_such_table")
+        .await?;
+
+    let warnings = conn.show_warnings().await?;
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].level(), "Note");
+    // ER_BAD_TABLE_ERROR
+    assert_eq!(warnings[0].code(), 1051);
+
+    let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let handler_seen = seen.clone();
+
+    conn.set_warning_handler(move |warnings| {
+        handler_seen
+            .lock()
+            .unwrap()
+            .extend(warnings.iter().map(|w| w.code()));
+    });
+
+    conn.execute("SELECT 1").await?;
+    assert!(seen.lock().unwrap().is_empty());
+
+    conn.execute("DROP TEMPORARY TABLE IF EXISTS no_such_table")
+        .await?;
+    assert_eq!(*seen.lock().unwrap(), [1051]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_recovers_from_an_error_part_way_through_rows() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // the correlated subquery fails with ER_SUBQUERY_NO_1_ROW only for the third row,
+    // after the first rows have been sent
+    let sql = "SELECT IF(n = 3, (SELECT t.n UNION ALL SELECT t.n), n) FROM \
+        (SELECT 1 AS n UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4) t";
+
+    let mut rows = conn.fetch(sql);
+
+    let err = loop {
+        match rows.try_next().await {
+            Ok(Some(_)) => {}
+            Ok(None) => panic!("expected an error"),
+            Err(e) => break e,
+        }
+    };
+
+    drop(rows);
+
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("21000")
+    );
+
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    // the error of a query whose rows were abandoned is not returned by the next query
+    let mut rows = conn.fetch(sql);
+    rows.try_next().await?;
+    drop(rows);
+
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_refuses_commands_larger_than_max_allowed_packet() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let max = conn
+        .max_allowed_packet()
+        .expect("max_allowed_packet is read on connect");
+
+    let res = sqlx::query("SELECT LENGTH(?)")
+        .bind(vec![0_u8; max as usize])
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Encode(_))));
+
+    // nothing was sent, so the connection is still usable
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_out_params_of_procedures() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    sqlx::raw_sql(
+        r#"
+DROP PROCEDURE IF EXISTS add_one;
+CREATE PROCEDURE add_one(IN n INT, OUT m INT, INOUT total INT)
+BEGIN
+    SET m = n + 1;
+    SET total = total + m;
+END;
+        "#,
+    )
+    .execute(&mut conn)
+    .await?;
+
+    let done = sqlx::query("CALL add_one(?, ?, ?)")
+        .bind(1_i32)
+        .bind(None::<i32>)
+        .bind(10_i32)
+        .execute(&mut conn)
+        .await?;
+
+    let out = done.out_params().expect("expected OUT parameters");
+
+    assert!(out.is_out_params());
+    assert_eq!(out.try_get::<i32, _>(0)?, 2);
+    assert_eq!(out.try_get::<i32, _>(1)?, 12);
+
+    // a statement without OUT parameters
+    let done = sqlx::query("SELECT ?")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await?;
+
+    assert!(done.out_params().is_none());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_works_with_cache_disabled() -> anyhow::Result<()> {
     setup_if_needed();
@@ -133,6 +304,425 @@ async fn it_drops_results_in_affected_rows() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_executes_multiple_statements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let results: Vec<_> = sqlx::raw_sql(
+        r#"
+CREATE TEMPORARY TABLE seeds (id INTEGER PRIMARY KEY);
+INSERT INTO seeds (id) VALUES (1), (2), (3);
+SELECT id FROM seeds ORDER BY id;
+UPDATE seeds SET id = id + 10 WHERE id > 1;
+SELECT COUNT(*) FROM seeds WHERE id > 10;
+        "#,
+    )
+    .fetch_many(&mut conn)
+    .try_collect()
+    .await?;
+
+    let rows_affected: Vec<u64> = results
+        .iter()
+        .filter_map(|v| v.as_ref().left())
+        .map(|done| done.rows_affected())
+        .collect();
+
+    // one result per statement
+    assert_eq!(rows_affected, [0, 3, 0, 2, 0]);
+
+    let rows: Vec<i64> = results
+        .iter()
+        .filter_map(|v| v.as_ref().right())
+        .map(|row| row.try_get(0))
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(rows, [1, 2, 3, 2]);
+
+    // an error stops the script and leaves the connection usable
+    let res = sqlx::raw_sql("INSERT INTO seeds (id) VALUES (4); SELECT * FROM nope; SELECT 1")
+        .execute(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM seeds")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 4);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_multiple_result_sets_from_procedures() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    sqlx::raw_sql(
+        r#"
+DROP PROCEDURE IF EXISTS two_result_sets;
+CREATE PROCEDURE two_result_sets(IN n INT)
+BEGIN
+    SELECT n AS a;
+    SELECT 'x' AS b, n + 1 AS c UNION ALL SELECT 'y', n + 2;
+END;
+        "#,
+    )
+    .execute(&mut conn)
+    .await?;
+
+    // the text protocol and the binary protocol, where the procedure was prepared without columns
+    for persistent in [None, Some(true), Some(false)] {
+        let results: Vec<_> = match persistent {
+            None => {
+                sqlx::raw_sql("CALL two_result_sets(1)")
+                    .fetch_many(&mut conn)
+                    .try_collect()
+                    .await?
+            }
+            Some(persistent) => {
+                sqlx::query("CALL two_result_sets(?)")
+                    .bind(1_i32)
+                    .persistent(persistent)
+                    .fetch_many(&mut conn)
+                    .try_collect()
+                    .await?
+            }
+        };
+
+        let rows: Vec<&MySqlRow> = results.iter().filter_map(|v| v.as_ref().right()).collect();
+
+        assert_eq!(rows.len(), 3);
+
+        assert_eq!(rows[0].columns().len(), 1);
+        assert_eq!(rows[0].try_get::<i64, _>("a")?, 1);
+
+        assert_eq!(rows[1].columns().len(), 2);
+        assert_eq!(rows[1].try_get::<String, _>("b")?, "x");
+        assert_eq!(rows[1].try_get::<i64, _>("c")?, 2);
+        assert_eq!(rows[2].try_get::<String, _>("b")?, "y");
+
+        // each result set and the status of the `CALL` itself
+        assert_eq!(results.iter().filter(|v| v.is_left()).count(), 3);
+
+        // the connection is left ready for the next query
+        let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+        assert_eq!(one, 1);
+    }
+
+    sqlx::raw_sql("DROP PROCEDURE two_result_sets")
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_loads_data_local_infile() -> anyhow::Result<()> {
+    use futures::stream::{self, StreamExt};
+
+    setup_if_needed();
+
+    let mut url = url::Url::parse(&env::var("DATABASE_URL")?)?;
+    url.query_pairs_mut().append_pair("local-infile", "true");
+
+    let mut conn = MySqlConnection::connect(url.as_ref()).await?;
+
+    // disabled by default since MySQL 8.0
+    conn.execute("SET GLOBAL local_infile = 1").await?;
+
+    conn.execute("CREATE TEMPORARY TABLE numbers (id INT PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    conn.set_local_infile_handler(|filename| {
+        assert_eq!(filename, "numbers.csv");
+
+        stream::iter([Ok("1,one\n2,".into()), Ok("two\n3,three\n".into())]).boxed()
+    });
+
+    let done = conn
+        .execute("LOAD DATA LOCAL INFILE 'numbers.csv' INTO TABLE numbers FIELDS TERMINATED BY ','")
+        .await?;
+
+    assert_eq!(done.rows_affected(), 3);
+
+    // a failing handler fails the statement and leaves the connection usable
+    conn.set_local_infile_handler(|_| {
+        stream::iter([Err(sqlx::Error::Protocol("no more numbers".into()))]).boxed()
+    });
+
+    let res = conn
+        .execute("LOAD DATA LOCAL INFILE 'more.csv' INTO TABLE numbers FIELDS TERMINATED BY ','")
+        .await;
+
+    assert!(res.is_err());
+
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM numbers ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(names, ["one", "two", "three"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sends_long_data() -> anyhow::Result<()> {
+    use sqlx::mysql::MySqlArguments;
+    use sqlx::Arguments;
+
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE blobs (id INT PRIMARY KEY, data LONGBLOB NOT NULL)")
+        .await?;
+
+    // more than one chunk
+    let data: Vec<u8> = (0..3 * 1024 * 1024 + 7).map(|i| i as u8).collect();
+
+    let mut arguments = MySqlArguments::default();
+    arguments.add(1_i32).map_err(sqlx::Error::Encode)?;
+    arguments.add_long_data(futures::io::Cursor::new(data.clone()));
+
+    sqlx::query_with(
+        "INSERT INTO blobs (id, data) VALUES (?, ?)",
+        arguments.clone(),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    let stored: Vec<u8> = sqlx::query_scalar("SELECT data FROM blobs WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(stored, data);
+
+    // the value can only be sent once
+    let res = sqlx::query_with("INSERT INTO blobs (id, data) VALUES (?, ?)", arguments)
+        .execute(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM blobs")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_rows_from_a_cursor() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let mut url = url::Url::parse(&env::var("DATABASE_URL")?)?;
+    url.query_pairs_mut().append_pair("cursor-fetch-size", "2");
+
+    let mut conn = MySqlConnection::connect(url.as_ref()).await?;
+
+    let sql = "SELECT n FROM (SELECT 1 AS n UNION ALL SELECT 2 UNION ALL SELECT 3 \
+               UNION ALL SELECT 4 UNION ALL SELECT 5) AS t WHERE n > ? ORDER BY n";
+
+    // three batches, the last of which is not full
+    let rows: Vec<i64> = sqlx::query_scalar(sql).bind(0).fetch_all(&mut conn).await?;
+    assert_eq!(rows, [1, 2, 3, 4, 5]);
+
+    // a multiple of the batch size
+    let rows: Vec<i64> = sqlx::query_scalar(sql).bind(1).fetch_all(&mut conn).await?;
+    assert_eq!(rows, [2, 3, 4, 5]);
+
+    let rows: Vec<i64> = sqlx::query_scalar(sql).bind(5).fetch_all(&mut conn).await?;
+    assert!(rows.is_empty());
+
+    // abandoning the cursor leaves the connection usable
+    let first: i64 = sqlx::query_scalar(sql).bind(0).fetch_one(&mut conn).await?;
+    assert_eq!(first, 1);
+
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resets_connections_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .after_release(|conn, _meta| {
+            Box::pin(async move {
+                conn.reset().await?;
+                Ok(true)
+            })
+        })
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    conn.execute("SET @answer = 42, time_zone = '+01:00'")
+        .await?;
+    conn.execute("CREATE TEMPORARY TABLE answers (id INT PRIMARY KEY)")
+        .await?;
+    let _: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(1)
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(conn.cached_statements_size(), 1);
+
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+
+    // the statements closed by the server are no longer cached
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    let answer: Option<i64> = sqlx::query_scalar("SELECT @answer")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(answer, None);
+
+    // the session variables set on connect are set again
+    let time_zone: String = sqlx::query_scalar("SELECT @@time_zone")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(time_zone, "+00:00");
+
+    assert!(conn.execute("SELECT * FROM answers").await.is_err());
+
+    let one: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(1)
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_initializes_sessions() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .sql_mode(Some("ANSI_QUOTES".to_owned()))
+        .pipes_as_concat(false)
+        .no_engine_subsitution(false)
+        .init_statement("SET @greeting = 'hello'")
+        .init_statement("SET @greeting = CONCAT(@greeting, ', world')");
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    let sql_mode: String = sqlx::query_scalar("SELECT @@sql_mode")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(sql_mode, "ANSI_QUOTES");
+
+    let greeting: String = sqlx::query_scalar("SELECT @greeting")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(greeting, "hello, world");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_validates_rows() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .validate_rows(true);
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    // binary protocol
+    let row: (i32, Option<String>, f64) = sqlx::query_as("SELECT ?, ?, ?")
+        .bind(42_i32)
+        .bind(None::<String>)
+        .bind(1.5_f64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(row, (42, None, 1.5));
+
+    // text protocol
+    let value: i64 = sqlx::query_scalar("SELECT CAST(42 AS SIGNED)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 42);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_statements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let handle = conn.cancel_handle();
+
+    let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(id, u64::from(handle.connection_id()));
+
+    let start = std::time::Instant::now();
+
+    let (res, cancelled) = futures::join!(conn.execute("SELECT SLEEP(30)"), async {
+        sqlx_core::rt::sleep(std::time::Duration::from_millis(500)).await;
+        handle.cancel().await
+    });
+
+    cancelled?;
+
+    // an interrupted `SLEEP()` returns 1 rather than failing
+    res?;
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sends_query_attributes() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // `mysql_query_attribute_string()` requires the `query_attributes` component
+    if conn
+        .execute("SELECT mysql_query_attribute_string('trace_id')")
+        .await
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    conn.set_query_attribute("trace_id", "4bf92f3577b34da6");
+
+    let text: Option<String> = sqlx::raw_sql("SELECT mysql_query_attribute_string('trace_id')")
+        .fetch_one(&mut conn)
+        .await?
+        .try_get(0)?;
+    assert_eq!(text.as_deref(), Some("4bf92f3577b34da6"));
+
+    let prepared: Option<String> = sqlx::query_scalar("SELECT mysql_query_attribute_string(?)")
+        .bind("trace_id")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(prepared.as_deref(), Some("4bf92f3577b34da6"));
+
+    conn.clear_query_attributes();
+
+    let cleared: Option<String> =
+        sqlx::query_scalar("SELECT mysql_query_attribute_string('trace_id')")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(cleared, None);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_selects_null() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -378,6 +968,16 @@ async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     assert_eq!(statement.column(2).type_info().name(), "TEXT");
     assert_eq!(statement.column(3).type_info().name(), "BIGINT");
 
+    assert_eq!(statement.parameters(), Some(sqlx::Either::Right(1)));
+
+    assert!(statement.column(0).is_primary_key());
+    assert!(statement.column(0).is_auto_increment());
+    assert!(!statement.column(0).is_unsigned());
+    assert_eq!(statement.column(0).is_nullable(), Some(false));
+    assert_eq!(statement.column(2).is_nullable(), Some(false));
+    assert_eq!(statement.column(3).is_nullable(), Some(true));
+    assert!(!statement.column(3).is_primary_key());
+
     let row = statement.query().bind(tweet_id).fetch_one(&mut *tx).await?;
     let tweet_text: &str = row.try_get("text")?;
 
@@ -500,6 +1100,96 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    sqlx::raw_sql("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY);")
+        .execute(&mut conn)
+        .await?;
+
+    let mut tx = conn.begin().await?;
+    assert_eq!(tx.transaction_depth(), 1);
+
+    sqlx::query("INSERT INTO users (id) VALUES (?)")
+        .bind(1_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    // a savepoint, rolled back
+    let mut savepoint = tx.begin().await?;
+    assert_eq!(savepoint.transaction_depth(), 2);
+
+    sqlx::query("INSERT INTO users (id) VALUES (?)")
+        .bind(2_i32)
+        .execute(&mut *savepoint)
+        .await?;
+    savepoint.rollback().await?;
+    assert_eq!(tx.transaction_depth(), 1);
+
+    // a savepoint, released
+    let mut savepoint = tx.begin().await?;
+    sqlx::query("INSERT INTO users (id) VALUES (?)")
+        .bind(3_i32)
+        .execute(&mut *savepoint)
+        .await?;
+
+    // a savepoint of the savepoint, dropped
+    {
+        let mut inner = savepoint.begin().await?;
+        assert_eq!(inner.transaction_depth(), 3);
+
+        sqlx::query("INSERT INTO users (id) VALUES (?)")
+            .bind(4_i32)
+            .execute(&mut *inner)
+            .await?;
+    }
+
+    savepoint.commit().await?;
+    tx.commit().await?;
+    assert_eq!(conn.transaction_depth(), 0);
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM users ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(ids, [1, 3]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_begin_transactions_with_options() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let mut tx = conn
+        .begin_with(
+            TxOptions::new()
+                .isolation(IsolationLevel::Serializable)
+                .read_only(),
+        )
+        .await?;
+
+    // ER_CANT_EXECUTE_IN_READ_ONLY_TRANSACTION
+    let err = sqlx::query("INSERT INTO tweet (text) VALUES ('read only')")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.as_database_error().unwrap().code().as_deref(),
+        Some("25006")
+    );
+
+    tx.rollback().await?;
+
+    // deferrable transactions are specific to Postgres
+    assert!(conn
+        .begin_with(TxOptions::new().deferrable())
+        .await
+        .is_err());
+    assert_eq!(conn.transaction_depth(), 0);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_handle_split_packets() -> anyhow::Result<()> {
     // This will only take effect on new connections
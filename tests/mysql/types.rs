@@ -26,6 +26,7 @@ test_type!(u32(MySql, "CAST(2141512 AS UNSIGNED)" == 2141512_u32));
 test_type!(i32(MySql, "2141512" == 2141512_i32));
 
 test_type!(u64(MySql, "CAST(2141512 AS UNSIGNED)" == 2141512_u64));
+test_type!(u64_max<u64>(MySql, "CAST(18446744073709551615 AS UNSIGNED)" == u64::MAX));
 test_type!(i64(MySql, "2141512" == 2141512_i64));
 
 test_type!(f64(MySql, "3.14159265e0" == 3.14159265_f64));
@@ -175,6 +175,40 @@ test_type!(floatrange<FloatRange>(Postgres,
     "'[1.234, 5.678]'::float_range" == FloatRange(PgRange::from((Bound::Included(1.234), Bound::Included(5.678)))),
 ));
 
+#[sqlx_macros::test]
+async fn test_enum_type_not_found() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::Type)]
+    #[sqlx(type_name = "this_enum_does_not_exist")]
+    enum Missing {
+        One,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let res = sqlx::query("SELECT $1::text")
+        .bind(Missing::One)
+        .execute(&mut conn)
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::TypeNotFound { type_name }) if type_name == "this_enum_does_not_exist"
+    ));
+
+    let res = sqlx::query("SELECT $1::text[]")
+        .bind(vec![Missing::One])
+        .execute(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::TypeNotFound { .. })));
+
+    // the connection is still usable
+    let (one,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_enum_type() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -391,6 +425,22 @@ SELECT $1 = ROW('fuzzy dice', 42, 199)::inventory_item, $1
     assert!(rec.0);
     assert_eq!(rec.1, value);
 
+    // records in the text format of unprepared queries
+    let row = conn
+        .fetch_one(r#"SELECT ROW('dés "en" peluche, rosés', NULL, 5)::inventory_item"#)
+        .await?;
+
+    let item: InventoryItem = sqlx::Row::try_get(&row, 0)?;
+
+    assert_eq!(
+        item,
+        InventoryItem {
+            name: r#"dés "en" peluche, rosés"#.to_owned(),
+            supplier_id: None,
+            price: Some(5),
+        }
+    );
+
     Ok(())
 }
 
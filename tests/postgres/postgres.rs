@@ -3,9 +3,9 @@ use futures::{Stream, StreamExt, TryStreamExt};
 use sqlx::postgres::types::Oid;
 use sqlx::postgres::{
     PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
-    PgPoolOptions, PgRow, PgSeverity, Postgres,
+    PgPoolOptions, PgRow, PgSeverity, PgTransactionExt, Postgres,
 };
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
+use sqlx::{Column, Connection, Executor, IsolationLevel, Row, Statement, TxOptions, TypeInfo};
 use sqlx_core::{bytes::Bytes, error::BoxDynError};
 use sqlx_test::{new, pool, setup_if_needed};
 use std::env;
@@ -512,6 +512,59 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_prepare_transactions_for_two_phase_commit() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // a savepoint cannot be prepared
+    let mut tx = conn.begin().await?;
+    let savepoint = tx.begin().await?;
+    assert!(matches!(
+        savepoint.prepare_two_phase("sqlx-savepoint").await,
+        Err(sqlx::Error::Configuration(_))
+    ));
+    tx.rollback().await?;
+
+    let max: String = sqlx::query_scalar("SELECT current_setting('max_prepared_transactions')")
+        .fetch_one(&mut conn)
+        .await?;
+
+    if max == "0" {
+        // prepared transactions are disabled on the server
+        return Ok(());
+    }
+
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_two_phase (id INTEGER PRIMARY KEY)")
+        .await?;
+    conn.execute("DELETE FROM _sqlx_two_phase").await?;
+
+    for (gid, commit) in [("sqlx-commit", true), ("sqlx-rollback", false)] {
+        let mut tx = conn.begin().await?;
+        sqlx::query("INSERT INTO _sqlx_two_phase (id) VALUES ($1)")
+            .bind(commit as i32)
+            .execute(&mut *tx)
+            .await?;
+        tx.prepare_two_phase(gid).await?;
+
+        // the connection is no longer in a transaction, and can finish it itself
+        if commit {
+            conn.commit_prepared(gid).await?;
+        } else {
+            conn.rollback_prepared(gid).await?;
+        }
+    }
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM _sqlx_two_phase")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, [1]);
+
+    conn.execute("DROP TABLE _sqlx_two_phase").await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -562,6 +615,95 @@ async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_tracks_the_transaction_depth() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut tx = conn.begin().await?;
+    assert_eq!(tx.transaction_depth(), 1);
+
+    let savepoint = tx.begin().await?;
+    assert_eq!(savepoint.transaction_depth(), 2);
+    savepoint.commit().await?;
+    assert_eq!(tx.transaction_depth(), 1);
+
+    tx.commit().await?;
+    assert_eq!(conn.transaction_depth(), 0);
+
+    let tx = conn.begin().await?;
+    tx.rollback().await?;
+    assert_eq!(conn.transaction_depth(), 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resets_the_transaction_depth_when_a_transaction_is_ended_by_a_query(
+) -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut tx = conn.begin().await?;
+    let savepoint = tx.begin().await?;
+    assert_eq!(savepoint.transaction_depth(), 2);
+
+    // forget the savepoint without releasing it, then end the transaction by hand
+    std::mem::forget(savepoint);
+    tx.execute("COMMIT").await?;
+    assert_eq!(tx.transaction_depth(), 0);
+
+    // nothing is left to commit
+    tx.commit().await?;
+
+    // the next transaction does not try to create a savepoint
+    let tx = conn.begin().await?;
+    assert_eq!(tx.transaction_depth(), 1);
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_begin_transactions_with_options() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut tx = conn
+        .begin_with(
+            TxOptions::new()
+                .isolation(IsolationLevel::Serializable)
+                .read_only()
+                .deferrable(),
+        )
+        .await?;
+
+    let isolation: String = sqlx::query_scalar("SHOW transaction_isolation")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(isolation, "serializable");
+
+    let read_only: String = sqlx::query_scalar("SHOW transaction_read_only")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(read_only, "on");
+
+    // options cannot be set on a savepoint
+    assert!(tx.begin_with(TxOptions::new().read_only()).await.is_err());
+    assert_eq!(tx.transaction_depth(), 1);
+
+    tx.rollback().await?;
+
+    // the options only apply to that transaction
+    let mut tx = conn.begin().await?;
+
+    let read_only: String = sqlx::query_scalar("SHOW transaction_read_only")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(read_only, "off");
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -813,6 +955,83 @@ async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_closes_statements_not_stored_in_the_cache() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // unprepared queries, so they do not show up themselves
+    let count_query = "SELECT COUNT(*) FROM pg_prepared_statements";
+    let before: i64 = conn.fetch_one(count_query).await?.get(0);
+
+    for i in 0..5_i32 {
+        let value: i32 = sqlx::query_scalar("SELECT $1")
+            .bind(i)
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i);
+    }
+
+    // a failing query must not leak its statement either
+    let res = sqlx::query("SELECT 1 / $1")
+        .bind(0_i32)
+        .persistent(false)
+        .execute(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    let after: i64 = conn.fetch_one(count_query).await?.get(0);
+    assert_eq!(before, after);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_rows_in_batches_from_a_portal() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.cursor_fetch_size(3);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let values: Vec<i32> = sqlx::query_scalar("SELECT generate_series(1, $1)")
+        .bind(10_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(values, (1..=10).collect::<Vec<_>>());
+
+    // stop reading part way through the rows
+    {
+        let mut s = sqlx::query_scalar::<_, i32>("SELECT generate_series(1, $1)")
+            .bind(100_i32)
+            .fetch(&mut conn);
+
+        assert_eq!(s.try_next().await?, Some(1));
+    }
+
+    // fail part way through the rows
+    let res = sqlx::query_scalar::<_, i32>("SELECT 10 / (5 - generate_series(1, $1))")
+        .bind(10_i32)
+        .fetch_all(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    // the connection is still usable
+    let value: i32 = sqlx::query_scalar("SELECT $1")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_sets_application_name() -> anyhow::Result<()> {
     sqlx_test::setup_if_needed();
@@ -833,6 +1052,30 @@ async fn it_sets_application_name() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_sets_startup_options_with_spaces() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.options([
+        ("search_path", "pg_catalog, public"),
+        ("statement_timeout", "5 min"),
+    ]);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let (search_path, statement_timeout): (String, String) = sqlx::query_as(
+        "SELECT current_setting('search_path'), current_setting('statement_timeout')",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(search_path, "pg_catalog, public");
+    assert_eq!(statement_timeout, "5min");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
     new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
@@ -1057,6 +1300,114 @@ async fn test_listener_cleanup() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_cancels_a_running_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let cancel = conn.cancel_handle();
+
+    sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(Duration::from_millis(200)).await;
+        cancel.cancel().await.unwrap();
+    });
+
+    let err = conn
+        .execute("SELECT pg_sleep(30)")
+        .await
+        .unwrap_err()
+        .into_database_error()
+        .unwrap();
+
+    // query_canceled
+    assert_eq!(err.code().as_deref(), Some("57014"));
+
+    // the connection is still usable
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_queries_in_a_pipeline() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE pipeline_test (id INT4 PRIMARY KEY)")
+        .await?;
+
+    let results = conn
+        .pipeline()
+        .push(sqlx::query("INSERT INTO pipeline_test (id) VALUES ($1)").bind(1_i32))
+        .push(
+            sqlx::query("INSERT INTO pipeline_test (id) VALUES ($1), ($2)")
+                .bind(2_i32)
+                .bind(3_i32),
+        )
+        .push(sqlx::query("SELECT id FROM pipeline_test ORDER BY id"))
+        .fetch_all()
+        .await?;
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_empty());
+    assert!(results[1].is_empty());
+
+    let ids: Vec<i32> = results[2].iter().map(|row| row.get(0)).collect();
+    assert_eq!(ids, [1, 2, 3]);
+
+    // the second query fails, which rolls back the first one and skips the third
+    let res = conn
+        .pipeline()
+        .push(sqlx::query("INSERT INTO pipeline_test (id) VALUES ($1)").bind(4_i32))
+        .push(sqlx::query("INSERT INTO pipeline_test (id) VALUES ($1)").bind(1_i32))
+        .push(sqlx::query("INSERT INTO pipeline_test (id) VALUES ($1)").bind(5_i32))
+        .execute()
+        .await;
+
+    assert!(res.is_err());
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pipeline_test")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_passes_notices_to_the_handler() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let notices = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let handler_notices = Arc::clone(&notices);
+
+    conn.set_notice_handler(move |notice| {
+        handler_notices.lock().unwrap().push((
+            notice.severity(),
+            notice.code().to_owned(),
+            notice.message().to_owned(),
+        ));
+    });
+
+    conn.execute("DO $$ BEGIN RAISE NOTICE 'hello %', 1; RAISE WARNING 'careful'; END $$")
+        .await?;
+
+    let notices = notices.lock().unwrap();
+
+    assert_eq!(
+        *notices,
+        [
+            (PgSeverity::Notice, "00000".to_owned(), "hello 1".to_owned()),
+            (
+                PgSeverity::Warning,
+                "01000".to_owned(),
+                "careful".to_owned()
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
     let pool = pool::<Postgres>().await?;
@@ -1516,6 +1867,21 @@ async fn test_pg_server_num() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_pg_parameter_status() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    assert!(conn.parameter("server_version").is_some());
+    assert_eq!(conn.parameter("client_encoding"), Some("UTF8"));
+    assert_eq!(conn.parameter("not_a_parameter"), None);
+
+    conn.execute("SET TIME ZONE 'Europe/Paris'").await?;
+
+    assert_eq!(conn.parameter("TimeZone"), Some("Europe/Paris"));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_copy_in() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -1549,6 +1915,41 @@ async fn it_can_copy_in() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_copy_in_through_a_sink() -> anyhow::Result<()> {
+    use futures::SinkExt;
+
+    let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
+    "#,
+    )
+    .await?;
+
+    let mut copy = conn
+        .copy_in_raw("COPY users (id) FROM STDIN WITH (FORMAT CSV)")
+        .await?;
+
+    let mut lines = futures::stream::iter(
+        (1..=1000).map(|id| Ok::<_, sqlx::Error>(format!("{id}\n").into_bytes())),
+    );
+
+    copy.send_all(&mut lines).await?;
+    SinkExt::<Vec<u8>>::close(&mut copy).await?;
+
+    let rows = copy.finish().await?;
+    assert_eq!(rows, 1000);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1000);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_abort_copy_in() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -1833,6 +2234,28 @@ async fn test_advisory_locks() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_transaction_advisory_locks() -> anyhow::Result<()> {
+    let mut conn1 = new::<Postgres>().await?;
+    let mut conn2 = new::<Postgres>().await?;
+
+    let lock = PgAdvisoryLock::new("sqlx-postgres-tests-xact");
+
+    let mut tx1 = conn1.begin().await?;
+    lock.acquire_for_transaction(&mut tx1).await?;
+
+    let mut tx2 = conn2.begin().await?;
+    assert!(!lock.try_acquire_for_transaction(&mut tx2).await?);
+
+    // ending the transaction releases the lock
+    tx1.commit().await?;
+
+    assert!(lock.try_acquire_for_transaction(&mut tx2).await?);
+    tx2.rollback().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_postgres_bytea_hex_deserialization_errors() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -1934,3 +2357,107 @@ async fn test_issue_3052() {
         "expected encode error, got {too_large_error:?}",
     );
 }
+
+#[sqlx_macros::test]
+async fn it_reads_and_writes_large_objects() -> anyhow::Result<()> {
+    use futures::{AsyncReadExt, AsyncWriteExt};
+    use sqlx::postgres::{PgLargeObject, PgLargeObjectMode};
+    use std::io::SeekFrom;
+
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    let mut object = PgLargeObject::create(&mut *tx).await?;
+    let oid = object.oid();
+
+    assert_eq!(object.write(b"Hello, world!").await?, 13);
+    assert_eq!(object.seek(SeekFrom::Start(7)).await?, 7);
+    assert_eq!(object.read(100).await?, b"world!");
+    assert_eq!(object.tell().await?, 13);
+
+    object.truncate(5).await?;
+    object.close().await?;
+
+    let object = PgLargeObject::open(&mut *tx, oid, PgLargeObjectMode::ReadWrite).await?;
+    let mut io = object.into_io();
+
+    let mut hello = [0; 5];
+    io.read_exact(&mut hello).await?;
+    assert_eq!(&hello, b"Hello");
+
+    // larger than the write buffer
+    let data = vec![b'!'; 100_000];
+    io.write_all(&data).await?;
+    io.flush().await?;
+
+    let mut object = io.into_inner();
+    object.seek(SeekFrom::Start(0)).await?;
+
+    let mut io = object.into_io();
+    let mut contents = Vec::new();
+    io.read_to_end(&mut contents).await?;
+
+    assert_eq!(contents.len(), 100_005);
+    assert!(contents.starts_with(b"Hello!!"));
+
+    io.into_inner().close().await?;
+    PgLargeObject::unlink(&mut *tx, oid).await?;
+
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_largeobject_metadata WHERE oid = $1)")
+            .bind(oid)
+            .fetch_one(&mut *tx)
+            .await?;
+
+    assert!(!exists);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_columns_without_binary_output_as_text() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `aclitem` has no binary output function, so the server refuses to send it in binary
+    let row = sqlx::query(
+        "SELECT acldefault('r', oid), $1::int4 FROM pg_roles WHERE rolname = current_user",
+    )
+    .bind(42_i32)
+    .fetch_one(&mut conn)
+    .await?;
+
+    let acl: String = row.try_get_unchecked(0)?;
+    assert!(acl.starts_with('{'), "{acl}");
+
+    // the other columns are still sent in binary
+    assert_eq!(row.try_get::<i32, _>(1)?, 42);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_object_identifier_types_as_oid() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let (class, oid): (Oid, Oid) =
+        sqlx::query_as("SELECT 'pg_class'::regclass, 'pg_class'::regclass::oid")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(class, oid);
+
+    let types: Vec<Oid> = sqlx::query_scalar("SELECT ARRAY['int4'::regtype, 'text'::regtype]")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(types, [Oid(23), Oid(25)]);
+
+    let class: Oid = sqlx::query_scalar("SELECT relname::regclass FROM pg_class WHERE oid = $1")
+        .bind(oid)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(class, oid);
+
+    Ok(())
+}
@@ -83,6 +83,14 @@ test_type!(string_vec<Vec<String>>(Postgres,
 
     "array['Hello, World', '', 'Goodbye']::text[]"
         == vec!["Hello, World", "", "Goodbye"],
+
+    "array['NULL', 'null']::text[]"
+        == vec!["NULL", "null"],
+));
+
+test_type!(null_string_vec<Vec<Option<String>>>(Postgres,
+    "array[NULL, 'NULL']::text[]"
+        == vec![None, Some("NULL".to_owned())],
 ));
 
 test_type!(string_array<[String; 3]>(Postgres,
@@ -207,6 +215,15 @@ test_type!(mac_address<sqlx::types::mac_address::MacAddress>(Postgres,
             .unwrap()
 ));
 
+// only EUI-48 addresses fit in a `MacAddress`; they are sent as such to a `macaddr8`
+#[cfg(feature = "mac_address")]
+test_type!(mac_address_macaddr8<sqlx::types::mac_address::MacAddress>(Postgres,
+    "'00:01:02:03:04:05'::macaddr8"
+        == "00:01:02:03:04:05"
+            .parse::<sqlx::types::mac_address::MacAddress>()
+            .unwrap()
+));
+
 #[cfg(feature = "bit-vec")]
 test_type!(bitvec<sqlx::types::BitVec>(
     Postgres,
@@ -298,6 +315,12 @@ mod chrono {
             ]
     ));
 
+    // the lower bound is quoted in the text format, the upper bound is empty
+    test_type!(chrono_date_time_range<PgRange<NaiveDateTime>>(Postgres,
+        "'[2019-01-02 05:10:20,)'::tsrange"
+            == PgRange::from(NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap()..)
+    ));
+
     test_type!(chrono_time_tz<PgTimeTz>(Postgres,
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(5, 10, 20, 115100).unwrap(), offset: FixedOffset::east_opt(0).unwrap() },
         "TIMETZ '05:10:20.115100+06:30'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(5, 10, 20, 115100).unwrap(), offset: FixedOffset::east_opt(60 * 60 * 6 + 1800).unwrap() },
@@ -475,6 +498,21 @@ test_type!(numrange_bigdecimal<PgRange<sqlx::types::BigDecimal>>(Postgres,
          Bound::Excluded("2.4".parse::<sqlx::types::BigDecimal>().unwrap())))
 ));
 
+test_type!(hstore<std::collections::HashMap<String, Option<String>>>(Postgres,
+    "''::hstore" == std::collections::HashMap::<String, Option<String>>::new(),
+    "'\"a\"=>\"1\", \"b\"=>NULL, \"c d\"=>\"\"'::hstore" == std::collections::HashMap::from([
+        ("a".to_string(), Some("1".to_string())),
+        ("b".to_string(), None),
+        ("c d".to_string(), Some("".to_string())),
+    ]),
+));
+
+test_type!(pg_hstore<sqlx::postgres::types::PgHstore>(Postgres,
+    "'\"key\"=>\"value\"'::hstore" == sqlx::postgres::types::PgHstore::from_iter([
+        ("key".to_string(), "value".to_string()),
+    ]),
+));
+
 #[cfg(any(postgres_14, postgres_15))]
 test_type!(cube<sqlx::postgres::types::PgCube>(Postgres,
     "cube(2)" == sqlx::postgres::types::PgCube::Point(2.),
@@ -537,6 +575,11 @@ test_type!(int4range<PgRange<i32>>(Postgres,
     "'[1,2]'::int4range" == PgRange::from((INC1, EXC3)),
 ));
 
+// an empty range is decoded as unbounded in both formats
+test_decode_type!(int4range_empty<PgRange<i32>>(Postgres,
+    "'empty'::int4range" == PgRange::from((UNB, UNB)),
+));
+
 test_prepared_type!(interval<PgInterval>(
     Postgres,
     "INTERVAL '1h'"